@@ -0,0 +1,37 @@
+//! `cargo bench -p mini-core --bench cmp_strings`
+//!
+//! 比较 [`cmp_strings_case_insensitive`] 和使用 `to_lowercase` 分配新字符串的实现。
+
+use std::{hint::black_box, time::Instant};
+
+use mini_core::utils::cmp_strings_case_insensitive;
+
+const ITERATIONS: u32 = 1_000_000;
+
+const PAIRS: [(&str, &str); 4] = [
+    ("png", "PNG"),
+    ("gltf", "glb"),
+    ("tar.gz", "TAR.GZ"),
+    ("ktx2", "jpeg"),
+];
+
+fn bench(name: &str, f: impl Fn(&str, &str) -> bool) {
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        for (a, b) in PAIRS {
+            black_box(f(black_box(a), black_box(b)));
+        }
+    }
+    let elapsed = start.elapsed();
+    println!(
+        "{name}: {:?} per comparison",
+        elapsed / (ITERATIONS * PAIRS.len() as u32)
+    );
+}
+
+fn main() {
+    bench("cmp_strings_case_insensitive", |a, b| {
+        cmp_strings_case_insensitive(a, b)
+    });
+    bench("to_lowercase", |a, b| a.to_lowercase() == b.to_lowercase());
+}
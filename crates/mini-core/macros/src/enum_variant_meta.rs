@@ -19,24 +19,33 @@ pub fn impl_enum_variant_meta(ast: DeriveInput) -> TokenStream2 {
 
     let variants = ty_args.data.take_enum().unwrap();
 
-    let idents = variants.iter().map(|v| &v.ident);
+    // 根据变体的形式生成匹配的模式，不关心字段
+    let patterns = variants
+        .iter()
+        .map(|v| {
+            let ident = &v.ident;
+            match v.fields {
+                Fields::Unit => quote!(#ty_ident::#ident),
+                Fields::Unnamed(_) => quote!(#ty_ident::#ident(..)),
+                Fields::Named(_) => quote!(#ty_ident::#ident { .. }),
+            }
+        })
+        .collect::<Vec<_>>();
     let names = variants.iter().map(|v| v.ident.to_string());
-    let indices = 0..names.len();
+    let indices = 0..patterns.len();
 
     quote! {
 
         impl #impl_generics #ty_ident #ty_generics #where_clause {
             pub fn enum_variant_index(&self) -> usize {
                 match self {
-                    #(#ty_ident::#idents {..} => #indices,)*
+                    #(#patterns => #indices,)*
                 }
             }
             pub fn enum_variant_name(&self) -> &'static str {
-                static variants: &[&str] = &[
-                    #(#names,)*
-                ];
-                let index = self.enum_variant_index();
-                variants[index]
+                match self {
+                    #(#patterns => #names,)*
+                }
             }
         }
 
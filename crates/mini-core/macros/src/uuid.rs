@@ -1,4 +1,4 @@
-use darling::*;
+use darling::{util::SpannedValue, *};
 use proc_macro2::TokenStream as TokenStream2;
 use quote::*;
 use syn::*;
@@ -8,14 +8,27 @@ use syn::*;
 pub struct TypeArgs {
     pub ident: Ident,
     pub generics: Generics,
-    pub id: String,
+    pub id: SpannedValue<String>,
 }
 
 pub fn impl_type_uuid_provider(ast: DeriveInput) -> TokenStream2 {
-    let ty_args = TypeArgs::from_derive_input(&ast).unwrap();
+    let ty_args = match TypeArgs::from_derive_input(&ast) {
+        Ok(ty_args) => ty_args,
+        Err(e) => return e.write_errors(),
+    };
     let ty_ident = &ty_args.ident;
     let id = &ty_args.id;
 
+    // 在编译期检查 uuid 的格式，错误指向 id 的字面量
+    if let Err(e) = ::uuid::Uuid::parse_str(id) {
+        return syn::Error::new(
+            id.span(),
+            format!("invalid type uuid `{}`: {e}", id.as_str()),
+        )
+        .to_compile_error();
+    }
+    let id = id.as_str();
+
     let (impl_generics, ty_generics, where_clause) = ty_args.generics.split_for_impl();
 
     quote! {
@@ -26,3 +39,30 @@ pub fn impl_type_uuid_provider(ast: DeriveInput) -> TokenStream2 {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_invalid_uuid() {
+        let valid = impl_type_uuid_provider(parse_quote! {
+            #[type_uuid(id = "5a0b1e46-8f7a-4a8e-9a3c-6f1f0e2d9b11")]
+            struct Valid;
+        });
+        assert!(!valid.to_string().contains("compile_error"));
+
+        let invalid = impl_type_uuid_provider(parse_quote! {
+            #[type_uuid(id = "5a0b1e46-8f7a")]
+            struct Invalid;
+        })
+        .to_string();
+        assert!(invalid.contains("compile_error"));
+        assert!(invalid.contains("invalid type uuid"));
+
+        let missing = impl_type_uuid_provider(parse_quote! {
+            struct Missing;
+        });
+        assert!(missing.to_string().contains("compile_error"));
+    }
+}
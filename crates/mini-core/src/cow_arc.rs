@@ -52,10 +52,39 @@ impl<'a, T: ?Sized> AsRef<T> for CowArc<'a, T> {
     }
 }
 
+impl<'a, T: ?Sized> CowArc<'a, T> {
+    /// Returns `true` if this is a [`CowArc::Borrowed`] value.
+    #[inline]
+    pub fn is_borrowed(&self) -> bool {
+        matches!(self, CowArc::Borrowed(_))
+    }
+
+    /// Returns `true` if this is a [`CowArc::Static`] value.
+    #[inline]
+    pub fn is_static(&self) -> bool {
+        matches!(self, CowArc::Static(_))
+    }
+
+    /// Returns `true` if this is a [`CowArc::Owned`] value.
+    #[inline]
+    pub fn is_owned(&self) -> bool {
+        matches!(self, CowArc::Owned(_))
+    }
+}
+
 impl<'a, T: ?Sized> CowArc<'a, T>
 where
     &'a T: Into<Arc<T>>,
 {
+    /// Upgrades a [`CowArc::Borrowed`] value in place to a [`CowArc::Owned`] one by cloning it into an [`Arc`].
+    /// [`CowArc::Static`] and [`CowArc::Owned`] values are left unchanged, so the value never changes.
+    #[inline]
+    pub fn make_owned(&mut self) {
+        if let CowArc::Borrowed(value) = *self {
+            *self = CowArc::Owned(value.into());
+        }
+    }
+
     /// Converts this into an "owned" value. If internally a value is borrowed, it will be cloned into an "owned [`Arc`]".
     /// If it is already a [`CowArc::Owned`] or a [`CowArc::Static`], it will remain unchanged.
     #[inline]
@@ -164,6 +193,13 @@ impl From<String> for CowArc<'static, str> {
     }
 }
 
+impl From<Vec<u8>> for CowArc<'static, [u8]> {
+    #[inline]
+    fn from(value: Vec<u8>) -> Self {
+        CowArc::Owned(value.into())
+    }
+}
+
 impl<'a> From<&'a String> for CowArc<'a, str> {
     #[inline]
     fn from(value: &'a String) -> Self {
@@ -177,3 +213,28 @@ impl<T: ?Sized> From<&'static T> for CowArc<'static, T> {
         CowArc::Static(value)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_make_owned() {
+        let value = String::from("path/to/file");
+        let mut borrowed = CowArc::Borrowed(value.as_str());
+        assert!(borrowed.is_borrowed());
+
+        borrowed.make_owned();
+        assert!(borrowed.is_owned());
+        assert_eq!(&*borrowed, "path/to/file");
+
+        let mut static_value = CowArc::<str>::from("static");
+        static_value.make_owned();
+        assert!(static_value.is_static());
+
+        let bytes = CowArc::from(vec![1u8, 2, 3]);
+        assert!(bytes.is_owned());
+        assert_eq!(&*bytes, &[1, 2, 3]);
+        assert!(CowArc::from(String::from("owned")).is_owned());
+    }
+}
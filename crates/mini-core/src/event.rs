@@ -0,0 +1,141 @@
+use std::any::{Any, TypeId};
+
+use crate::utils::FxHashMap;
+
+/// 可以通过 [`EventBus`] 发送的事件
+pub trait Event: 'static + Send + Sync {}
+
+impl<T: 'static + Send + Sync> Event for T {}
+
+// 双缓冲的事件队列，`current` 是这一帧发送的事件，`readable` 是上一帧发送的事件
+struct EventQueue<E> {
+    readable: Vec<E>,
+    current: Vec<E>,
+}
+
+impl<E> Default for EventQueue<E> {
+    fn default() -> Self {
+        Self {
+            readable: vec![],
+            current: vec![],
+        }
+    }
+}
+
+trait ErasedEventQueue: Any + Send + Sync {
+    fn swap(&mut self);
+
+    fn as_any(&self) -> &dyn Any;
+
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<E: Event> ErasedEventQueue for EventQueue<E> {
+    fn swap(&mut self) {
+        self.readable = std::mem::take(&mut self.current);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// 按事件类型存储的事件总线。
+///
+/// 这一帧发送的事件在调用 [`EventBus::update`] 之后才能读取，
+/// 下一次 update 时没有被读取的事件会被丢弃。
+#[derive(Default)]
+pub struct EventBus {
+    queues: FxHashMap<TypeId, Box<dyn ErasedEventQueue>>,
+}
+
+impl EventBus {
+    pub fn send<E: Event>(&mut self, event: E) {
+        self.queue_mut::<E>().current.push(event);
+    }
+
+    /// 上一帧发送的事件，按发送的顺序返回，事件不会被取出
+    pub fn read<E: Event>(&self) -> &[E] {
+        match self.queues.get(&TypeId::of::<E>()) {
+            Some(queue) => {
+                &queue
+                    .as_any()
+                    .downcast_ref::<EventQueue<E>>()
+                    .expect("The queue is keyed by the type id of its events")
+                    .readable
+            }
+            None => &[],
+        }
+    }
+
+    /// 取出上一帧发送的事件，按发送的顺序返回
+    pub fn drain<E: Event>(&mut self) -> Vec<E> {
+        match self.queues.get_mut(&TypeId::of::<E>()) {
+            Some(queue) => std::mem::take(&mut downcast_queue::<E>(queue).readable),
+            None => vec![],
+        }
+    }
+
+    /// 在每一帧开始时调用，使这一帧发送的事件可以被读取
+    pub fn update(&mut self) {
+        for queue in self.queues.values_mut() {
+            queue.swap();
+        }
+    }
+
+    fn queue_mut<E: Event>(&mut self) -> &mut EventQueue<E> {
+        let queue = self
+            .queues
+            .entry(TypeId::of::<E>())
+            .or_insert_with(|| Box::new(EventQueue::<E>::default()));
+        downcast_queue(queue)
+    }
+}
+
+fn downcast_queue<E: Event>(queue: &mut Box<dyn ErasedEventQueue>) -> &mut EventQueue<E> {
+    queue
+        .as_any_mut()
+        .downcast_mut()
+        .expect("The queue is keyed by the type id of its events")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Resized(u32, u32);
+
+    #[derive(Debug, PartialEq)]
+    struct FileDropped(&'static str);
+
+    #[test]
+    fn test_event_bus() {
+        let mut bus = EventBus::default();
+        bus.send(Resized(800, 600));
+        bus.send(FileDropped("a.png"));
+        bus.send(Resized(1024, 768));
+
+        // 这一帧发送的事件在下一帧才能读取
+        assert!(bus.drain::<Resized>().is_empty());
+
+        bus.update();
+        bus.send(FileDropped("b.png"));
+        assert_eq!(bus.read::<Resized>().len(), 2);
+        assert_eq!(
+            bus.drain::<Resized>(),
+            [Resized(800, 600), Resized(1024, 768)]
+        );
+        assert!(bus.drain::<Resized>().is_empty());
+
+        bus.update();
+        // 上一帧没有读取的事件被丢弃
+        assert_eq!(bus.drain::<FileDropped>(), [FileDropped("b.png")]);
+        assert!(bus.drain::<u32>().is_empty());
+        assert!(bus.read::<u32>().is_empty());
+    }
+}
@@ -1,4 +1,5 @@
 pub mod cow_arc;
+pub mod event;
 pub mod future;
 pub mod sparse;
 pub mod time_to_live;
@@ -9,6 +10,7 @@ pub use async_fs;
 pub use bitflags;
 pub use bytemuck;
 pub use downcast_rs as downcast;
+pub use futures;
 pub use futures_io;
 pub use futures_lite;
 pub use parking_lot;
@@ -19,6 +21,7 @@ pub use tracing_subscriber;
 pub use uuid;
 
 pub mod prelude {
+    pub use crate::event::*;
     pub use crate::future::*;
     pub use crate::sparse::*;
     pub use crate::time_to_live::*;
@@ -27,3 +30,41 @@ pub mod prelude {
 
     pub use mini_core_macros::{Deref, DerefMut, EnumVariantMeta};
 }
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::EnumVariantMeta;
+
+    #[derive(EnumVariantMeta)]
+    #[allow(dead_code)]
+    enum Shape {
+        Empty,
+        Circle(f32),
+        Rect { width: f32, height: f32 },
+        Line(f32, f32),
+    }
+
+    #[test]
+    fn test_enum_variant_meta() {
+        let shapes = [
+            Shape::Empty,
+            Shape::Circle(1.0),
+            Shape::Rect {
+                width: 1.0,
+                height: 2.0,
+            },
+            Shape::Line(0.0, 1.0),
+        ];
+
+        let indices = shapes
+            .iter()
+            .map(Shape::enum_variant_index)
+            .collect::<Vec<_>>();
+        let names = shapes
+            .iter()
+            .map(Shape::enum_variant_name)
+            .collect::<Vec<_>>();
+        assert_eq!(indices, [0, 1, 2, 3]);
+        assert_eq!(names, ["Empty", "Circle", "Rect", "Line"]);
+    }
+}
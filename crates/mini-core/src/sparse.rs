@@ -109,8 +109,9 @@ impl<T> SparseBuffer<T> {
         }
     }
 
+    /// 存放的元素数量，不包含被释放的位置
     pub fn len(&self) -> usize {
-        self.vec.len()
+        self.filled()
     }
 
     pub fn is_empty(&self) -> bool {
@@ -141,12 +142,34 @@ impl<T> SparseBuffer<T> {
         self.vec.get_mut(index).and_then(|entry| entry.as_mut())
     }
 
-    pub fn iter(&self) -> impl Iterator<Item = &T> {
-        self.vec.iter().filter_map(|entry| entry.as_ref())
+    /// 按照索引顺序遍历所有元素
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &T)> {
+        self.vec
+            .iter()
+            .enumerate()
+            .filter_map(|(index, entry)| entry.as_ref().map(|payload| (index, payload)))
     }
 
-    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
-        self.vec.iter_mut().filter_map(|entry| entry.as_mut())
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (usize, &mut T)> {
+        self.vec
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(index, entry)| entry.as_mut().map(|payload| (index, payload)))
+    }
+
+    /// 释放 `f` 返回 `false` 的元素，其余元素的索引保持不变，被释放的索引之后可以复用
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(usize, &mut T) -> bool,
+    {
+        for (index, entry) in self.vec.iter_mut().enumerate() {
+            if let Some(payload) = entry {
+                if !f(index, payload) {
+                    *entry = None;
+                    self.free.push(index);
+                }
+            }
+        }
     }
 
     pub fn clear(&mut self) {
@@ -230,6 +253,12 @@ mod test {
         let sb = SparseBuffer::<f32>::default();
 
         assert_eq!(sb.len(), 0);
+
+        let sb = SparseBuffer {
+            vec: vec![None, Some(1)],
+            free: vec![0],
+        };
+        assert_eq!(sb.len(), 1);
     }
 
     #[test]
@@ -317,7 +346,7 @@ mod test {
             free: vec![],
         };
 
-        assert!(sb.iter().eq([&1]));
+        assert!(sb.iter().eq([(1, &1)]));
     }
 
     #[test]
@@ -327,7 +356,7 @@ mod test {
             free: vec![],
         };
 
-        assert!(sb.iter_mut().eq([&mut 1]));
+        assert!(sb.iter_mut().eq([(1, &mut 1)]));
     }
 
     #[test]
@@ -384,4 +413,33 @@ mod test {
         assert_eq!(sb.vec, vec![Some(42), Some(1), Some(5)]);
         assert_eq!(sb.free, vec![]);
     }
+
+    #[test]
+    fn sparse_buffer_retain() {
+        let mut sb = SparseBuffer::default();
+        let indices = (0..6).map(|i| sb.spawn(i * 10)).collect::<Vec<_>>();
+
+        sb.retain(|index, payload| {
+            *payload += 1;
+            index % 2 == 0
+        });
+
+        assert_eq!(sb.len(), 3);
+        assert!(sb.iter().eq([(0, &1), (2, &21), (4, &41)]));
+        for (i, index) in indices.iter().enumerate() {
+            assert_eq!(sb.is_index_valid(index), i % 2 == 0);
+        }
+        assert_eq!(sb.get(&indices[4]), Some(&41));
+
+        // 被释放的索引会被复用，不会增加长度
+        let reused = sb.spawn(100);
+        assert!(reused.get() % 2 == 1);
+        assert_eq!(sb.get(&reused), Some(&100));
+        assert_eq!(sb.vec.len(), 6);
+        assert_eq!(sb.len(), 4);
+
+        sb.retain(|_, _| false);
+        assert!(sb.is_empty());
+        assert_eq!(sb.iter().count(), 0);
+    }
 }
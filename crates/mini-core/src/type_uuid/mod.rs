@@ -1,7 +1,13 @@
+#[cfg(feature = "type-uuid-registry")]
+pub mod registry;
 mod std_impl;
 
+use std::collections::HashMap;
+
 use uuid::Uuid;
 
+use crate::utils::{fnv1a, FNV1A_OFFSET_BASIS};
+
 pub use mini_core_macros::TypeUuidProvider;
 
 //为类型实现唯一的标识
@@ -9,6 +15,23 @@ pub trait TypeUuidProvider: Sized {
     fn type_uuid() -> Uuid;
 }
 
+/// 开启 `type-uuid-registry` feature 时记录类型的 uuid，不同的类型使用同一个 uuid 时在 debug 构建中 panic。
+///
+/// 没有开启 feature 时什么也不做。
+#[inline]
+pub fn register_type_uuid<T: TypeUuidProvider + 'static>() {
+    #[cfg(feature = "type-uuid-registry")]
+    if let Err(existing) = registry::try_register_type_uuid::<T>() {
+        debug_assert!(
+            false,
+            "{} and {} have the same type uuid {}",
+            existing,
+            std::any::type_name::<T>(),
+            T::type_uuid()
+        );
+    }
+}
+
 #[macro_export]
 macro_rules! uuid_provider {
     ($type:ident $(<$($generics:tt),*>)? = $uuid:expr) => {
@@ -38,12 +61,114 @@ impl<T: TypeUuidProvider> TypeUuidProvider for Vec<T> {
     }
 }
 
+impl<A: TypeUuidProvider, B: TypeUuidProvider> TypeUuidProvider for (A, B) {
+    fn type_uuid() -> Uuid {
+        combine_uuids_all(&[
+            uuid::uuid!("42436773-636b-4096-a470-8e7a6dffb489"),
+            A::type_uuid(),
+            B::type_uuid(),
+        ])
+    }
+}
+
+impl<A: TypeUuidProvider, B: TypeUuidProvider, C: TypeUuidProvider> TypeUuidProvider for (A, B, C) {
+    fn type_uuid() -> Uuid {
+        combine_uuids_all(&[
+            uuid::uuid!("9533d90c-8887-46f8-aaea-11d60d9b0dfb"),
+            A::type_uuid(),
+            B::type_uuid(),
+            C::type_uuid(),
+        ])
+    }
+}
+
+//数组的长度也是类型的一部分
+impl<T: TypeUuidProvider, const N: usize> TypeUuidProvider for [T; N] {
+    fn type_uuid() -> Uuid {
+        combine_uuids_all(&[
+            uuid::uuid!("33ad03ee-a54b-4168-b21e-5f2f1631f664"),
+            T::type_uuid(),
+            Uuid::from_u128(N as u128),
+        ])
+    }
+}
+
+impl<K: TypeUuidProvider, V: TypeUuidProvider, S> TypeUuidProvider for HashMap<K, V, S> {
+    fn type_uuid() -> Uuid {
+        combine_uuids_all(&[
+            uuid::uuid!("0b3904d5-001c-4d07-9a0b-2d37c809190c"),
+            K::type_uuid(),
+            V::type_uuid(),
+        ])
+    }
+}
+
+/// 合并两个 uuid，`combine_uuids(a, b)` 与 `combine_uuids(b, a)` 不同。
 pub fn combine_uuids(a: Uuid, b: Uuid) -> Uuid {
-    let mut combined_bytes = a.into_bytes();
+    combine_uuids_all(&[a, b])
+}
+
+/// 按顺序合并多个 uuid，顺序不同结果也不同。
+///
+/// 使用 FNV-1a (128 位) 按字节折叠后生成 v4 格式的 uuid，结果在不同的运行之间保持稳定。
+pub fn combine_uuids_all(uuids: &[Uuid]) -> Uuid {
+    let hash = uuids.iter().fold(FNV1A_OFFSET_BASIS, |hash, uuid| {
+        fnv1a(hash, uuid.as_bytes())
+    });
+
+    uuid::Builder::from_random_bytes(hash.to_be_bytes()).into_uuid()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
 
-    for (src, dest) in b.into_bytes().into_iter().zip(combined_bytes.iter_mut()) {
-        *dest ^= src;
+    #[test]
+    fn test_combine_uuids() {
+        let a = uuid::uuid!("7a8c337c-0219-466b-92b5-81460fa9c836");
+        let b = uuid::uuid!("3036f00e-5986-4ac3-8763-19e51d0889d7");
+
+        assert_ne!(combine_uuids(a, b), combine_uuids(b, a));
+        assert_eq!(combine_uuids(a, b), combine_uuids(a, b));
+
+        // 与自身合并不会得到全零的 uuid
+        let aa = combine_uuids(a, a);
+        assert!(!aa.is_nil());
+        assert_ne!(aa, a);
+        assert_ne!(aa, combine_uuids(b, b));
+        assert_eq!(aa.get_version_num(), 4);
+
+        assert_ne!(
+            <Vec<Option<u8>>>::type_uuid(),
+            <Option<Vec<u8>>>::type_uuid()
+        );
+    }
+
+    #[test]
+    fn test_composite_type_uuids() {
+        assert_ne!(<(u32, u8)>::type_uuid(), <(u8, u32)>::type_uuid());
+        assert_ne!(<(u8, u8, u32)>::type_uuid(), <(u8, u32, u8)>::type_uuid());
+        assert_ne!(<(u8, u8)>::type_uuid(), <[u8; 2]>::type_uuid());
+        assert_ne!(<[u8; 2]>::type_uuid(), <[u8; 3]>::type_uuid());
+        assert_ne!(
+            <HashMap<String, u32>>::type_uuid(),
+            <HashMap<u32, String>>::type_uuid()
+        );
+        assert_eq!(<[u8; 4]>::type_uuid(), <[u8; 4]>::type_uuid());
     }
 
-    Uuid::from_bytes(combined_bytes)
+    #[test]
+    fn test_combine_uuids_all() {
+        let a = uuid::uuid!("7a8c337c-0219-466b-92b5-81460fa9c836");
+        let b = uuid::uuid!("3036f00e-5986-4ac3-8763-19e51d0889d7");
+        let c = uuid::uuid!("c662169d-cc3b-453c-bdf3-e0104ac3b966");
+
+        let abc = combine_uuids_all(&[a, b, c]);
+        assert_ne!(abc, combine_uuids_all(&[c, b, a]));
+        assert_ne!(abc, combine_uuids_all(&[a, c, b]));
+        assert_eq!(abc, combine_uuids_all(&[a, b, c]));
+
+        // 结果需要在不同的运行之间保持一致
+        assert_eq!(abc, uuid::uuid!("360baa1b-4990-46e3-8db0-e0df6e1cff82"));
+    }
 }
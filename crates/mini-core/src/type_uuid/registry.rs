@@ -0,0 +1,64 @@
+use std::{
+    any::{type_name, TypeId},
+    sync::OnceLock,
+};
+
+use parking_lot::Mutex;
+use uuid::Uuid;
+
+use super::TypeUuidProvider;
+use crate::utils::FxHashMap;
+
+#[derive(Debug, Clone, Copy)]
+struct RegisteredType {
+    type_id: TypeId,
+    type_name: &'static str,
+}
+
+fn registry() -> &'static Mutex<FxHashMap<Uuid, RegisteredType>> {
+    static REGISTRY: OnceLock<Mutex<FxHashMap<Uuid, RegisteredType>>> = OnceLock::new();
+    REGISTRY.get_or_init(Default::default)
+}
+
+/// 记录类型的 uuid，返回之前使用同一个 uuid 的其他类型的名称
+pub fn try_register_type_uuid<T: TypeUuidProvider + 'static>() -> Result<(), &'static str> {
+    let registered = RegisteredType {
+        type_id: TypeId::of::<T>(),
+        type_name: type_name::<T>(),
+    };
+
+    let mut registry = registry().lock();
+    let existing = *registry.entry(T::type_uuid()).or_insert(registered);
+    if existing.type_id == registered.type_id {
+        Ok(())
+    } else {
+        Err(existing.type_name)
+    }
+}
+
+/// 已经记录的使用 `uuid` 的类型名称
+pub fn registered_type_name(uuid: Uuid) -> Option<&'static str> {
+    registry()
+        .lock()
+        .get(&uuid)
+        .map(|registered| registered.type_name)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct A;
+    struct B;
+
+    crate::uuid_provider!(A = "0d9c7a3e-6c55-4f0f-8f3e-52a7f0c1d001");
+    crate::uuid_provider!(B = "0d9c7a3e-6c55-4f0f-8f3e-52a7f0c1d001");
+
+    #[test]
+    fn test_type_uuid_collision() {
+        assert!(try_register_type_uuid::<A>().is_ok());
+        assert!(try_register_type_uuid::<A>().is_ok());
+        assert_eq!(try_register_type_uuid::<B>(), Err(type_name::<A>()));
+        assert_eq!(registered_type_name(A::type_uuid()), Some(type_name::<A>()));
+    }
+}
@@ -1,15 +1,173 @@
+use std::cell::UnsafeCell;
+
+use parking_lot::Once;
+
+//FxHasher 没有随机种子，相同的插入顺序在每次运行中得到相同的遍历顺序
 pub use rustc_hash::*;
 
+/// 忽略 ASCII 字母的大小写比较两个字符串，用于比较文件扩展名，不会分配内存。
+///
+/// 非 ASCII 字符需要完全相同。
 pub fn cmp_strings_case_insensitive(a: impl AsRef<str>, b: impl AsRef<str>) -> bool {
-    let a_ref = a.as_ref();
-    let b_ref = b.as_ref();
+    let (a, b) = (a.as_ref().as_bytes(), b.as_ref().as_bytes());
+    a.len() == b.len()
+        && a.iter()
+            .zip(b.iter())
+            .all(|(ca, cb)| ca.eq_ignore_ascii_case(cb))
+}
+
+/// 延迟初始化的值，第一次调用 [`get_or_init`](Self::get_or_init) 时才会构建，之后的调用都返回同一个值。
+///
+/// 适合依赖设备等运行时状态的缓存，初始化由 [`parking_lot::Once`] 保证只执行一次，可以在多个线程之间共享。
+pub struct Lazy<T> {
+    once: Once,
+    value: UnsafeCell<Option<T>>,
+}
+
+// SAFETY: value 只在 once 中写入一次，之后只会被共享读取
+unsafe impl<T: Send + Sync> Sync for Lazy<T> {}
+
+impl<T> Lazy<T> {
+    pub const fn new() -> Self {
+        Self {
+            once: Once::new(),
+            value: UnsafeCell::new(None),
+        }
+    }
+
+    /// 没有初始化时调用 `f`，多个线程同时调用时只有一个线程执行 `f`，其他线程等待它完成
+    pub fn get_or_init(&self, f: impl FnOnce() -> T) -> &T {
+        self.once.call_once(|| {
+            // SAFETY: call_once 保证只有一个线程写入，写入时没有其他引用
+            unsafe { *self.value.get() = Some(f()) };
+        });
+        self.get().unwrap()
+    }
+
+    /// 已经初始化的值
+    pub fn get(&self) -> Option<&T> {
+        if self.once.state().done() {
+            // SAFETY: 初始化完成之后不会再写入
+            unsafe { (*self.value.get()).as_ref() }
+        } else {
+            None
+        }
+    }
+}
+
+impl<T> Default for Lazy<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// FNV-1a (128 位) 的初始值，传给 [`fnv1a`] 开始计算哈希
+pub const FNV1A_OFFSET_BASIS: u128 = 0x6c62272e07bb014262b821756295c58d;
+const FNV1A_PRIME: u128 = 0x0000000001000000000000000000013b;
 
-    if a_ref.len() != b_ref.len() {
-        return false;
+/// 把 `bytes` 折叠进 FNV-1a 哈希，数据分成几段依次折叠和一次折叠的结果相同。
+///
+/// 结果在不同的运行之间保持稳定，可以写入文件。
+pub fn fnv1a(mut hash: u128, bytes: &[u8]) -> u128 {
+    for byte in bytes {
+        hash ^= *byte as u128;
+        hash = hash.wrapping_mul(FNV1A_PRIME);
     }
+    hash
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_fnv1a() {
+        assert_eq!(fnv1a(FNV1A_OFFSET_BASIS, b""), FNV1A_OFFSET_BASIS);
+        // 128 位 FNV-1a 的标准测试向量
+        assert_eq!(
+            fnv1a(FNV1A_OFFSET_BASIS, b"a"),
+            0xd228cb696f1a8caf78912b704e4a8964
+        );
+        assert_eq!(
+            fnv1a(fnv1a(FNV1A_OFFSET_BASIS, b"hello "), b"world"),
+            fnv1a(FNV1A_OFFSET_BASIS, b"hello world")
+        );
+    }
+
+    #[test]
+    fn test_cmp_strings_case_insensitive() {
+        assert!(cmp_strings_case_insensitive("png", "PNG"));
+        assert!(cmp_strings_case_insensitive("tar.GZ", "TAR.gz"));
+        assert!(cmp_strings_case_insensitive("", ""));
+        assert!(!cmp_strings_case_insensitive("png", "pn"));
+        assert!(!cmp_strings_case_insensitive("jpg", "jpeg"));
+
+        // 只忽略 ASCII 的大小写，看起来相同的 unicode 字符不相等
+        assert!(cmp_strings_case_insensitive("ktx2", "KTX2"));
+        assert!(!cmp_strings_case_insensitive("ktx2", "\u{212A}tx2"));
+        assert!(!cmp_strings_case_insensitive("ico", "\u{0130}co"));
+        assert!(!cmp_strings_case_insensitive("é.txt", "É.txt"));
+        assert!(cmp_strings_case_insensitive("é.TXT", "é.txt"));
+    }
+
+    #[test]
+    fn test_fx_hash_is_deterministic() {
+        use std::hash::{BuildHasher, Hash, Hasher};
 
-    a_ref
-        .chars()
-        .zip(b_ref.chars())
-        .all(|(ca, cb)| ca.to_lowercase().eq(cb.to_lowercase()))
+        // 其他 crate 通过 prelude 使用
+        use crate::prelude::{FxBuildHasher, FxHashMap, FxHashSet, FxHasher};
+
+        // 没有随机种子，和直接使用 FxHasher 的结果相同
+        let mut hasher = FxHasher::default();
+        "textures/a.png".hash(&mut hasher);
+        assert_eq!(FxBuildHasher.hash_one("textures/a.png"), hasher.finish());
+
+        let keys = ["a.png", "b.gltf", "c.wgsl", "d.ron", "e.txt"];
+        let map = keys
+            .iter()
+            .map(|key| (*key, ()))
+            .collect::<FxHashMap<_, _>>();
+        let other = keys
+            .iter()
+            .map(|key| (*key, ()))
+            .collect::<FxHashMap<_, _>>();
+        assert!(map.keys().eq(other.keys()));
+        let set = keys.iter().copied().collect::<FxHashSet<_>>();
+        assert!(set.iter().eq(map.keys()));
+    }
+
+    #[test]
+    fn test_lazy_init_once() {
+        let lazy = Arc::new(Lazy::new());
+        let calls = Arc::new(AtomicUsize::new(0));
+        assert!(lazy.get().is_none());
+
+        let handles = (0..8)
+            .map(|_| {
+                let lazy = lazy.clone();
+                let calls = calls.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..100 {
+                        let value = lazy.get_or_init(|| {
+                            calls.fetch_add(1, Ordering::SeqCst);
+                            42
+                        });
+                        assert_eq!(*value, 42);
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(lazy.get(), Some(&42));
+    }
 }
@@ -1,43 +1,404 @@
-use std::sync::Arc;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
-use mini_core::tracing_subscriber::{self};
-use mini_renderer::graphics_context::GraphicsContext;
+use mini_core::{
+    event::EventBus,
+    tracing::error,
+    tracing_subscriber::{self},
+};
+use mini_math::prelude::Color;
 use mini_resource::prelude::ResourceManager;
 use mini_task::TaskPool;
-use mini_window::prelude::ErasedWindow;
+use mini_window::prelude::{AppLifecycle, CursorIcon, ErasedWindow, Input, Window, WindowId};
 
-use crate::scene::Scene;
+use super::{EngineEvent, FixedTimestep, Time, WindowCommand};
+
+use crate::{
+    renderer::{graphics_context::GraphicsContext, settings::RenderSettings, wgpu::SurfaceError},
+    scene::Scene,
+};
+
+type FixedUpdate = dyn FnMut(&mut Engine);
+//参数为距离上一帧的时间
+type UpdateSystem = dyn FnMut(&mut Engine, Duration);
 
 pub struct Engine {
     resource_manager: ResourceManager,
     pub graphics_context: GraphicsContext,
+    //在 initialize 时用于创建渲染设备
+    pub render_settings: RenderSettings,
     pub scene: Scene,
+    //每帧开始时用于清屏的颜色
+    pub clear_color: Color,
+    //键盘和鼠标的输入状态
+    pub input: Input,
+    pub time: Time,
+    fixed_timestep: Option<FixedTimestep>,
+    fixed_update: Option<Box<FixedUpdate>>,
+    update_systems: Vec<Box<UpdateSystem>>,
+    window_commands: Vec<WindowCommand>,
+    //executor 发送的 EngineEvent，在下一次 update 时可以读取
+    events: EventBus,
+    //渲染时无法恢复的错误，执行器取出后退出
+    render_error: Option<SurfaceError>,
+    //由 executor 根据平台的事件设置，`Suspended` 时不渲染
+    pub lifecycle: AppLifecycle,
+    //最后一个窗口关闭后是否退出，为 false 时可以之后再创建窗口
+    pub exit_on_last_window_closed: bool,
 }
 
 impl Engine {
     pub fn initialize(&mut self, window: &ErasedWindow) {
-        self.graphics_context
-            .initialize(&window, &self.resource_manager);
+        self.graphics_context.initialize_with_settings(
+            window,
+            &self.resource_manager,
+            &self.render_settings,
+        );
     }
 
+    /// 创建引擎，不依赖窗口和事件循环。
+    ///
+    /// 调用 [`Engine::initialize`] 之前不会渲染，可以直接调用 [`Engine::update`]。
     pub fn from_params() -> Self {
-        tracing_subscriber::fmt()
+        // 可能已经有其他引擎或测试设置了全局的 subscriber
+        let _ = tracing_subscriber::fmt()
             .with_env_filter("mini_renderer=info")
-            .init();
+            .try_init();
 
         let task_pool = Arc::new(TaskPool::new());
         let resource_manager = ResourceManager::new(task_pool);
 
-        let scene = Scene {};
+        let scene = Scene::new();
 
         Engine {
             resource_manager,
             graphics_context: GraphicsContext::Uninitialized,
+            render_settings: RenderSettings::default(),
             scene,
+            clear_color: Color::BLACK,
+            input: Input::default(),
+            time: Time::default(),
+            fixed_timestep: None,
+            fixed_update: None,
+            update_systems: vec![],
+            window_commands: vec![],
+            events: EventBus::default(),
+            render_error: None,
+            lifecycle: AppLifecycle::Idle,
+            exit_on_last_window_closed: true,
         }
     }
 
     pub fn update(&mut self) {
-        self.graphics_context.render();
+        self.update_with_instant(Instant::now());
+    }
+
+    /// 使用指定的时刻更新，可以在测试中控制每帧的时间
+    pub fn update_with_instant(&mut self, instant: Instant) {
+        self.time.update_with_instant(instant);
+        // 上一次 update 之后收到的事件在这一帧可以读取，没有取出的旧事件被丢弃
+        self.events.update();
+        //wasm32 上资源加载任务在这里执行
+        self.resource_manager.task_pool().run_until_stalled();
+        self.resource_manager.update_cache(self.time.delta());
+        // 监听到修改的资源在任务池中重新加载
+        self.resource_manager.process_file_events();
+        self.run_fixed_update();
+        self.run_update_systems();
+
+        // 挂起时 surface 已经被释放
+        if self.lifecycle != AppLifecycle::Suspended {
+            if let Err(err) = self.render() {
+                error!("Failed to render the frame: {err}");
+                self.render_error = Some(err);
+            }
+        }
+
+        self.input.clear();
+    }
+
+    /// 距离上一次 update 的时间，第一帧为 0
+    pub fn delta_time(&self) -> Duration {
+        self.time.delta()
+    }
+
+    /// 从第一次 update 开始经过的时间
+    pub fn elapsed(&self) -> Duration {
+        self.time.elapsed()
+    }
+
+    /// 添加每次 update 都会调用的系统，按照添加的顺序执行
+    pub fn add_update_system(&mut self, system: impl FnMut(&mut Engine, Duration) + 'static) {
+        self.update_systems.push(Box::new(system));
+    }
+
+    fn run_update_systems(&mut self) {
+        let delta_time = self.delta_time();
+        let mut systems = std::mem::take(&mut self.update_systems);
+        for system in systems.iter_mut() {
+            system(self, delta_time);
+        }
+        // 系统中可能添加了新的系统
+        systems.append(&mut self.update_systems);
+        self.update_systems = systems;
+    }
+
+    /// 设置固定时间步长的回调，每次 update 会根据经过的时间调用若干次
+    pub fn set_fixed_update(
+        &mut self,
+        timestep: Duration,
+        fixed_update: impl FnMut(&mut Engine) + 'static,
+    ) {
+        self.fixed_timestep = Some(FixedTimestep::new(timestep));
+        self.fixed_update = Some(Box::new(fixed_update));
+    }
+
+    pub fn fixed_timestep(&self) -> Option<&FixedTimestep> {
+        self.fixed_timestep.as_ref()
+    }
+
+    fn run_fixed_update(&mut self) {
+        let Some(fixed_timestep) = self.fixed_timestep.as_mut() else {
+            return;
+        };
+        let steps = fixed_timestep.accumulate(self.time.delta());
+
+        // 回调需要访问引擎，执行期间先取出来
+        let Some(mut fixed_update) = self.fixed_update.take() else {
+            return;
+        };
+        for _ in 0..steps {
+            fixed_update(self);
+        }
+        if self.fixed_update.is_none() {
+            self.fixed_update = Some(fixed_update);
+        }
+    }
+
+    /// 渲染器没有初始化时什么都不做。
+    ///
+    /// 获取不到 swapchain 纹理的窗口会跳过这一帧，只返回无法恢复的错误。
+    pub fn render(&mut self) -> Result<(), SurfaceError> {
+        self.graphics_context.set_clear_color(self.clear_color);
+        self.graphics_context.render()
+    }
+
+    /// 取出 [`update`](Self::update) 中渲染失败的错误，有错误时应该退出
+    pub fn take_render_error(&mut self) -> Option<SurfaceError> {
+        self.render_error.take()
+    }
+
+    pub fn frame_count(&self) -> u64 {
+        self.time.frame_count()
+    }
+
+    pub fn is_headless(&self) -> bool {
+        matches!(self.graphics_context, GraphicsContext::Uninitialized)
+    }
+
+    /// 请求创建一个新的窗口，窗口会在下一帧创建
+    pub fn create_window(&mut self, window: Window) {
+        self.window_commands
+            .push(WindowCommand::CreateWindow(window));
+    }
+
+    /// 请求关闭窗口，窗口和它的 surface 会在下一帧移除
+    pub fn close_window(&mut self, window: WindowId) {
+        self.window_commands
+            .push(WindowCommand::CloseWindow(window));
+    }
+
+    /// 修改主窗口的鼠标图标
+    pub fn set_cursor_icon(&mut self, cursor_icon: CursorIcon) {
+        self.window_commands.push(WindowCommand::SetCursorIcon {
+            window: None,
+            cursor_icon,
+        });
+    }
+
+    pub fn set_window_cursor_icon(&mut self, window: WindowId, cursor_icon: CursorIcon) {
+        self.window_commands.push(WindowCommand::SetCursorIcon {
+            window: Some(window),
+            cursor_icon,
+        });
+    }
+
+    /// 取出所有等待执行的窗口操作
+    pub fn take_window_commands(&mut self) -> Vec<WindowCommand> {
+        std::mem::take(&mut self.window_commands)
+    }
+
+    /// 由 executor 调用，事件在下一次 [`update`](Self::update) 中可以读取
+    pub fn send_event(&mut self, event: EngineEvent) {
+        self.events.send(event);
+    }
+
+    /// 这一帧可以读取的事件，保留到下一次 update 开始
+    pub fn events(&self) -> &[EngineEvent] {
+        self.events.read()
+    }
+
+    /// 取出这一帧的事件，之后的更新系统不会再看到它们
+    pub fn drain_events(&mut self) -> Vec<EngineEvent> {
+        self.events.drain()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use mini_window::prelude::KeyCode;
+
+    use super::*;
+
+    #[test]
+    fn test_headless_update() {
+        let mut engine = Engine::from_params();
+        assert!(engine.is_headless());
+
+        engine.input.keyboard.press(KeyCode::Space);
+        assert!(engine.input.just_pressed(KeyCode::Space));
+
+        for _ in 0..10 {
+            engine.update();
+        }
+
+        assert_eq!(engine.frame_count(), 10);
+        assert!(engine.input.pressed(KeyCode::Space));
+        assert!(!engine.input.just_pressed(KeyCode::Space));
+
+        // 可以创建多个引擎
+        let engine = Engine::from_params();
+        assert_eq!(engine.frame_count(), 0);
+    }
+
+    #[test]
+    fn test_close_window_command() {
+        let mut engine = Engine::from_params();
+        assert!(engine.exit_on_last_window_closed);
+
+        let window = WindowId::new(1);
+        engine.create_window(Window::default());
+        engine.close_window(window);
+
+        assert_eq!(
+            engine.take_window_commands(),
+            [
+                WindowCommand::CreateWindow(Window::default()),
+                WindowCommand::CloseWindow(window),
+            ]
+        );
+        assert!(engine.take_window_commands().is_empty());
+    }
+
+    #[test]
+    fn test_suspended_update() {
+        let mut engine = Engine::from_params();
+        assert_eq!(engine.lifecycle, AppLifecycle::Idle);
+
+        // 挂起时只跳过渲染，更新系统照常执行
+        engine.lifecycle = AppLifecycle::Suspended;
+        engine.add_update_system(|engine, _| engine.clear_color = Color::WHITE);
+        engine.update();
+        assert_eq!(engine.frame_count(), 1);
+        assert_eq!(engine.clear_color, Color::WHITE);
+        assert!(engine.take_render_error().is_none());
+    }
+
+    #[test]
+    fn test_engine_events() {
+        use mini_math::UVec2;
+        use std::{cell::RefCell, rc::Rc};
+
+        let mut engine = Engine::from_params();
+        let seen = Rc::new(RefCell::new(vec![]));
+        let system_seen = seen.clone();
+        engine.add_update_system(move |engine, _| {
+            system_seen.borrow_mut().extend_from_slice(engine.events())
+        });
+
+        let window = WindowId::new(1);
+        engine.send_event(EngineEvent::Resized(window, UVec2::new(800, 600)));
+        engine.send_event(EngineEvent::Focused(window, false));
+        // 下一次 update 之前不能读取
+        assert!(engine.events().is_empty());
+        engine.update();
+        assert_eq!(
+            *seen.borrow(),
+            [
+                EngineEvent::Resized(window, UVec2::new(800, 600)),
+                EngineEvent::Focused(window, false),
+            ]
+        );
+
+        // 下一次 update 时丢弃
+        engine.update();
+        assert_eq!(seen.borrow().len(), 2);
+        assert!(engine.events().is_empty());
+
+        engine.send_event(EngineEvent::CloseRequested(window));
+        engine.update();
+        assert_eq!(engine.drain_events(), [EngineEvent::CloseRequested(window)]);
+        assert!(engine.events().is_empty());
+        assert_eq!(seen.borrow().len(), 3);
+    }
+
+    #[test]
+    fn test_close_requested_before_close() {
+        use std::{cell::RefCell, rc::Rc};
+
+        let mut engine = Engine::from_params();
+        let closing = Rc::new(RefCell::new(vec![]));
+        let system_closing = closing.clone();
+        engine.add_update_system(move |engine, _| {
+            for event in engine.events() {
+                if let EngineEvent::CloseRequested(window) = event {
+                    system_closing.borrow_mut().push(*window);
+                }
+            }
+        });
+
+        // executor 收到 CloseRequested 时的处理：发送事件，窗口在 update 之后关闭
+        let window = WindowId::new(1);
+        engine.send_event(EngineEvent::CloseRequested(window));
+        engine.close_window(window);
+        engine.update();
+
+        assert_eq!(*closing.borrow(), [window]);
+        assert_eq!(
+            engine.take_window_commands(),
+            [WindowCommand::CloseWindow(window)]
+        );
+    }
+
+    #[test]
+    fn test_delta_time() {
+        use std::{cell::RefCell, rc::Rc};
+
+        let mut engine = Engine::from_params();
+        let deltas = Rc::new(RefCell::new(vec![]));
+        let system_deltas = deltas.clone();
+        engine.add_update_system(move |_, delta_time| system_deltas.borrow_mut().push(delta_time));
+
+        let start = Instant::now();
+        engine.update_with_instant(start);
+        assert_eq!(engine.delta_time(), Duration::ZERO);
+
+        engine.update_with_instant(start + Duration::from_millis(16));
+        assert_eq!(engine.delta_time(), Duration::from_millis(16));
+
+        engine.update_with_instant(start + Duration::from_millis(50));
+        assert_eq!(engine.delta_time(), Duration::from_millis(34));
+        assert_eq!(engine.elapsed(), Duration::from_millis(50));
+
+        assert_eq!(
+            *deltas.borrow(),
+            [
+                Duration::ZERO,
+                Duration::from_millis(16),
+                Duration::from_millis(34)
+            ]
+        );
     }
 }
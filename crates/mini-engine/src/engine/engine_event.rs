@@ -0,0 +1,15 @@
+use mini_math::UVec2;
+use mini_window::prelude::WindowId;
+
+/// executor 收到的窗口事件，在下一次 [`Engine::update`](super::Engine::update) 中可以读取，再下一次 update 时丢弃。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EngineEvent {
+    //窗口大小改变，单位为物理像素
+    Resized(WindowId, UVec2),
+    //用户请求关闭窗口
+    CloseRequested(WindowId),
+    //窗口获得或者失去焦点
+    Focused(WindowId, bool),
+    //窗口的缩放比例改变
+    ScaleFactorChanged(WindowId, f32),
+}
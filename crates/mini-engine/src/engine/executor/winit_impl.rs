@@ -1,30 +1,89 @@
-use crate::engine::Engine;
+use crate::engine::{Engine, EngineEvent, WindowCommand};
 
-use mini_window::window::{AppLifecycle, Window};
+use mini_math::{UVec2, Vec2};
+use mini_window::window::{AppLifecycle, Window, WindowId};
 use mini_winit::{
+    converters::{convert_key_code, convert_mouse_button, convert_scroll_delta, convert_window_id},
     windows::WinitWindows,
-    winit::{self, application::ApplicationHandler, event::WindowEvent, event_loop::ControlFlow},
+    winit::{
+        self,
+        application::ApplicationHandler,
+        event::{DeviceEvent, DeviceId, ElementState, WindowEvent},
+        event_loop::{ActiveEventLoop, ControlFlow},
+    },
 };
 
 pub struct WinitExecutor {
     pub engine: Engine,
     pub windows: WinitWindows,
-    lifecycle: AppLifecycle,
     pub is_initialize: bool,
 }
 
+impl Default for WinitExecutor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl WinitExecutor {
     pub fn new() -> Self {
         WinitExecutor {
             engine: Engine::from_params(),
             windows: WinitWindows::default(),
-            lifecycle: AppLifecycle::Idle,
             is_initialize: false,
         }
     }
 }
 
-impl WinitExecutor {}
+impl WinitExecutor {
+    fn apply_window_commands(&mut self, event_loop: &ActiveEventLoop) {
+        for command in self.engine.take_window_commands() {
+            match command {
+                WindowCommand::CreateWindow(window) => {
+                    let window_id = self.windows.create_window(event_loop, window);
+
+                    // 渲染器还没有初始化或者挂起时，surface 会在初始化或者恢复时创建
+                    if self.is_initialize && self.engine.lifecycle != AppLifecycle::Suspended {
+                        self.engine.graphics_context.initialize_window(
+                            &self.windows.get_window(window_id).unwrap().erased_window,
+                        );
+                    }
+                }
+                WindowCommand::CloseWindow(window_id) => self.close_window(event_loop, window_id),
+                WindowCommand::SetCursorIcon {
+                    window,
+                    cursor_icon,
+                } => {
+                    if let Some(window) = window.or(self.windows.primary) {
+                        self.windows.set_cursor_icon(window, cursor_icon);
+                    }
+                }
+            }
+        }
+    }
+
+    //渲染出现无法恢复的错误时退出，跳过的帧不会有错误
+    fn update(&mut self, event_loop: &ActiveEventLoop) {
+        self.engine.update();
+        if self.engine.take_render_error().is_some() {
+            event_loop.exit();
+        }
+    }
+
+    fn close_window(&mut self, event_loop: &ActiveEventLoop, window_id: WindowId) {
+        if self.windows.get_window(window_id).is_none() {
+            return;
+        }
+
+        // 先释放 surface，再销毁窗口，之后创建的窗口会重新初始化 surface
+        self.engine.graphics_context.remove_window(window_id);
+        self.windows.remove_window(window_id);
+
+        if self.windows.is_empty() && self.engine.exit_on_last_window_closed {
+            event_loop.exit();
+        }
+    }
+}
 
 impl ApplicationHandler for WinitExecutor {
     fn new_events(
@@ -32,19 +91,31 @@ impl ApplicationHandler for WinitExecutor {
         event_loop: &winit::event_loop::ActiveEventLoop,
         _cause: winit::event::StartCause,
     ) {
-        if self.lifecycle == AppLifecycle::Idle {
+        if self.engine.lifecycle == AppLifecycle::Idle && self.windows.primary.is_none() {
             self.windows.create_window(event_loop, Window::default());
         }
     }
 
     fn resumed(&mut self, _event_loop: &winit::event_loop::ActiveEventLoop) {
-        self.lifecycle = AppLifecycle::WillResume;
+        self.engine.lifecycle = AppLifecycle::WillResume;
+    }
+
+    fn suspended(&mut self, event_loop: &winit::event_loop::ActiveEventLoop) {
+        // 挂起前还有一帧可以处理，之后释放 surface，Android 上 surface 在挂起后失效
+        self.engine.lifecycle = AppLifecycle::WillSuspend;
+        self.update(event_loop);
+
+        for window_id in self.windows.windows.keys() {
+            self.engine.graphics_context.remove_window(*window_id);
+        }
+        self.engine.lifecycle = AppLifecycle::Suspended;
     }
 
     fn about_to_wait(&mut self, event_loop: &winit::event_loop::ActiveEventLoop) {
         event_loop.set_control_flow(ControlFlow::Wait);
 
-        if self.lifecycle == AppLifecycle::WillResume {
+        // 恢复时重新创建挂起时释放的 surface
+        if self.engine.lifecycle == AppLifecycle::WillResume {
             if !self.is_initialize {
                 self.is_initialize = true;
 
@@ -64,23 +135,121 @@ impl ApplicationHandler for WinitExecutor {
                     .initialize_window(&window.erased_window);
             }
 
-            self.lifecycle = AppLifecycle::Running;
+            self.engine.lifecycle = AppLifecycle::Running;
         }
 
-        self.engine.update();
+        self.update(event_loop);
+        self.apply_window_commands(event_loop);
     }
 
     fn window_event(
         &mut self,
         event_loop: &winit::event_loop::ActiveEventLoop,
-        _window_id: winit::window::WindowId,
+        window_id: winit::window::WindowId,
         event: WindowEvent,
     ) {
+        let window_id = convert_window_id(window_id);
+        if self.windows.get_window(window_id).is_none() {
+            return;
+        }
+
         match event {
-            WindowEvent::CloseRequested => event_loop.exit(),
+            // 窗口在下一次 update 之后关闭，更新系统可以先读取到 CloseRequested，
+            // 即使这是最后一个窗口
+            WindowEvent::CloseRequested => {
+                self.engine
+                    .send_event(EngineEvent::CloseRequested(window_id));
+                self.engine.close_window(window_id);
+            }
+
+            WindowEvent::RedrawRequested => self.update(event_loop),
+
+            WindowEvent::Resized(size) => {
+                if let Some(window) = self.windows.get_window_mut(window_id) {
+                    window
+                        .erased_window
+                        .window
+                        .resolution
+                        .set_physical_resolution(size.width, size.height);
+                }
+                self.engine
+                    .graphics_context
+                    .resize_window(window_id, size.width, size.height);
+                self.engine.send_event(EngineEvent::Resized(
+                    window_id,
+                    UVec2::new(size.width, size.height),
+                ));
+            }
+
+            WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+                if let Some(window) = self.windows.get_window_mut(window_id) {
+                    window
+                        .erased_window
+                        .window
+                        .resolution
+                        .set_scale_factor(scale_factor as f32);
+                }
+                self.engine.send_event(EngineEvent::ScaleFactorChanged(
+                    window_id,
+                    scale_factor as f32,
+                ));
+            }
+
+            WindowEvent::KeyboardInput { event, .. } => {
+                if let Some(key_code) = convert_key_code(event.physical_key) {
+                    match event.state {
+                        ElementState::Pressed => self.engine.input.keyboard.press(key_code),
+                        ElementState::Released => self.engine.input.keyboard.release(key_code),
+                    }
+                }
+            }
+
+            WindowEvent::MouseInput { state, button, .. } => {
+                let button = convert_mouse_button(button);
+                match state {
+                    ElementState::Pressed => self.engine.input.mouse.press(button),
+                    ElementState::Released => self.engine.input.mouse.release(button),
+                }
+            }
+
+            WindowEvent::CursorMoved { position, .. } => {
+                self.engine
+                    .input
+                    .set_cursor_position(Some(Vec2::new(position.x as f32, position.y as f32)));
+            }
+
+            WindowEvent::CursorLeft { .. } => self.engine.input.set_cursor_position(None),
+
+            WindowEvent::MouseWheel { delta, .. } => {
+                self.engine
+                    .input
+                    .add_scroll_delta(convert_scroll_delta(delta));
+            }
+
+            WindowEvent::Focused(focused) => {
+                // 失去焦点后收不到松开的事件
+                if !focused {
+                    self.engine.input.keyboard.release_all();
+                    self.engine.input.mouse.release_all();
+                }
+                self.engine
+                    .send_event(EngineEvent::Focused(window_id, focused));
+            }
 
-            WindowEvent::RedrawRequested => self.engine.update(),
             _ => {}
         }
     }
+
+    fn device_event(
+        &mut self,
+        _event_loop: &ActiveEventLoop,
+        _device_id: DeviceId,
+        event: DeviceEvent,
+    ) {
+        if let DeviceEvent::MouseMotion { delta: (x, y) } = event {
+            self.engine
+                .input
+                .add_mouse_motion(Vec2::new(x as f32, y as f32));
+        }
+    }
 }
@@ -1,4 +1,11 @@
+#[allow(clippy::module_inception)]
 pub mod engine;
+pub mod engine_event;
 pub mod executor;
+pub mod time;
+pub mod window_command;
 
 pub use engine::*;
+pub use engine_event::*;
+pub use time::*;
+pub use window_command::*;
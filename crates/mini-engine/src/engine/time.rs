@@ -0,0 +1,151 @@
+use std::time::{Duration, Instant};
+
+/// 帧与帧之间的时间，由引擎在每次 update 开始时更新
+#[derive(Debug, Clone)]
+pub struct Time {
+    startup: Instant,
+    last_update: Option<Instant>,
+    delta: Duration,
+    elapsed: Duration,
+    frame_count: u64,
+}
+
+impl Default for Time {
+    fn default() -> Self {
+        Self::new(Instant::now())
+    }
+}
+
+impl Time {
+    pub fn new(startup: Instant) -> Self {
+        Self {
+            startup,
+            last_update: None,
+            delta: Duration::ZERO,
+            elapsed: Duration::ZERO,
+            frame_count: 0,
+        }
+    }
+
+    pub fn update(&mut self) {
+        self.update_with_instant(Instant::now());
+    }
+
+    /// 第一帧的 delta 为 0
+    pub fn update_with_instant(&mut self, instant: Instant) {
+        let delta = match self.last_update {
+            Some(last_update) => instant.saturating_duration_since(last_update),
+            None => Duration::ZERO,
+        };
+        self.last_update = Some(instant);
+        self.advance_by(delta);
+    }
+
+    /// 直接推进一段时间，不读取系统时钟
+    pub fn advance_by(&mut self, delta: Duration) {
+        self.delta = delta;
+        self.elapsed += delta;
+        self.frame_count += 1;
+    }
+
+    pub fn delta(&self) -> Duration {
+        self.delta
+    }
+
+    pub fn delta_secs(&self) -> f32 {
+        self.delta.as_secs_f32()
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    pub fn elapsed_secs(&self) -> f32 {
+        self.elapsed.as_secs_f32()
+    }
+
+    pub fn startup(&self) -> Instant {
+        self.startup
+    }
+
+    pub fn frame_count(&self) -> u64 {
+        self.frame_count
+    }
+}
+
+/// 固定时间步长的累加器
+#[derive(Debug, Clone)]
+pub struct FixedTimestep {
+    timestep: Duration,
+    accumulator: Duration,
+}
+
+impl FixedTimestep {
+    pub fn new(timestep: Duration) -> Self {
+        assert!(
+            !timestep.is_zero(),
+            "The timestep must be greater than zero"
+        );
+        Self {
+            timestep,
+            accumulator: Duration::ZERO,
+        }
+    }
+
+    pub fn from_hz(hz: f64) -> Self {
+        Self::new(Duration::from_secs_f64(1.0 / hz))
+    }
+
+    pub fn timestep(&self) -> Duration {
+        self.timestep
+    }
+
+    /// 累加这一帧的时间，返回这一帧需要执行的固定步数
+    pub fn accumulate(&mut self, delta: Duration) -> u32 {
+        self.accumulator += delta;
+
+        let mut steps = 0;
+        while self.accumulator >= self.timestep {
+            self.accumulator -= self.timestep;
+            steps += 1;
+        }
+        steps
+    }
+
+    /// 累加器中剩余的时间占一个步长的比例，可以用于插值
+    pub fn overstep_fraction(&self) -> f32 {
+        self.accumulator.as_secs_f32() / self.timestep.as_secs_f32()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_fixed_timestep() {
+        let mut fixed = FixedTimestep::new(Duration::from_millis(10));
+
+        assert_eq!(fixed.accumulate(Duration::from_millis(5)), 0);
+        assert_eq!(fixed.accumulate(Duration::from_millis(5)), 1);
+        assert_eq!(fixed.accumulate(Duration::from_millis(25)), 2);
+        assert!((fixed.overstep_fraction() - 0.5).abs() < 1e-6);
+        assert_eq!(fixed.accumulate(Duration::from_millis(5)), 1);
+        assert_eq!(fixed.accumulate(Duration::ZERO), 0);
+    }
+
+    #[test]
+    fn test_time_update() {
+        let startup = Instant::now();
+        let mut time = Time::new(startup);
+
+        time.update_with_instant(startup);
+        assert_eq!(time.delta(), Duration::ZERO);
+
+        time.update_with_instant(startup + Duration::from_millis(16));
+        time.update_with_instant(startup + Duration::from_millis(40));
+        assert_eq!(time.delta(), Duration::from_millis(24));
+        assert_eq!(time.elapsed(), Duration::from_millis(40));
+        assert_eq!(time.frame_count(), 3);
+    }
+}
@@ -0,0 +1,15 @@
+use mini_window::prelude::{CursorIcon, Window, WindowId};
+
+/// 需要在窗口所在的线程上执行的操作，由 executor 在每帧处理。
+#[derive(Debug, Clone, PartialEq)]
+pub enum WindowCommand {
+    //创建一个新的窗口
+    CreateWindow(Window),
+    //关闭窗口并释放它的 surface
+    CloseWindow(WindowId),
+    /// `window` 为 `None` 时作用于主窗口
+    SetCursorIcon {
+        window: Option<WindowId>,
+        cursor_icon: CursorIcon,
+    },
+}
@@ -1,7 +1,40 @@
 pub mod engine;
 pub mod scene;
 
+/// 渲染相关的类型统一由 `mini-renderer` 提供。
+pub use mini_renderer as renderer;
+
 pub mod prelude {
     pub use crate::engine::*;
     pub use crate::scene::*;
 }
+
+#[cfg(test)]
+mod test {
+    use mini_math::prelude::Color;
+    use mini_window::window::WindowId;
+
+    use crate::renderer::{
+        renderer::Renderer,
+        surface_data::{SurfaceData, WindowSurfaceDatas},
+        test_utils::{headless_renderer, read_pixel, render_target},
+    };
+
+    #[test]
+    fn test_renderer_reexport() {
+        let Some(mut renderer): Option<Renderer> = headless_renderer() else {
+            return;
+        };
+        // 窗口的 surface 使用 mini-renderer 的 SurfaceData
+        let _: &WindowSurfaceDatas<SurfaceData> = &renderer.window_surface_datas;
+
+        // 通过 mini_engine::renderer 清屏并读回颜色
+        renderer.clear_color = Color::linear_rgb(0.0, 1.0, 0.0);
+        let texture = render_target(&renderer);
+        renderer.clear_view(&texture.create_view(&Default::default()));
+        assert_eq!(read_pixel(&renderer, &texture), [0, 255, 0, 255]);
+
+        // 没有 surface 的窗口跳过这一帧
+        assert!(renderer.render_clear(WindowId::new(0)).is_ok());
+    }
+}
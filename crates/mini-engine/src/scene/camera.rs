@@ -0,0 +1,153 @@
+use mini_math::prelude::{Mat4, Transform, Vec2, Vec3};
+
+/// 投影方式，深度范围为 wgpu 使用的 `[0, 1]`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Projection {
+    //`fov` 为垂直视角，单位为弧度
+    Perspective { fov: f32, near: f32, far: f32 },
+    //`height` 为可见区域的高度，宽度由宽高比决定
+    Orthographic { height: f32, near: f32, far: f32 },
+}
+
+impl Default for Projection {
+    fn default() -> Self {
+        Projection::Perspective {
+            fov: std::f32::consts::FRAC_PI_4,
+            near: 0.1,
+            far: 1000.0,
+        }
+    }
+}
+
+impl Projection {
+    pub fn matrix(&self, aspect_ratio: f32) -> Mat4 {
+        match *self {
+            Projection::Perspective { fov, near, far } => {
+                Mat4::perspective_rh(fov, aspect_ratio, near, far)
+            }
+            Projection::Orthographic { height, near, far } => {
+                let half_height = height * 0.5;
+                let half_width = half_height * aspect_ratio;
+                Mat4::orthographic_rh(
+                    -half_width,
+                    half_width,
+                    -half_height,
+                    half_height,
+                    near,
+                    far,
+                )
+            }
+        }
+    }
+}
+
+/// 世界空间中的射线，`direction` 为单位向量
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ray {
+    pub origin: Vec3,
+    pub direction: Vec3,
+}
+
+impl Ray {
+    pub fn get_point(&self, distance: f32) -> Vec3 {
+        self.origin + self.direction * distance
+    }
+}
+
+/// 相机，沿着 `transform` 的 `-Z` 轴观察
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Camera {
+    pub transform: Transform,
+    pub projection: Projection,
+}
+
+impl Camera {
+    pub fn new(transform: Transform, projection: Projection) -> Self {
+        Self {
+            transform,
+            projection,
+        }
+    }
+
+    /// 世界空间到相机空间的变换
+    pub fn view_matrix(&self) -> Mat4 {
+        self.transform.compute_matrix().inverse()
+    }
+
+    pub fn view_projection_matrix(&self, aspect_ratio: f32) -> Mat4 {
+        self.projection.matrix(aspect_ratio) * self.view_matrix()
+    }
+
+    /// 屏幕上 `cursor` 处的射线，用于拾取。
+    ///
+    /// `cursor` 和 `viewport` 的单位都是像素，原点在左上角。`viewport` 为空时返回 `None`。
+    pub fn screen_to_world_ray(&self, cursor: Vec2, viewport: Vec2) -> Option<Ray> {
+        if viewport.x <= 0.0 || viewport.y <= 0.0 {
+            return None;
+        }
+
+        let ndc = Vec2::new(
+            cursor.x / viewport.x * 2.0 - 1.0,
+            1.0 - cursor.y / viewport.y * 2.0,
+        );
+        let ndc_to_world = self
+            .view_projection_matrix(viewport.x / viewport.y)
+            .inverse();
+        let near = ndc_to_world.project_point3(ndc.extend(0.0));
+        let far = ndc_to_world.project_point3(ndc.extend(1.0));
+
+        let direction = (far - near).try_normalize()?;
+        Some(Ray {
+            origin: near,
+            direction,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_forward_point_projects_to_center() {
+        let transform =
+            Transform::from_xyz(1.0, 2.0, 3.0).looking_at(Vec3::new(4.0, 0.0, -2.0), Vec3::Y);
+        let point = transform.translation + transform.forward() * 5.0;
+
+        for projection in [
+            Projection::default(),
+            Projection::Orthographic {
+                height: 10.0,
+                near: 0.1,
+                far: 100.0,
+            },
+        ] {
+            let camera = Camera::new(transform, projection);
+            let ndc = camera
+                .view_projection_matrix(16.0 / 9.0)
+                .project_point3(point);
+            assert!(ndc.truncate().abs_diff_eq(Vec2::ZERO, 1e-4));
+            assert!((0.0..=1.0).contains(&ndc.z));
+        }
+    }
+
+    #[test]
+    fn test_screen_to_world_ray() {
+        let transform = Transform::from_xyz(0.0, 0.0, 5.0);
+        let camera = Camera::new(transform, Projection::default());
+        let viewport = Vec2::new(800.0, 600.0);
+
+        // 屏幕中心的射线沿着相机的朝向
+        let ray = camera
+            .screen_to_world_ray(viewport * 0.5, viewport)
+            .unwrap();
+        assert!(ray.direction.abs_diff_eq(Vec3::NEG_Z, 1e-5));
+        assert!(ray.get_point(4.9).abs_diff_eq(Vec3::ZERO, 1e-3));
+
+        // 左上角的射线指向左上方
+        let ray = camera.screen_to_world_ray(Vec2::ZERO, viewport).unwrap();
+        assert!(ray.direction.x < 0.0 && ray.direction.y > 0.0);
+
+        assert!(camera.screen_to_world_ray(Vec2::ZERO, Vec2::ZERO).is_none());
+    }
+}
@@ -1,2 +0,0 @@
-#[derive(Debug, Clone)]
-pub struct Material {}
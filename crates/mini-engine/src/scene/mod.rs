@@ -1,11 +1,185 @@
-pub mod material;
+pub mod camera;
 pub mod node;
 pub mod object;
 
-pub struct Scene {}
+use mini_core::thiserror::Error;
+use mini_math::prelude::Mat4;
+use mini_pool::prelude::{Handle, Pool};
+
+use camera::Camera;
+use node::Node;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum SceneError {
+    #[error("invalid node handle: {0:?}")]
+    InvalidHandle(Handle<Node>),
+    #[error("setting {parent:?} as the parent of {child:?} would create a cycle")]
+    Cycle {
+        child: Handle<Node>,
+        parent: Handle<Node>,
+    },
+}
+
+///场景，所有节点保存在 pool 中，通过 handle 组成父子关系
+#[derive(Default)]
+pub struct Scene {
+    nodes: Pool<Node>,
+    //渲染时使用的相机
+    camera: Option<Camera>,
+}
+
+impl Scene {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn camera(&self) -> Option<&Camera> {
+        self.camera.as_ref()
+    }
+
+    pub fn camera_mut(&mut self) -> Option<&mut Camera> {
+        self.camera.as_mut()
+    }
+
+    /// 设置渲染时使用的相机，返回之前的相机
+    pub fn set_camera(&mut self, camera: Option<Camera>) -> Option<Camera> {
+        std::mem::replace(&mut self.camera, camera)
+    }
+
+    pub fn add_node(&mut self, node: Node) -> Handle<Node> {
+        self.nodes.spawn(node)
+    }
+
+    pub fn try_get(&self, handle: Handle<Node>) -> Option<&Node> {
+        self.nodes.try_borrow(handle)
+    }
+
+    pub fn try_get_mut(&mut self, handle: Handle<Node>) -> Option<&mut Node> {
+        self.nodes.try_borrow_mut(handle)
+    }
+
+    /// 设置父节点，`None` 表示成为根节点。
+    pub fn set_parent(
+        &mut self,
+        child: Handle<Node>,
+        parent: Option<Handle<Node>>,
+    ) -> Result<(), SceneError> {
+        if !self.nodes.is_valid(child) {
+            return Err(SceneError::InvalidHandle(child));
+        }
+
+        if let Some(parent) = parent {
+            if !self.nodes.is_valid(parent) {
+                return Err(SceneError::InvalidHandle(parent));
+            }
+
+            // 父节点不能是 child 自己或者 child 的子孙节点
+            let mut current = Some(parent);
+            while let Some(handle) = current {
+                if handle == child {
+                    return Err(SceneError::Cycle { child, parent });
+                }
+                current = self.nodes.borrow(handle).parent;
+            }
+        }
+
+        if let Some(old_parent) = self.nodes.borrow(child).parent {
+            self.nodes
+                .borrow_mut(old_parent)
+                .children
+                .retain(|handle| *handle != child);
+        }
+
+        self.nodes.borrow_mut(child).parent = parent;
+        if let Some(parent) = parent {
+            self.nodes.borrow_mut(parent).children.push(child);
+        }
+
+        Ok(())
+    }
+
+    /// 沿着父节点链计算世界变换。
+    ///
+    /// # Panics
+    /// handle 失效时 panic。
+    pub fn global_transform(&self, handle: Handle<Node>) -> Mat4 {
+        let mut node = self.nodes.borrow(handle);
+        let mut matrix = node.transform.compute_matrix();
+        while let Some(parent) = node.parent {
+            node = self.nodes.borrow(parent);
+            matrix = node.transform.compute_matrix() * matrix;
+        }
+        matrix
+    }
+}
 
 pub mod prelude {
-    pub use super::material::*;
+    pub use super::camera::*;
     pub use super::node::*;
     pub use super::object::*;
+    pub use super::{Scene, SceneError};
+}
+
+#[cfg(test)]
+mod test {
+    use mini_math::prelude::{Quat, Transform, Vec3};
+
+    use super::{object::ObjectTrait, prelude::*};
+
+    #[derive(Clone)]
+    struct Pivot;
+
+    impl ObjectTrait for Pivot {}
+    impl NodeTrait for Pivot {}
+
+    #[test]
+    fn test_global_transform() {
+        let mut scene = Scene::new();
+        let root = scene.add_node(
+            Node::new(Pivot).with_transform(Transform::from_translation(Vec3::new(1.0, 0.0, 0.0))),
+        );
+        let child = scene.add_node(Node::new(Pivot).with_transform(Transform {
+            translation: Vec3::new(0.0, 2.0, 0.0),
+            rotation: Quat::from_rotation_z(std::f32::consts::FRAC_PI_2),
+            scale: Vec3::ONE,
+        }));
+        let leaf = scene.add_node(
+            Node::new(Pivot).with_transform(Transform::from_translation(Vec3::new(1.0, 0.0, 0.0))),
+        );
+
+        scene.set_parent(child, Some(root)).unwrap();
+        scene.set_parent(leaf, Some(child)).unwrap();
+
+        let position = scene.global_transform(leaf).transform_point3(Vec3::ZERO);
+        assert!(position.abs_diff_eq(Vec3::new(1.0, 3.0, 0.0), 1e-5));
+        assert_eq!(scene.try_get(root).unwrap().children(), &[child]);
+    }
+
+    #[test]
+    fn test_set_parent_cycle() {
+        let mut scene = Scene::new();
+        let a = scene.add_node(Node::new(Pivot));
+        let b = scene.add_node(Node::new(Pivot));
+        scene.set_parent(b, Some(a)).unwrap();
+
+        assert_eq!(
+            scene.set_parent(a, Some(b)),
+            Err(SceneError::Cycle {
+                child: a,
+                parent: b
+            })
+        );
+        assert_eq!(
+            scene.set_parent(a, Some(a)),
+            Err(SceneError::Cycle {
+                child: a,
+                parent: a
+            })
+        );
+
+        // 重新挂载时从旧的父节点移除
+        scene.set_parent(b, None).unwrap();
+        assert!(scene.try_get(a).unwrap().children().is_empty());
+        scene.set_parent(a, Some(b)).unwrap();
+    }
 }
@@ -1,3 +1,6 @@
+use mini_math::prelude::Transform;
+use mini_pool::prelude::Handle;
+
 use super::object::{ErasedObjectTrait, ObjectTrait};
 
 pub trait NodeTrait: Clone {}
@@ -6,4 +9,38 @@ impl<T: NodeTrait + ObjectTrait> ErasedNodeTrait for T {}
 
 pub trait ErasedNodeTrait: ErasedObjectTrait {}
 
-pub struct Node(Box<dyn ErasedNodeTrait>);
+pub struct Node {
+    //局部变换
+    pub transform: Transform,
+    pub(crate) parent: Option<Handle<Node>>,
+    pub(crate) children: Vec<Handle<Node>>,
+    inner: Box<dyn ErasedNodeTrait>,
+}
+
+impl Node {
+    pub fn new<T: NodeTrait + ObjectTrait>(node: T) -> Self {
+        Node {
+            transform: Transform::default(),
+            parent: None,
+            children: vec![],
+            inner: Box::new(node),
+        }
+    }
+
+    pub fn with_transform(mut self, transform: Transform) -> Self {
+        self.transform = transform;
+        self
+    }
+
+    pub fn inner(&self) -> &dyn ErasedNodeTrait {
+        &*self.inner
+    }
+
+    pub fn parent(&self) -> Option<Handle<Node>> {
+        self.parent
+    }
+
+    pub fn children(&self) -> &[Handle<Node>] {
+        &self.children
+    }
+}
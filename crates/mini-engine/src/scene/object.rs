@@ -7,3 +7,13 @@ impl<T: ObjectTrait> ErasedObjectTrait for T {}
 pub trait ErasedObjectTrait: Downcast {}
 
 pub struct Object(Box<dyn ErasedObjectTrait>);
+
+impl Object {
+    pub fn new<T: ObjectTrait>(object: T) -> Self {
+        Object(Box::new(object))
+    }
+
+    pub fn inner(&self) -> &dyn ErasedObjectTrait {
+        &*self.0
+    }
+}
@@ -0,0 +1,214 @@
+/// 颜色，分别保存 sRGB 和线性空间下的 RGBA，所有通道都会被限制在 `[0, 1]`。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Color {
+    Srgba {
+        red: f32,
+        green: f32,
+        blue: f32,
+        alpha: f32,
+    },
+    LinearRgba {
+        red: f32,
+        green: f32,
+        blue: f32,
+        alpha: f32,
+    },
+}
+
+impl Default for Color {
+    fn default() -> Self {
+        Self::WHITE
+    }
+}
+
+impl Color {
+    pub const WHITE: Color = Color::Srgba {
+        red: 1.0,
+        green: 1.0,
+        blue: 1.0,
+        alpha: 1.0,
+    };
+
+    pub const BLACK: Color = Color::Srgba {
+        red: 0.0,
+        green: 0.0,
+        blue: 0.0,
+        alpha: 1.0,
+    };
+
+    pub const RED: Color = Color::Srgba {
+        red: 1.0,
+        green: 0.0,
+        blue: 0.0,
+        alpha: 1.0,
+    };
+
+    pub fn srgba(red: f32, green: f32, blue: f32, alpha: f32) -> Self {
+        Color::Srgba {
+            red: red.clamp(0.0, 1.0),
+            green: green.clamp(0.0, 1.0),
+            blue: blue.clamp(0.0, 1.0),
+            alpha: alpha.clamp(0.0, 1.0),
+        }
+    }
+
+    pub fn srgb(red: f32, green: f32, blue: f32) -> Self {
+        Self::srgba(red, green, blue, 1.0)
+    }
+
+    pub fn linear_rgba(red: f32, green: f32, blue: f32, alpha: f32) -> Self {
+        Color::LinearRgba {
+            red: red.clamp(0.0, 1.0),
+            green: green.clamp(0.0, 1.0),
+            blue: blue.clamp(0.0, 1.0),
+            alpha: alpha.clamp(0.0, 1.0),
+        }
+    }
+
+    pub fn linear_rgb(red: f32, green: f32, blue: f32) -> Self {
+        Self::linear_rgba(red, green, blue, 1.0)
+    }
+
+    /// 转换到线性空间，alpha 保持不变。
+    pub fn to_linear(&self) -> Color {
+        match *self {
+            Color::Srgba {
+                red,
+                green,
+                blue,
+                alpha,
+            } => Color::LinearRgba {
+                red: srgb_to_linear(red),
+                green: srgb_to_linear(green),
+                blue: srgb_to_linear(blue),
+                alpha,
+            },
+            linear => linear,
+        }
+    }
+
+    /// 转换到 sRGB 空间，alpha 保持不变。
+    pub fn to_srgba(&self) -> Color {
+        match *self {
+            Color::LinearRgba {
+                red,
+                green,
+                blue,
+                alpha,
+            } => Color::Srgba {
+                red: linear_to_srgb(red),
+                green: linear_to_srgb(green),
+                blue: linear_to_srgb(blue),
+                alpha,
+            },
+            srgba => srgba,
+        }
+    }
+
+    /// 当前空间下的 `[r, g, b, a]`
+    pub fn to_array(&self) -> [f32; 4] {
+        match *self {
+            Color::Srgba {
+                red,
+                green,
+                blue,
+                alpha,
+            }
+            | Color::LinearRgba {
+                red,
+                green,
+                blue,
+                alpha,
+            } => [red, green, blue, alpha],
+        }
+    }
+
+    /// 线性空间下的 `[r, g, b, a]`，用于顶点颜色和 uniform。
+    pub fn to_linear_array(&self) -> [f32; 4] {
+        self.to_linear().to_array()
+    }
+
+    /// 转换为线性空间下的 [`wgpu_types::Color`]
+    #[cfg(feature = "wgpu-types")]
+    pub fn as_wgpu_color(&self) -> wgpu_types::Color {
+        let [r, g, b, a] = self.to_linear_array();
+        wgpu_types::Color {
+            r: r as f64,
+            g: g as f64,
+            b: b as f64,
+            a: a as f64,
+        }
+    }
+}
+
+fn srgb_to_linear(value: f32) -> f32 {
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(value: f32) -> f32 {
+    if value <= 0.0031308 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn assert_close(a: [f32; 4], b: [f32; 4]) {
+        for (a, b) in a.iter().zip(b) {
+            assert!((a - b).abs() < 1e-3, "{a} != {b}");
+        }
+    }
+
+    #[test]
+    fn test_srgb_linear() {
+        let pairs = [
+            (0.0, 0.0),
+            (0.02, 0.001548),
+            (0.5, 0.214),
+            (0.8, 0.6038),
+            (1.0, 1.0),
+        ];
+        for (srgb, linear) in pairs {
+            let color = Color::srgba(srgb, srgb, srgb, 0.5);
+            assert_close(color.to_linear().to_array(), [linear, linear, linear, 0.5]);
+
+            let color = Color::linear_rgba(linear, linear, linear, 0.5);
+            assert_close(color.to_srgba().to_array(), [srgb, srgb, srgb, 0.5]);
+        }
+
+        assert_eq!(
+            Color::srgba(2.0, -1.0, 0.5, 1.0).to_array(),
+            [1.0, 0.0, 0.5, 1.0]
+        );
+        assert_eq!(Color::RED.to_linear_array(), [1.0, 0.0, 0.0, 1.0]);
+    }
+
+    #[cfg(feature = "wgpu-types")]
+    #[test]
+    fn test_as_wgpu_color() {
+        // 线性颜色的通道直接转换为 f64
+        let color = Color::linear_rgba(0.1, 0.25, 0.7, 0.4).as_wgpu_color();
+        assert_eq!(color.r, 0.1f32 as f64);
+        assert_eq!(color.g, 0.25);
+        assert_eq!(color.b, 0.7f32 as f64);
+        assert_eq!(color.a, 0.4f32 as f64);
+
+        // sRGB 颜色先转换为线性颜色，alpha 不变
+        let color = Color::srgba(0.5, 0.8, 0.02, 0.5).as_wgpu_color();
+        let expected = [0.214, 0.6038, 0.001548, 0.5];
+        for (channel, expected) in [color.r, color.g, color.b, color.a]
+            .into_iter()
+            .zip(expected)
+        {
+            assert!((channel - expected).abs() < 1e-3, "{channel} != {expected}");
+        }
+    }
+}
@@ -1,8 +1,13 @@
+pub mod color;
+pub mod transform;
+
 pub use glam::*;
 
 pub mod prelude {
 
     pub use crate::{
+        color::Color,
+        transform::{GlobalTransform, Transform},
         BVec2, BVec3, BVec4, EulerRot, FloatExt, IVec2, IVec3, IVec4, Mat2, Mat3, Mat4, Quat,
         UVec2, UVec3, UVec4, Vec2, Vec2Swizzles, Vec3, Vec3Swizzles, Vec4, Vec4Swizzles,
     };
@@ -0,0 +1,230 @@
+use std::ops::Mul;
+
+use glam::{Affine3A, Mat3, Mat4, Quat, Vec3};
+
+/// 仿射变换：先缩放，再旋转，最后平移
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform {
+    pub translation: Vec3,
+    pub rotation: Quat,
+    pub scale: Vec3,
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Transform {
+    pub const IDENTITY: Self = Transform {
+        translation: Vec3::ZERO,
+        rotation: Quat::IDENTITY,
+        scale: Vec3::ONE,
+    };
+
+    #[inline]
+    pub const fn from_xyz(x: f32, y: f32, z: f32) -> Self {
+        Self::from_translation(Vec3::new(x, y, z))
+    }
+
+    #[inline]
+    pub const fn from_translation(translation: Vec3) -> Self {
+        Transform {
+            translation,
+            ..Self::IDENTITY
+        }
+    }
+
+    #[inline]
+    pub const fn from_rotation(rotation: Quat) -> Self {
+        Transform {
+            rotation,
+            ..Self::IDENTITY
+        }
+    }
+
+    #[inline]
+    pub const fn from_scale(scale: Vec3) -> Self {
+        Transform {
+            scale,
+            ..Self::IDENTITY
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn with_translation(mut self, translation: Vec3) -> Self {
+        self.translation = translation;
+        self
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn with_rotation(mut self, rotation: Quat) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn with_scale(mut self, scale: Vec3) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// 旋转变换，使局部的 `-Z` 轴指向 `target`，`Y` 轴尽量靠近 `up`。
+    #[inline]
+    #[must_use]
+    pub fn looking_at(mut self, target: Vec3, up: Vec3) -> Self {
+        self.look_at(target, up);
+        self
+    }
+
+    /// 参考 [`Transform::looking_at`]，`target` 与当前位置重合或 `up` 与朝向平行时保持不变。
+    pub fn look_at(&mut self, target: Vec3, up: Vec3) {
+        let back = (self.translation - target).normalize_or_zero();
+        let right = up.cross(back).normalize_or_zero();
+        if back == Vec3::ZERO || right == Vec3::ZERO {
+            return;
+        }
+        let up = back.cross(right);
+        self.rotation = Quat::from_mat3(&Mat3::from_cols(right, up, back));
+    }
+
+    /// 局部的 `-Z` 轴
+    #[inline]
+    pub fn forward(&self) -> Vec3 {
+        self.rotation * Vec3::NEG_Z
+    }
+
+    #[inline]
+    pub fn compute_matrix(&self) -> Mat4 {
+        Mat4::from_scale_rotation_translation(self.scale, self.rotation, self.translation)
+    }
+
+    #[inline]
+    pub fn compute_affine(&self) -> Affine3A {
+        Affine3A::from_scale_rotation_translation(self.scale, self.rotation, self.translation)
+    }
+
+    /// 将 `other` 作为子变换组合，结果等价于先应用 `other` 再应用 `self`。
+    #[inline]
+    #[must_use]
+    pub fn mul_transform(&self, other: Transform) -> Self {
+        Transform {
+            translation: self.transform_point(other.translation),
+            rotation: self.rotation * other.rotation,
+            scale: self.scale * other.scale,
+        }
+    }
+
+    #[inline]
+    pub fn transform_point(&self, point: Vec3) -> Vec3 {
+        self.rotation * (self.scale * point) + self.translation
+    }
+}
+
+impl Mul<Transform> for Transform {
+    type Output = Transform;
+
+    fn mul(self, transform: Transform) -> Self::Output {
+        self.mul_transform(transform)
+    }
+}
+
+/// 节点在世界空间中的变换
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GlobalTransform(pub Mat4);
+
+impl Default for GlobalTransform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl GlobalTransform {
+    pub const IDENTITY: Self = GlobalTransform(Mat4::IDENTITY);
+
+    #[inline]
+    pub fn compute_matrix(&self) -> Mat4 {
+        self.0
+    }
+
+    #[inline]
+    pub fn translation(&self) -> Vec3 {
+        self.0.w_axis.truncate()
+    }
+
+    #[inline]
+    pub fn transform_point(&self, point: Vec3) -> Vec3 {
+        self.0.transform_point3(point)
+    }
+
+    /// 将局部变换 `transform` 组合到当前的世界变换上。
+    #[inline]
+    pub fn mul_transform(&self, transform: Transform) -> Self {
+        GlobalTransform(self.0 * transform.compute_matrix())
+    }
+}
+
+impl From<Transform> for GlobalTransform {
+    fn from(transform: Transform) -> Self {
+        GlobalTransform(transform.compute_matrix())
+    }
+}
+
+impl From<Mat4> for GlobalTransform {
+    fn from(matrix: Mat4) -> Self {
+        GlobalTransform(matrix)
+    }
+}
+
+impl Mul<Transform> for GlobalTransform {
+    type Output = GlobalTransform;
+
+    fn mul(self, transform: Transform) -> Self::Output {
+        self.mul_transform(transform)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_looking_at() {
+        let target = Vec3::new(3.0, -2.0, 5.0);
+        let transform = Transform::from_xyz(1.0, 2.0, 3.0).looking_at(target, Vec3::Y);
+
+        let expected = (target - transform.translation).normalize();
+        assert!(transform.forward().abs_diff_eq(expected, 1e-5));
+
+        // 矩阵的 -Z 轴同样指向目标
+        let matrix = transform.compute_matrix();
+        let forward = -matrix.z_axis.truncate();
+        assert!(forward.abs_diff_eq(expected, 1e-5));
+        assert!(matrix
+            .transform_point3(-Vec3::Z * expected.length())
+            .abs_diff_eq(transform.translation + expected, 1e-5));
+    }
+
+    #[test]
+    fn test_mul_transform() {
+        let parent = Transform::from_xyz(1.0, 0.0, 0.0)
+            .with_rotation(Quat::from_rotation_z(std::f32::consts::FRAC_PI_2))
+            .with_scale(Vec3::splat(2.0));
+        let child = Transform::from_xyz(1.0, 0.0, 0.0);
+
+        let combined = parent * child;
+        let point = Vec3::new(0.5, 0.0, 0.0);
+        assert!(combined
+            .transform_point(point)
+            .abs_diff_eq(parent.transform_point(child.transform_point(point)), 1e-5));
+
+        let global = GlobalTransform::from(parent) * child;
+        assert!(global
+            .compute_matrix()
+            .abs_diff_eq(combined.compute_matrix(), 1e-5));
+    }
+}
@@ -15,11 +15,33 @@ where
     free_stack: Vec<u32>,
 }
 
+impl<T, P> Default for Pool<T, P>
+where
+    T: Sized,
+    P: PayloadContainer<Element = T>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<T, P> Pool<T, P>
 where
     T: Sized,
     P: PayloadContainer<Element = T>,
 {
+    pub fn new() -> Self {
+        Pool {
+            records: Vec::new(),
+            free_stack: Vec::new(),
+        }
+    }
+
+    fn records_get(&self, index: u32) -> Option<&PoolRecord<T, P>> {
+        let index = usize::try_from(index).expect("Index overflowed usize");
+        self.records.get(index)
+    }
+
     fn records_get_mut(&mut self, index: u32) -> Option<&mut PoolRecord<T, P>> {
         let index = usize::try_from(index).expect("Index overflowed usize");
         self.records.get_mut(index)
@@ -76,6 +98,121 @@ where
             handle
         }
     }
+
+    /// 释放 handle 对应的对象，槽位会在之后的 [`Pool::spawn`] 中被复用。
+    pub fn free(&mut self, handle: Handle<T>) -> Option<T> {
+        let record = self.records_get_mut(handle.index)?;
+        if record.generation != handle.generation {
+            return None;
+        }
+        let payload = record.payload.take()?;
+        self.free_stack.push(handle.index);
+        Some(payload)
+    }
+
+    /// handle 指向的槽位仍然被同一代的对象占用时返回 true。
+    #[inline]
+    pub fn is_valid(&self, handle: Handle<T>) -> bool {
+        self.try_borrow(handle).is_some()
+    }
+
+    /// 失效的 handle（对象已释放或槽位已被复用）返回 `None`。
+    #[inline]
+    pub fn try_borrow(&self, handle: Handle<T>) -> Option<&T> {
+        self.records_get(handle.index)
+            .filter(|record| record.generation == handle.generation)
+            .and_then(|record| record.payload.as_ref())
+    }
+
+    #[inline]
+    pub fn try_borrow_mut(&mut self, handle: Handle<T>) -> Option<&mut T> {
+        self.records_get_mut(handle.index)
+            .filter(|record| record.generation == handle.generation)
+            .and_then(|record| record.payload.as_mut())
+    }
+
+    /// # Panics
+    /// handle 失效时 panic。
+    #[inline]
+    pub fn borrow(&self, handle: Handle<T>) -> &T {
+        self.try_borrow(handle).unwrap_or_else(|| {
+            panic!("Attempt to borrow an object using invalid handle {handle:?}")
+        })
+    }
+
+    /// # Panics
+    /// handle 失效时 panic。
+    #[inline]
+    pub fn borrow_mut(&mut self, handle: Handle<T>) -> &mut T {
+        self.try_borrow_mut(handle).unwrap_or_else(|| {
+            panic!("Attempt to borrow an object using invalid handle {handle:?}")
+        })
+    }
+
+    /// 同时可变借用两个对象，两个 handle 指向同一个槽位或者任意一个失效时返回 `None`。
+    pub fn get_two_mut(&mut self, a: Handle<T>, b: Handle<T>) -> Option<(&mut T, &mut T)> {
+        if a.index == b.index || !self.is_valid(a) || !self.is_valid(b) {
+            return None;
+        }
+
+        let (low, high) = (a.index.min(b.index) as usize, a.index.max(b.index) as usize);
+        let (head, tail) = self.records.split_at_mut(high);
+        let low = head[low].payload.as_mut()?;
+        let high = tail[0].payload.as_mut()?;
+        if a.index < b.index {
+            Some((low, high))
+        } else {
+            Some((high, low))
+        }
+    }
+
+    /// 通过引用查找对象的 handle，只比较地址，`item` 不在池中时返回 `None`。
+    pub fn handle_of(&self, item: &T) -> Option<Handle<T>> {
+        self.pair_iter()
+            .find(|(_, payload)| std::ptr::eq(*payload, item))
+            .map(|(handle, _)| handle)
+    }
+
+    /// 所有槽位的数量，包括已经释放的槽位。
+    #[inline]
+    pub fn total_count(&self) -> usize {
+        self.records.len()
+    }
+
+    /// 正在使用的对象数量。
+    #[inline]
+    pub fn alive_count(&self) -> usize {
+        self.records.len() - self.free_stack.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.records
+            .iter()
+            .filter_map(|record| record.payload.as_ref())
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.records
+            .iter_mut()
+            .filter_map(|record| record.payload.as_mut())
+    }
+
+    /// 遍历所有存活的对象及其 handle。
+    pub fn pair_iter(&self) -> impl Iterator<Item = (Handle<T>, &T)> {
+        self.records
+            .iter()
+            .enumerate()
+            .filter_map(|(index, record)| {
+                record.payload.as_ref().map(|payload| {
+                    let handle = Handle {
+                        index: index as u32,
+                        generation: record.generation,
+                        type_marker: PhantomData,
+                    };
+                    (handle, payload)
+                })
+            })
+    }
 }
 
 #[derive(Debug)]
@@ -88,3 +225,68 @@ where
     generation: u32,
     payload: Payload<P>,
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_stale_handle() {
+        let mut pool = Pool::<u32>::new();
+        let a = pool.spawn(1);
+        let b = pool.spawn(2);
+
+        assert_eq!(pool.free(a), Some(1));
+        assert!(!pool.is_valid(a));
+        assert_eq!(pool.free(a), None);
+
+        // 复用了 a 的槽位，但 generation 不同
+        let c = pool.spawn(3);
+        assert_eq!(c.index(), a.index());
+        assert_ne!(c.generation(), a.generation());
+
+        assert!(!pool.is_valid(a));
+        assert_eq!(pool.try_borrow(a), None);
+        assert_eq!(pool.try_borrow_mut(a), None);
+        assert_eq!(*pool.borrow(c), 3);
+
+        assert_eq!(pool.total_count(), 2);
+        assert_eq!(pool.alive_count(), 2);
+        assert_eq!(pool.pair_iter().collect::<Vec<_>>(), vec![(c, &3), (b, &2)]);
+    }
+
+    #[test]
+    fn test_get_two_mut() {
+        let mut pool = Pool::<u32>::new();
+        let a = pool.spawn(1);
+        let b = pool.spawn(2);
+
+        let (x, y) = pool.get_two_mut(b, a).unwrap();
+        assert_eq!((*x, *y), (2, 1));
+        std::mem::swap(x, y);
+        assert_eq!(*pool.borrow(a), 2);
+        assert_eq!(*pool.borrow(b), 1);
+
+        // 同一个 handle 会产生别名，必须拒绝
+        assert!(pool.get_two_mut(a, a).is_none());
+
+        // 复用槽位后旧的 handle 和新的 handle 指向同一个槽位
+        pool.free(a);
+        let c = pool.spawn(3);
+        assert!(pool.get_two_mut(a, c).is_none());
+        assert!(pool.get_two_mut(a, b).is_none());
+        assert!(pool.get_two_mut(c, b).is_some());
+    }
+
+    #[test]
+    fn test_handle_of() {
+        let mut pool = Pool::<u32>::new();
+        let a = pool.spawn(1);
+        let b = pool.spawn(1);
+
+        assert_eq!(pool.handle_of(pool.borrow(b)), Some(b));
+        assert_eq!(pool.handle_of(pool.borrow(a)), Some(a));
+        // 值相同但不在池中
+        assert_eq!(pool.handle_of(&1), None);
+    }
+}
@@ -1,24 +1,36 @@
 use std::sync::Arc;
 
 use mini_core::{futures_lite, parking_lot::Mutex};
+use mini_math::prelude::Color;
 use mini_resource::prelude::ResourceManager;
-use mini_window::window::ErasedWindow;
+use mini_window::window::{ErasedWindow, WindowId};
 
 use crate::{
+    material::StandardMaterial,
     renderer::{RenderAdapter, RenderDevice, RenderInstance, RenderQueue, Renderer},
+    settings::RenderSettings,
+    texture::prelude::FallbackTexture,
     wrapper::WgpuWrapper,
 };
 
-use wgpu::{DeviceDescriptor, Instance, MemoryHints, Surface, SurfaceTargetUnsafe};
+use wgpu::{Instance, Surface, SurfaceError, SurfaceTargetUnsafe};
 
 pub struct InitializedGraphicsContext {
-    renderer: Renderer,
+    renderer: Box<Renderer>,
 }
 
 impl InitializedGraphicsContext {
-    pub fn render(&mut self) {
+    pub fn render(&mut self) -> Result<(), SurfaceError> {
         self.renderer.render()
     }
+
+    pub fn renderer(&self) -> &Renderer {
+        &self.renderer
+    }
+
+    pub fn renderer_mut(&mut self) -> &mut Renderer {
+        &mut self.renderer
+    }
 }
 
 pub enum GraphicsContext {
@@ -31,29 +43,42 @@ type FutureRendererResources =
 
 impl GraphicsContext {
     pub fn initialize(&mut self, window: &ErasedWindow, resource_manager: &ResourceManager) {
-        self.initialize_graphics_context(window);
+        self.initialize_with_settings(window, resource_manager, &RenderSettings::default());
+    }
+
+    pub fn initialize_with_settings(
+        &mut self,
+        window: &ErasedWindow,
+        resource_manager: &ResourceManager,
+        settings: &RenderSettings,
+    ) {
+        self.initialize_graphics_context(window, settings);
         self.build_resource_manager(resource_manager);
     }
 
-    pub fn build_resource_manager(&mut self, _resource_manager: &ResourceManager) {}
+    pub fn build_resource_manager(&mut self, resource_manager: &ResourceManager) {
+        let fallback_texture = FallbackTexture::register(resource_manager);
+        StandardMaterial::register_shader(resource_manager);
 
-    fn initialize_graphics_context(&mut self, window: &ErasedWindow) {
+        if let GraphicsContext::Initialized(context) = self {
+            context.renderer.fallback_texture = Some(fallback_texture);
+        }
+    }
+
+    fn initialize_graphics_context(&mut self, window: &ErasedWindow, settings: &RenderSettings) {
         let future_renderer_resources: FutureRendererResources = Arc::new(Mutex::new(None));
 
         let window_clone = window.raw_handle_wrapper_holder.clone();
         let future_renderer_resources_clone = future_renderer_resources.clone();
+        let device_descriptor = settings.device_descriptor();
+        let trace_path = settings.device_trace_path().map(|path| path.to_path_buf());
 
         let async_renderer = async move {
             let target = {
                 let raw_handle = window_clone.0.lock();
                 let (raw_display_handle, raw_window_handle) = (*raw_handle)
                     .as_ref()
-                    .and_then(|raw_handle| {
-                        Some((
-                            raw_handle.display_handle.clone(),
-                            raw_handle.window_handle.clone(),
-                        ))
-                    })
+                    .map(|raw_handle| (raw_handle.display_handle, raw_handle.window_handle))
                     .unwrap();
 
                 SurfaceTargetUnsafe::RawHandle {
@@ -78,16 +103,7 @@ impl GraphicsContext {
             let adapter = instance.request_adapter(&options).await.unwrap();
 
             let (device, queue) = adapter
-                .request_device(
-                    &DeviceDescriptor {
-                        required_features: wgpu::Features::empty(),
-
-                        required_limits: wgpu::Limits::default(),
-                        label: None,
-                        memory_hints: MemoryHints::default(),
-                    },
-                    None, // Trace path
-                )
+                .request_device(&device_descriptor, trace_path.as_deref())
                 .await
                 .unwrap();
 
@@ -104,8 +120,11 @@ impl GraphicsContext {
 
         let (device, queue, instance, adapter) = future_renderer_resources.lock().take().unwrap();
 
+        let mut renderer = Renderer::new(device, queue, instance, adapter);
+        renderer.sample_count = settings.sample_count;
+
         *self = GraphicsContext::Initialized(InitializedGraphicsContext {
-            renderer: Renderer::new(device, queue, instance, adapter),
+            renderer: Box::new(renderer),
         })
     }
 
@@ -115,9 +134,37 @@ impl GraphicsContext {
         }
     }
 
-    pub fn render(&mut self) {
+    pub fn remove_window(&mut self, window_id: WindowId) {
+        if let GraphicsContext::Initialized(context) = self {
+            context.renderer.remove_window(window_id)
+        }
+    }
+
+    /// 没有初始化时什么都不做，错误见 [`Renderer::render`]
+    pub fn render(&mut self) -> Result<(), SurfaceError> {
+        match self {
+            GraphicsContext::Initialized(context) => context.render(),
+            GraphicsContext::Uninitialized => Ok(()),
+        }
+    }
+
+    pub fn resize_window(&mut self, window_id: WindowId, width: u32, height: u32) {
+        if let GraphicsContext::Initialized(context) = self {
+            context.renderer.resize_window(window_id, width, height)
+        }
+    }
+
+    /// 只清空窗口，见 [`Renderer::render_clear`]
+    pub fn render_clear(&mut self, window_id: WindowId) -> Result<(), SurfaceError> {
+        match self {
+            GraphicsContext::Initialized(context) => context.renderer.render_clear(window_id),
+            GraphicsContext::Uninitialized => Ok(()),
+        }
+    }
+
+    pub fn set_clear_color(&mut self, color: Color) {
         if let GraphicsContext::Initialized(context) = self {
-            context.render();
+            context.renderer.clear_color = color;
         }
     }
 }
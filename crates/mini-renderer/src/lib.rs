@@ -1,4 +1,63 @@
 pub mod graphics_context;
+pub mod material;
+pub mod mesh;
+pub mod render_resource;
 pub mod renderer;
+pub mod settings;
 pub mod surface_data;
+pub mod texture;
 pub mod wrapper;
+
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
+
+pub use wgpu;
+
+#[cfg(test)]
+mod test {
+    use std::{any::type_name, path::PathBuf};
+
+    use mini_core::type_uuid::{registry::try_register_type_uuid, TypeUuidProvider};
+    use mini_resource::prelude::{LoadedFolder, UntypedResource};
+
+    use crate::{material::StandardMaterial, render_resource::Shader, texture::prelude::Image};
+
+    fn register<T: TypeUuidProvider + 'static>() {
+        if let Err(existing) = try_register_type_uuid::<T>() {
+            panic!(
+                "{existing} and {} have the same type uuid {}",
+                type_name::<T>(),
+                T::type_uuid()
+            );
+        }
+    }
+
+    #[test]
+    fn test_type_uuids_are_distinct() {
+        // mini-core
+        register::<u8>();
+        register::<i8>();
+        register::<u16>();
+        register::<i16>();
+        register::<u32>();
+        register::<i32>();
+        register::<u64>();
+        register::<i64>();
+        register::<f32>();
+        register::<f64>();
+        register::<usize>();
+        register::<isize>();
+        register::<bool>();
+        register::<PathBuf>();
+        register::<String>();
+
+        // mini-resource
+        register::<UntypedResource>();
+        register::<LoadedFolder>();
+
+        // mini-renderer
+        register::<Image>();
+        register::<Shader>();
+        register::<StandardMaterial>();
+    }
+}
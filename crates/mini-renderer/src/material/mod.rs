@@ -0,0 +1,72 @@
+mod standard_material;
+
+pub use standard_material::*;
+
+use std::any::type_name;
+
+use mini_resource::{io::ResourcePath, prelude::ResourceData};
+use wgpu::{BindGroupLayoutEntry, Face, TextureFormat};
+
+use crate::{
+    mesh::mesh::{Mesh, MeshVertexBufferLayoutRef, VertexAttributeDescriptor},
+    render_resource::{AlphaMode, PipelineError, RenderPipelineDescriptor, Shader},
+};
+
+/// 材质，提供着色器和绑定组的布局，和网格的顶点布局一起决定渲染管线
+pub trait Material: ResourceData {
+    /// 着色器的路径，需要包含 `vs_main` 和 `fs_main` 两个入口
+    fn fragment_shader() -> ResourcePath<'static>;
+
+    /// `@group(0)` 的绑定
+    fn bind_group_layout() -> Vec<BindGroupLayoutEntry>;
+
+    /// 顶点着色器需要的网格属性，默认为位置和第一套 uv
+    fn vertex_attributes() -> Vec<VertexAttributeDescriptor> {
+        vec![
+            Mesh::ATTRIBUTE_POSITION.at_shader_location(0),
+            Mesh::ATTRIBUTE_UV_0.at_shader_location(1),
+        ]
+    }
+
+    /// 决定渲染时颜色附件的混合状态
+    fn alpha_mode(&self) -> AlphaMode {
+        AlphaMode::default()
+    }
+
+    /// 剔除的面，为 `None` 时双面渲染，例如植被
+    fn cull_mode(&self) -> Option<Face> {
+        Some(Face::Back)
+    }
+
+    /// 把材质实例的渲染状态写入 [`pipeline_descriptor`](Self::pipeline_descriptor) 返回的描述
+    fn specialize(&self, descriptor: &mut RenderPipelineDescriptor) {
+        descriptor.set_alpha_mode(self.alpha_mode());
+        descriptor.primitive.cull_mode = self.cull_mode();
+    }
+
+    /// 材质在网格上渲染时使用的管线描述，描述同时是管线的缓存键
+    fn pipeline_descriptor(
+        shader: &Shader,
+        layout: &MeshVertexBufferLayoutRef,
+        format: TextureFormat,
+    ) -> Result<RenderPipelineDescriptor, PipelineError> {
+        let vertex_buffer =
+            layout
+                .0
+                .get_layout(&Self::vertex_attributes())
+                .map_err(|mut err| {
+                    err.pipeline_type = Some(type_name::<Self>());
+                    err
+                })?;
+
+        Ok(RenderPipelineDescriptor {
+            label: Some(type_name::<Self>().to_string()),
+            shader: shader.source.clone(),
+            vertex_buffers: vec![vertex_buffer],
+            targets: vec![Some(format.into())],
+            bind_group_layouts: vec![Self::bind_group_layout()],
+            depth_stencil: Some(RenderPipelineDescriptor::default_depth_stencil()),
+            ..Default::default()
+        })
+    }
+}
@@ -0,0 +1,180 @@
+use mini_core::{
+    prelude::TypeUuidProvider,
+    uuid::{uuid, Uuid},
+};
+use mini_math::prelude::Color;
+use mini_resource::{
+    io::ResourcePath,
+    prelude::{Resource, ResourceData, ResourceManager},
+};
+use wgpu::{
+    BindGroupLayoutEntry, BindingType, BufferBindingType, Face, SamplerBindingType, ShaderStages,
+    TextureSampleType, TextureViewDimension,
+};
+
+use super::Material;
+use crate::{
+    render_resource::{AlphaMode, Shader},
+    texture::prelude::Image,
+};
+
+/// 内置的 [`StandardMaterial`] 着色器的路径
+pub const STANDARD_MATERIAL_SHADER_PATH: &str = "mini_renderer/standard_material.wgsl";
+
+/// 基础颜色乘以基础颜色纹理的材质
+#[derive(Debug, Clone, TypeUuidProvider, ResourceData)]
+#[type_uuid(id = "a24d7682-d434-43ef-84bb-e0dcddb9148d")]
+pub struct StandardMaterial {
+    pub base_color: Color,
+    //没有纹理时按照白色纹理处理
+    pub base_color_texture: Option<Resource<Image>>,
+    pub alpha_mode: AlphaMode,
+    //为 `None` 时双面渲染
+    pub cull_mode: Option<Face>,
+}
+
+impl Default for StandardMaterial {
+    fn default() -> Self {
+        Self {
+            base_color: Color::default(),
+            base_color_texture: None,
+            alpha_mode: AlphaMode::default(),
+            cull_mode: Some(Face::Back),
+        }
+    }
+}
+
+impl StandardMaterial {
+    /// 将着色器注册为内置资源
+    pub fn register_shader(resource_manager: &ResourceManager) -> Resource<Shader> {
+        resource_manager.add_built_in(
+            STANDARD_MATERIAL_SHADER_PATH,
+            Shader {
+                path: STANDARD_MATERIAL_SHADER_PATH.to_string(),
+                source: include_str!("standard_material.wgsl").to_string(),
+            },
+        )
+    }
+
+    /// `@binding(0)` 的 uniform 数据，颜色为线性空间
+    pub fn uniform_data(&self) -> [f32; 4] {
+        self.base_color.to_linear_array()
+    }
+}
+
+impl Material for StandardMaterial {
+    fn fragment_shader() -> ResourcePath<'static> {
+        STANDARD_MATERIAL_SHADER_PATH.into()
+    }
+
+    fn bind_group_layout() -> Vec<BindGroupLayoutEntry> {
+        vec![
+            BindGroupLayoutEntry {
+                binding: 0,
+                visibility: ShaderStages::FRAGMENT,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            },
+            BindGroupLayoutEntry {
+                binding: 1,
+                visibility: ShaderStages::FRAGMENT,
+                ty: BindingType::Texture {
+                    sample_type: TextureSampleType::Float { filterable: true },
+                    view_dimension: TextureViewDimension::D2,
+                    multisampled: false,
+                },
+                count: None,
+            },
+            BindGroupLayoutEntry {
+                binding: 2,
+                visibility: ShaderStages::FRAGMENT,
+                ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                count: None,
+            },
+        ]
+    }
+
+    fn alpha_mode(&self) -> AlphaMode {
+        self.alpha_mode
+    }
+
+    fn cull_mode(&self) -> Option<Face> {
+        self.cull_mode
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use wgpu::{PrimitiveTopology, TextureFormat};
+
+    use super::*;
+    use crate::{
+        mesh::mesh::{Mesh, MeshVertexBufferLayouts},
+        render_resource::{PipelineError, RenderPipelineDescriptor},
+    };
+
+    #[test]
+    fn test_standard_material_pipeline_descriptor() {
+        let shader = Shader {
+            path: STANDARD_MATERIAL_SHADER_PATH.to_string(),
+            source: include_str!("standard_material.wgsl").to_string(),
+        };
+        let mut layouts = MeshVertexBufferLayouts::default();
+
+        let mesh = Mesh::new(PrimitiveTopology::TriangleList)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, vec![[0.0; 3]; 3])
+            .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0.0; 3]; 3])
+            .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0.0; 2]; 3]);
+        let layout = mesh.get_mesh_vertex_buffer_layout(&mut layouts);
+
+        let descriptor =
+            StandardMaterial::pipeline_descriptor(&shader, &layout, TextureFormat::Bgra8UnormSrgb)
+                .unwrap();
+        descriptor.validate().unwrap();
+        let locations = descriptor.vertex_buffers[0]
+            .attributes
+            .iter()
+            .map(|attribute| (attribute.shader_location, attribute.offset))
+            .collect::<Vec<_>>();
+        // 法线不会传给着色器
+        assert_eq!(locations, [(0, 0), (1, 24)]);
+        assert_eq!(
+            descriptor.bind_group_layouts,
+            [StandardMaterial::bind_group_layout()]
+        );
+        assert_eq!(
+            descriptor.depth_stencil.as_ref().unwrap().format,
+            TextureFormat::Depth32Float
+        );
+
+        // 缺少 uv 的网格不能使用这个材质
+        let mesh = Mesh::new(PrimitiveTopology::TriangleList)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, vec![[0.0; 3]; 3]);
+        let layout = mesh.get_mesh_vertex_buffer_layout(&mut layouts);
+        assert!(matches!(
+            StandardMaterial::pipeline_descriptor(&shader, &layout, TextureFormat::Bgra8UnormSrgb),
+            Err(PipelineError::MissingVertexAttribute(_))
+        ));
+    }
+
+    #[test]
+    fn test_material_cull_mode() {
+        let mut default_descriptor = RenderPipelineDescriptor::default();
+        StandardMaterial::default().specialize(&mut default_descriptor);
+        assert_eq!(default_descriptor.primitive.cull_mode, Some(Face::Back));
+
+        let double_sided = StandardMaterial {
+            cull_mode: None,
+            ..Default::default()
+        };
+        let mut double_sided_descriptor = RenderPipelineDescriptor::default();
+        double_sided.specialize(&mut double_sided_descriptor);
+        assert_eq!(double_sided_descriptor.primitive.cull_mode, None);
+
+        assert_ne!(default_descriptor, double_sided_descriptor);
+    }
+}
@@ -0,0 +1,30 @@
+struct StandardMaterial {
+    base_color: vec4<f32>,
+};
+
+@group(0) @binding(0) var<uniform> material: StandardMaterial;
+@group(0) @binding(1) var base_color_texture: texture_2d<f32>;
+@group(0) @binding(2) var base_color_sampler: sampler;
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) uv: vec2<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+@vertex
+fn vs_main(in: VertexInput) -> VertexOutput {
+    var out: VertexOutput;
+    out.clip_position = vec4<f32>(in.position, 1.0);
+    out.uv = in.uv;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    return material.base_color * textureSample(base_color_texture, base_color_sampler, in.uv);
+}
@@ -1,6 +1,14 @@
-use std::sync::Arc;
+use std::{
+    collections::HashSet,
+    hash::{Hash, Hasher},
+    sync::Arc,
+};
+
+use mini_core::thiserror::{self, Error};
+use wgpu::VertexAttribute;
 
 use super::MeshVertexAttributeId;
+use crate::render_resource::VertexBufferLayout;
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
 pub struct MeshVertexBufferLayout {
@@ -71,14 +79,14 @@ impl MeshVertexBufferLayout {
 /// once. Therefore, comparing these for equality requires only a single pointer
 /// comparison, and this type's [`PartialEq`] and [`Hash`] implementations take
 /// advantage of this. To that end, this type doesn't implement
-/// [`bevy_derive::Deref`] or [`bevy_derive::DerefMut`] in order to reduce the
+/// [`Deref`](std::ops::Deref) or [`DerefMut`](std::ops::DerefMut) in order to reduce the
 /// possibility of accidental deep comparisons, which would be needlessly
 /// expensive.
 #[derive(Clone, Debug)]
 pub struct MeshVertexBufferLayoutRef(pub Arc<MeshVertexBufferLayout>);
 
 /// Stores the single copy of each mesh vertex buffer layout.
-#[derive(Clone, Default, Resource)]
+#[derive(Clone, Default)]
 pub struct MeshVertexBufferLayouts(HashSet<Arc<MeshVertexBufferLayout>>);
 
 impl MeshVertexBufferLayouts {
@@ -90,11 +98,12 @@ impl MeshVertexBufferLayouts {
         // compare by pointer are on `MeshVertexBufferLayoutRef`, not on
         // `Arc<MeshVertexBufferLayout>`, this compares the mesh vertex buffer
         // structurally, not by pointer.
-        MeshVertexBufferLayoutRef(
-            self.0
-                .get_or_insert_with(&layout, |layout| Arc::new(layout.clone()))
-                .clone(),
-        )
+        if let Some(layout) = self.0.get(&layout) {
+            return MeshVertexBufferLayoutRef(layout.clone());
+        }
+        let layout = Arc::new(layout);
+        self.0.insert(layout.clone());
+        MeshVertexBufferLayoutRef(layout)
     }
 }
 
@@ -113,3 +122,29 @@ impl Hash for MeshVertexBufferLayoutRef {
         (Arc::as_ptr(&self.0) as usize).hash(state);
     }
 }
+
+/// 着色器中顶点属性的位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VertexAttributeDescriptor {
+    pub shader_location: u32,
+    pub id: MeshVertexAttributeId,
+    pub name: &'static str,
+}
+
+impl VertexAttributeDescriptor {
+    pub const fn new(shader_location: u32, id: MeshVertexAttributeId, name: &'static str) -> Self {
+        VertexAttributeDescriptor {
+            shader_location,
+            id,
+            name,
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+#[error("Mesh is missing requested attribute: {name} ({id:?}, pipeline type: {pipeline_type:?})")]
+pub struct MissingVertexAttributeError {
+    pub pipeline_type: Option<&'static str>,
+    id: MeshVertexAttributeId,
+    name: &'static str,
+}
@@ -1,4 +1,6 @@
 mod layout;
+#[allow(clippy::module_inception)]
 mod mesh;
 
+pub use layout::*;
 pub use mesh::*;
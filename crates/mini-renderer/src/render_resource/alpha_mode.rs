@@ -0,0 +1,73 @@
+use std::hash::{Hash, Hasher};
+
+use wgpu::BlendState;
+
+/// `Mask` 模式下传给着色器的 pipeline-overridable 常量的名称，
+/// 着色器中需要声明 `override alpha_cutoff: f32;`
+pub const ALPHA_CUTOFF_CONSTANT: &str = "alpha_cutoff";
+
+/// 材质的透明方式，决定颜色附件的混合状态
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum AlphaMode {
+    //直接覆盖颜色附件，忽略 alpha
+    #[default]
+    Opaque,
+    /// alpha 小于 cutoff 的片元被丢弃，其余的按不透明处理
+    Mask(f32),
+    //按照 alpha 和颜色附件混合
+    Blend,
+}
+
+impl AlphaMode {
+    pub fn blend_state(&self) -> BlendState {
+        match self {
+            AlphaMode::Opaque | AlphaMode::Mask(_) => BlendState::REPLACE,
+            AlphaMode::Blend => BlendState::ALPHA_BLENDING,
+        }
+    }
+
+    /// 只有 `Mask` 模式有值
+    pub fn alpha_cutoff(&self) -> Option<f32> {
+        match self {
+            AlphaMode::Mask(cutoff) => Some(*cutoff),
+            _ => None,
+        }
+    }
+}
+
+// cutoff 按位比较，这样 AlphaMode 可以作为管线的缓存键
+impl Eq for AlphaMode {}
+
+impl Hash for AlphaMode {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        if let AlphaMode::Mask(cutoff) = self {
+            cutoff.to_bits().hash(state);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use wgpu::{BlendComponent, BlendFactor, BlendOperation};
+
+    use super::*;
+
+    #[test]
+    fn test_alpha_mode_blend_state() {
+        assert_eq!(AlphaMode::Opaque.blend_state(), BlendState::REPLACE);
+        assert_eq!(AlphaMode::Mask(0.5).blend_state(), BlendState::REPLACE);
+        assert_eq!(
+            AlphaMode::Blend.blend_state().color,
+            BlendComponent {
+                src_factor: BlendFactor::SrcAlpha,
+                dst_factor: BlendFactor::OneMinusSrcAlpha,
+                operation: BlendOperation::Add,
+            }
+        );
+
+        assert_eq!(AlphaMode::Opaque.alpha_cutoff(), None);
+        assert_eq!(AlphaMode::Mask(0.5).alpha_cutoff(), Some(0.5));
+        assert_eq!(AlphaMode::Blend.alpha_cutoff(), None);
+    }
+}
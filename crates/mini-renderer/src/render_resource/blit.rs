@@ -0,0 +1,166 @@
+use std::{borrow::Cow, collections::HashMap, sync::Arc};
+
+use mini_core::parking_lot::Mutex;
+use wgpu::{
+    BindGroupLayout, CommandEncoderDescriptor, PipelineLayout, RenderPipeline, Sampler,
+    ShaderModule, TextureFormat, TextureView,
+};
+
+use super::RenderPassBuilder;
+use crate::renderer::{RenderDevice, RenderQueue};
+
+const BLIT_SHADER: &str = include_str!("blit.wgsl");
+
+/// 把纹理原样绘制到颜色附件，管线按照颜色附件的格式缓存。
+///
+/// 创建时会编译着色器，通常保存在 [`Lazy`](mini_core::utils::Lazy) 中，第一次使用时才创建。
+pub struct BlitPipeline {
+    shader: ShaderModule,
+    bind_group_layout: BindGroupLayout,
+    pipeline_layout: PipelineLayout,
+    sampler: Sampler,
+    pipelines: Mutex<HashMap<TextureFormat, Arc<RenderPipeline>>>,
+}
+
+impl BlitPipeline {
+    pub fn new(device: &RenderDevice) -> Self {
+        let device = device.wgpu_device();
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("blit_shader"),
+            source: wgpu::ShaderSource::Wgsl(Cow::Borrowed(BLIT_SHADER)),
+        });
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("blit_bind_group_layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("blit_pipeline_layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("blit_sampler"),
+            ..Default::default()
+        });
+
+        Self {
+            shader,
+            bind_group_layout,
+            pipeline_layout,
+            sampler,
+            pipelines: Default::default(),
+        }
+    }
+
+    /// 渲染到 `format` 格式的颜色附件的管线
+    pub fn pipeline(&self, device: &RenderDevice, format: TextureFormat) -> Arc<RenderPipeline> {
+        self.pipelines
+            .lock()
+            .entry(format)
+            .or_insert_with(|| {
+                let pipeline =
+                    device
+                        .wgpu_device()
+                        .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                            label: Some("blit_pipeline"),
+                            layout: Some(&self.pipeline_layout),
+                            vertex: wgpu::VertexState {
+                                module: &self.shader,
+                                entry_point: "vs_main",
+                                compilation_options: Default::default(),
+                                buffers: &[],
+                            },
+                            fragment: Some(wgpu::FragmentState {
+                                module: &self.shader,
+                                entry_point: "fs_main",
+                                compilation_options: Default::default(),
+                                targets: &[Some(format.into())],
+                            }),
+                            primitive: Default::default(),
+                            depth_stencil: None,
+                            multisample: Default::default(),
+                            multiview: None,
+                            cache: None,
+                        });
+                Arc::new(pipeline)
+            })
+            .clone()
+    }
+
+    /// 把 `source` 绘制到 `target`，`format` 为 `target` 的格式
+    pub fn blit(
+        &self,
+        device: &RenderDevice,
+        queue: &RenderQueue,
+        source: &TextureView,
+        target: &TextureView,
+        format: TextureFormat,
+    ) {
+        let pipeline = self.pipeline(device, format);
+        let device = device.wgpu_device();
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("blit_bind_group"),
+            layout: &self.bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(source),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&self.sampler),
+                },
+            ],
+        });
+
+        let mut encoder = device.create_command_encoder(&CommandEncoderDescriptor {
+            label: Some("blit_encoder"),
+        });
+        {
+            let mut pass = RenderPassBuilder::new("blit_pass")
+                .with_color_attachment(target, None)
+                .begin(&mut encoder);
+            pass.set_pipeline(&pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.draw(0..3, 0..1);
+        }
+        queue.submit([encoder.finish()]);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_utils::headless_renderer;
+
+    #[test]
+    fn test_blit_pipeline_per_format() {
+        let Some(renderer) = headless_renderer() else {
+            return;
+        };
+        let blit_pipeline = BlitPipeline::new(&renderer.device);
+        let pipeline = blit_pipeline.pipeline(&renderer.device, TextureFormat::Rgba8Unorm);
+        let cached = blit_pipeline.pipeline(&renderer.device, TextureFormat::Rgba8Unorm);
+        assert!(Arc::ptr_eq(&pipeline, &cached));
+
+        blit_pipeline.pipeline(&renderer.device, TextureFormat::Bgra8UnormSrgb);
+        assert_eq!(blit_pipeline.pipelines.lock().len(), 2);
+    }
+}
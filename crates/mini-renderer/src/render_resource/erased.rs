@@ -0,0 +1,22 @@
+use crate::wrapper::render_resource_wrapper;
+
+render_resource_wrapper!(
+    /// 引用计数的 [`wgpu::ShaderModule`]
+    ErasedShaderModule,
+    wgpu::ShaderModule
+);
+render_resource_wrapper!(
+    /// 引用计数的 [`wgpu::Buffer`]
+    ErasedBuffer,
+    wgpu::Buffer
+);
+render_resource_wrapper!(
+    /// 引用计数的 [`wgpu::BindGroup`]
+    ErasedBindGroup,
+    wgpu::BindGroup
+);
+render_resource_wrapper!(
+    /// 引用计数的 [`wgpu::RenderPipeline`]，保存在 [`PipelineCache`](super::PipelineCache) 中
+    ErasedRenderPipeline,
+    wgpu::RenderPipeline
+);
@@ -1,3 +1,19 @@
+mod alpha_mode;
+mod blit;
+mod erased;
 mod pipeline;
+mod pipeline_cache;
+mod render_pass;
+mod sampler_cache;
+mod shader;
+mod vertex_format;
 
+pub use alpha_mode::*;
+pub use blit::*;
+pub use erased::*;
 pub use pipeline::*;
+pub use pipeline_cache::*;
+pub use render_pass::*;
+pub use sampler_cache::*;
+pub use shader::*;
+pub use vertex_format::*;
@@ -1,3 +1,21 @@
+use std::{borrow::Cow, collections::HashMap};
+
+use mini_core::{
+    thiserror::{self, Error},
+    tracing::warn,
+};
+use mini_window::window::WindowId;
+use wgpu::{
+    BindGroupLayoutEntry, BufferAddress, ColorTargetState, CompareFunction, DepthStencilState,
+    Features, PolygonMode, PrimitiveState, RenderPipeline, VertexAttribute, VertexFormat,
+    VertexStepMode,
+};
+
+use super::{vertex_format_size, AlphaMode, ALPHA_CUTOFF_CONSTANT};
+use crate::{
+    mesh::mesh::MissingVertexAttributeError, renderer::RenderDevice, texture::prelude::DEPTH_FORMAT,
+};
+
 /// Describes how the vertex buffer is interpreted.
 #[derive(Default, Clone, Debug, Hash, Eq, PartialEq)]
 pub struct VertexBufferLayout {
@@ -8,3 +26,386 @@ pub struct VertexBufferLayout {
     /// The list of attributes which comprise a single vertex.
     pub attributes: Vec<VertexAttribute>,
 }
+
+impl VertexBufferLayout {
+    /// 根据顶点格式依次计算偏移，`shader_location` 从 0 开始递增。
+    pub fn from_vertex_formats<T: IntoIterator<Item = VertexFormat>>(
+        step_mode: VertexStepMode,
+        vertex_formats: T,
+    ) -> Self {
+        let mut offset = 0;
+        let mut attributes = Vec::new();
+        for (shader_location, format) in vertex_formats.into_iter().enumerate() {
+            attributes.push(VertexAttribute {
+                format,
+                offset,
+                shader_location: shader_location as u32,
+            });
+            offset += vertex_format_size(format);
+        }
+
+        VertexBufferLayout {
+            array_stride: offset,
+            step_mode,
+            attributes,
+        }
+    }
+}
+
+impl VertexBufferLayout {
+    pub fn as_wgpu(&self) -> wgpu::VertexBufferLayout<'_> {
+        wgpu::VertexBufferLayout {
+            array_stride: self.array_stride,
+            step_mode: self.step_mode,
+            attributes: &self.attributes,
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum PipelineError {
+    #[error("failed to parse the shader: {0}")]
+    ShaderParse(String),
+    #[error("the shader has no fragment entry point named {0}")]
+    MissingFragmentEntryPoint(String),
+    #[error("the fragment shader writes {outputs} color outputs, but the pipeline has {targets} color targets")]
+    TargetCountMismatch { outputs: usize, targets: usize },
+    #[error(
+        "the alpha mode is Mask, but the shader does not declare `override alpha_cutoff: f32`"
+    )]
+    MissingAlphaCutoff,
+    #[error(transparent)]
+    MissingVertexAttribute(#[from] MissingVertexAttributeError),
+    #[error("window {0:?} has no initialized surface")]
+    MissingSurface(WindowId),
+}
+
+/// 渲染管线的描述，`targets` 依次对应片元着色器的 `@location(n)` 输出，
+/// 每个颜色附件可以有自己的格式和混合方式。
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RenderPipelineDescriptor {
+    pub label: Option<String>,
+    //wgsl 源码
+    pub shader: String,
+    pub vertex_entry_point: String,
+    pub fragment_entry_point: String,
+    pub vertex_buffers: Vec<VertexBufferLayout>,
+    pub targets: Vec<Option<ColorTargetState>>,
+    //`polygon_mode` 为 `Line` 时以线框模式渲染
+    pub primitive: PrimitiveState,
+    //通过 `set_alpha_mode` 修改，同时会修改颜色附件的混合状态
+    pub alpha_mode: AlphaMode,
+    //依次对应 `@group(n)` 的绑定，为空时由 wgpu 根据着色器推导
+    pub bind_group_layouts: Vec<Vec<BindGroupLayoutEntry>>,
+    //在带有深度附件的渲染通道中使用时必须设置，格式和深度纹理一致
+    pub depth_stencil: Option<DepthStencilState>,
+    //需要和颜色附件的采样数一致
+    pub sample_count: u32,
+}
+
+impl Default for RenderPipelineDescriptor {
+    fn default() -> Self {
+        Self {
+            label: None,
+            shader: String::new(),
+            vertex_entry_point: "vs_main".to_string(),
+            fragment_entry_point: "fs_main".to_string(),
+            vertex_buffers: vec![],
+            targets: vec![],
+            primitive: PrimitiveState::default(),
+            alpha_mode: AlphaMode::default(),
+            bind_group_layouts: vec![],
+            depth_stencil: None,
+            sample_count: 1,
+        }
+    }
+}
+
+impl RenderPipelineDescriptor {
+    /// 近处的片元覆盖远处的片元，写入深度
+    pub fn default_depth_stencil() -> DepthStencilState {
+        DepthStencilState {
+            format: DEPTH_FORMAT,
+            depth_write_enabled: true,
+            depth_compare: CompareFunction::Less,
+            stencil: Default::default(),
+            bias: Default::default(),
+        }
+    }
+
+    fn parse_shader(&self) -> Result<naga::Module, PipelineError> {
+        naga::front::wgsl::parse_str(&self.shader)
+            .map_err(|e| PipelineError::ShaderParse(e.emit_to_string(&self.shader)))
+    }
+
+    /// 片元着色器入口的颜色输出数量
+    pub fn fragment_output_count(&self) -> Result<usize, PipelineError> {
+        let module = self.parse_shader()?;
+
+        let entry_point = module
+            .entry_points
+            .iter()
+            .find(|entry_point| {
+                entry_point.stage == naga::ShaderStage::Fragment
+                    && entry_point.name == self.fragment_entry_point
+            })
+            .ok_or_else(|| {
+                PipelineError::MissingFragmentEntryPoint(self.fragment_entry_point.clone())
+            })?;
+
+        let is_location = |binding: &Option<naga::Binding>| {
+            matches!(binding, Some(naga::Binding::Location { .. }))
+        };
+
+        let count = match &entry_point.function.result {
+            None => 0,
+            Some(result) if result.binding.is_some() => is_location(&result.binding) as usize,
+            //输出为结构体时统计带有 location 的成员
+            Some(result) => match &module.types[result.ty].inner {
+                naga::TypeInner::Struct { members, .. } => members
+                    .iter()
+                    .filter(|member| is_location(&member.binding))
+                    .count(),
+                _ => 0,
+            },
+        };
+        Ok(count)
+    }
+
+    /// 设置透明方式，所有颜色附件的混合状态都会随之修改
+    pub fn set_alpha_mode(&mut self, alpha_mode: AlphaMode) {
+        self.alpha_mode = alpha_mode;
+        for target in self.targets.iter_mut().flatten() {
+            target.blend = Some(alpha_mode.blend_state());
+        }
+    }
+
+    /// 传给片元着色器的 pipeline-overridable 常量
+    pub fn fragment_constants(&self) -> HashMap<String, f64> {
+        let mut constants = HashMap::new();
+        if let Some(cutoff) = self.alpha_mode.alpha_cutoff() {
+            constants.insert(ALPHA_CUTOFF_CONSTANT.to_string(), cutoff as f64);
+        }
+        constants
+    }
+
+    /// 设备不支持 `primitive.polygon_mode` 需要的 feature 时回退到 `Fill`
+    pub fn supported_primitive(&self, features: Features) -> PrimitiveState {
+        let required = match self.primitive.polygon_mode {
+            PolygonMode::Fill => Features::empty(),
+            PolygonMode::Line => Features::POLYGON_MODE_LINE,
+            PolygonMode::Point => Features::POLYGON_MODE_POINT,
+        };
+
+        let mut primitive = self.primitive;
+        if !features.contains(required) {
+            warn!(
+                "{:?} polygon mode requires {:?}, falling back to Fill.",
+                primitive.polygon_mode, required
+            );
+            primitive.polygon_mode = PolygonMode::Fill;
+        }
+        primitive
+    }
+
+    /// 检查颜色附件的数量是否和片元着色器的输出一致，`Mask` 模式下着色器需要声明 alpha cutoff 常量
+    pub fn validate(&self) -> Result<(), PipelineError> {
+        let outputs = self.fragment_output_count()?;
+        if outputs != self.targets.len() {
+            return Err(PipelineError::TargetCountMismatch {
+                outputs,
+                targets: self.targets.len(),
+            });
+        }
+
+        if self.alpha_mode.alpha_cutoff().is_some() {
+            let module = self.parse_shader()?;
+            let declared = module
+                .overrides
+                .iter()
+                .any(|(_, constant)| constant.name.as_deref() == Some(ALPHA_CUTOFF_CONSTANT));
+            if !declared {
+                return Err(PipelineError::MissingAlphaCutoff);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn create_render_pipeline(
+        &self,
+        device: &RenderDevice,
+    ) -> Result<RenderPipeline, PipelineError> {
+        self.validate()?;
+
+        let device = device.wgpu_device();
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: self.label.as_deref(),
+            source: wgpu::ShaderSource::Wgsl(Cow::Borrowed(&self.shader)),
+        });
+        let vertex_buffers = self
+            .vertex_buffers
+            .iter()
+            .map(VertexBufferLayout::as_wgpu)
+            .collect::<Vec<_>>();
+        let fragment_constants = self.fragment_constants();
+        let bind_group_layouts = self
+            .bind_group_layouts
+            .iter()
+            .map(|entries| {
+                device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                    label: self.label.as_deref(),
+                    entries,
+                })
+            })
+            .collect::<Vec<_>>();
+        let layout = (!bind_group_layouts.is_empty()).then(|| {
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: self.label.as_deref(),
+                bind_group_layouts: &bind_group_layouts.iter().collect::<Vec<_>>(),
+                push_constant_ranges: &[],
+            })
+        });
+
+        Ok(
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: self.label.as_deref(),
+                layout: layout.as_ref(),
+                vertex: wgpu::VertexState {
+                    module: &module,
+                    entry_point: &self.vertex_entry_point,
+                    compilation_options: Default::default(),
+                    buffers: &vertex_buffers,
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &module,
+                    entry_point: &self.fragment_entry_point,
+                    compilation_options: wgpu::PipelineCompilationOptions {
+                        constants: &fragment_constants,
+                        ..Default::default()
+                    },
+                    targets: &self.targets,
+                }),
+                primitive: self.supported_primitive(device.features()),
+                depth_stencil: self.depth_stencil.clone(),
+                multisample: wgpu::MultisampleState {
+                    count: self.sample_count,
+                    ..Default::default()
+                },
+                multiview: None,
+                cache: None,
+            }),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use wgpu::{BlendState, ColorWrites, TextureFormat};
+
+    use super::*;
+
+    const MRT_SHADER: &str = r#"
+struct FragmentOutput {
+    @location(0) albedo: vec4<f32>,
+    @location(1) normal: vec4<f32>,
+};
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+    let x = f32(i32(index) - 1);
+    let y = f32(i32(index & 1u) * 2 - 1);
+    return vec4<f32>(x, y, 0.0, 1.0);
+}
+
+@fragment
+fn fs_main() -> FragmentOutput {
+    var out: FragmentOutput;
+    out.albedo = vec4<f32>(1.0, 0.0, 0.0, 1.0);
+    out.normal = vec4<f32>(0.0, 1.0, 0.0, 1.0);
+    return out;
+}
+"#;
+
+    #[test]
+    fn test_wireframe_polygon_mode() {
+        let descriptor = RenderPipelineDescriptor {
+            primitive: PrimitiveState {
+                polygon_mode: PolygonMode::Line,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let primitive = descriptor.supported_primitive(Features::POLYGON_MODE_LINE);
+        assert_eq!(primitive.polygon_mode, PolygonMode::Line);
+        let primitive = descriptor.supported_primitive(Features::empty());
+        assert_eq!(primitive.polygon_mode, PolygonMode::Fill);
+    }
+
+    #[test]
+    fn test_alpha_mode_pipeline_key() {
+        let mut descriptor = RenderPipelineDescriptor {
+            shader: MRT_SHADER.to_string(),
+            targets: vec![
+                Some(TextureFormat::Rgba8Unorm.into()),
+                Some(TextureFormat::Rgba16Float.into()),
+            ],
+            ..Default::default()
+        };
+        let opaque = descriptor.clone();
+
+        descriptor.set_alpha_mode(AlphaMode::Blend);
+        assert_ne!(descriptor, opaque);
+        assert!(descriptor
+            .targets
+            .iter()
+            .all(|target| target.as_ref().unwrap().blend == Some(BlendState::ALPHA_BLENDING)));
+        assert!(descriptor.fragment_constants().is_empty());
+
+        descriptor.set_alpha_mode(AlphaMode::Mask(0.5));
+        assert_eq!(descriptor.fragment_constants()[ALPHA_CUTOFF_CONSTANT], 0.5);
+        assert!(matches!(
+            descriptor.validate(),
+            Err(PipelineError::MissingAlphaCutoff)
+        ));
+
+        descriptor.shader = format!("override alpha_cutoff: f32 = 0.5;\n{MRT_SHADER}");
+        assert!(descriptor.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_fragment_targets() {
+        let target = |format, blend| {
+            Some(ColorTargetState {
+                format,
+                blend,
+                write_mask: ColorWrites::ALL,
+            })
+        };
+        let mut descriptor = RenderPipelineDescriptor {
+            shader: MRT_SHADER.to_string(),
+            targets: vec![target(TextureFormat::Rgba8Unorm, None)],
+            ..Default::default()
+        };
+        assert_eq!(descriptor.fragment_output_count().unwrap(), 2);
+        assert!(matches!(
+            descriptor.validate(),
+            Err(PipelineError::TargetCountMismatch {
+                outputs: 2,
+                targets: 1
+            })
+        ));
+
+        descriptor.targets.push(target(
+            TextureFormat::Rgba16Float,
+            Some(BlendState::ALPHA_BLENDING),
+        ));
+        assert!(descriptor.validate().is_ok());
+
+        descriptor.fragment_entry_point = "missing".to_string();
+        assert!(matches!(
+            descriptor.validate(),
+            Err(PipelineError::MissingFragmentEntryPoint(_))
+        ));
+    }
+}
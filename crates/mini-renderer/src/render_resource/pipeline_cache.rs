@@ -0,0 +1,258 @@
+use std::collections::{HashMap, HashSet};
+
+use wgpu::{RenderPipeline, TextureFormat};
+
+use super::{ErasedRenderPipeline, PipelineError, RenderPipelineDescriptor, Shader};
+use crate::{material::Material, mesh::mesh::MeshVertexBufferLayoutRef, renderer::RenderDevice};
+
+/// [`PipelineCache`] 中管线的编号，管线被移除后编号不会被复用
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CachedPipelineId(usize);
+
+/// 相同描述的渲染管线只创建一次
+///
+/// 描述中包含着色器源码、顶点布局和颜色附件的格式，所以它们中任意一个变化都会创建新的管线。
+#[derive(Default)]
+pub struct PipelineCache {
+    slots: PipelineSlots<ErasedRenderPipeline>,
+}
+
+impl PipelineCache {
+    /// 返回描述对应的管线编号，没有时创建管线
+    pub fn specialize(
+        &mut self,
+        device: &RenderDevice,
+        descriptor: &RenderPipelineDescriptor,
+    ) -> Result<CachedPipelineId, PipelineError> {
+        self.slots.get_or_insert_with(descriptor, || {
+            descriptor
+                .create_render_pipeline(device)
+                .map(ErasedRenderPipeline::new)
+        })
+    }
+
+    /// 返回材质在网格上渲染时使用的管线编号，管线会和 `shader` 的路径关联，
+    /// 之后可以通过 [`invalidate_shader`](Self::invalidate_shader) 移除
+    pub fn specialize_material<M: Material>(
+        &mut self,
+        device: &RenderDevice,
+        material: &M,
+        shader: &Shader,
+        layout: &MeshVertexBufferLayoutRef,
+        format: TextureFormat,
+        sample_count: u32,
+    ) -> Result<CachedPipelineId, PipelineError> {
+        let mut descriptor = M::pipeline_descriptor(shader, layout, format)?;
+        material.specialize(&mut descriptor);
+        descriptor.sample_count = sample_count;
+        let id = self.specialize(device, &descriptor)?;
+        self.slots.associate_shader(&shader.path, id);
+        Ok(id)
+    }
+
+    pub fn get(&self, id: CachedPipelineId) -> Option<&RenderPipeline> {
+        self.slots.get(id).map(|pipeline| &**pipeline)
+    }
+
+    /// 和 [`get`](Self::get) 相同，但是返回共享的管线，管线从缓存中移除后仍然可以使用
+    pub fn get_erased(&self, id: CachedPipelineId) -> Option<ErasedRenderPipeline> {
+        self.slots.get(id).cloned()
+    }
+
+    /// 移除使用路径为 `path` 的着色器创建的管线，返回被移除的管线编号
+    pub fn invalidate_shader(&mut self, path: &str) -> Vec<CachedPipelineId> {
+        self.slots.invalidate_shader(path)
+    }
+
+    /// 缓存中管线的数量
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slots.len() == 0
+    }
+
+    /// 移除所有管线，之前的编号不会被之后创建的管线复用
+    pub fn clear(&mut self) {
+        self.slots.clear();
+    }
+}
+
+//管线编号的分配和着色器的关联，和创建管线的设备无关
+struct PipelineSlots<P> {
+    ids: HashMap<RenderPipelineDescriptor, CachedPipelineId>,
+    pipelines: HashMap<CachedPipelineId, P>,
+    //着色器路径对应的管线，着色器重新加载时移除
+    shader_pipelines: HashMap<String, HashSet<CachedPipelineId>>,
+    //下一个管线的编号，清空后也不会重置
+    next_id: usize,
+}
+
+impl<P> Default for PipelineSlots<P> {
+    fn default() -> Self {
+        Self {
+            ids: HashMap::new(),
+            pipelines: HashMap::new(),
+            shader_pipelines: HashMap::new(),
+            next_id: 0,
+        }
+    }
+}
+
+impl<P> PipelineSlots<P> {
+    fn get_or_insert_with<E>(
+        &mut self,
+        descriptor: &RenderPipelineDescriptor,
+        create: impl FnOnce() -> Result<P, E>,
+    ) -> Result<CachedPipelineId, E> {
+        if let Some(id) = self.ids.get(descriptor) {
+            return Ok(*id);
+        }
+
+        let pipeline = create()?;
+        let id = CachedPipelineId(self.next_id);
+        self.next_id += 1;
+        self.pipelines.insert(id, pipeline);
+        self.ids.insert(descriptor.clone(), id);
+        Ok(id)
+    }
+
+    fn associate_shader(&mut self, path: &str, id: CachedPipelineId) {
+        self.shader_pipelines
+            .entry(path.to_string())
+            .or_default()
+            .insert(id);
+    }
+
+    fn get(&self, id: CachedPipelineId) -> Option<&P> {
+        self.pipelines.get(&id)
+    }
+
+    fn invalidate_shader(&mut self, path: &str) -> Vec<CachedPipelineId> {
+        let Some(ids) = self.shader_pipelines.remove(path) else {
+            return vec![];
+        };
+
+        self.ids.retain(|_, id| !ids.contains(id));
+        for id in ids.iter() {
+            self.pipelines.remove(id);
+        }
+
+        let mut ids = ids.into_iter().collect::<Vec<_>>();
+        ids.sort();
+        ids
+    }
+
+    fn len(&self) -> usize {
+        self.pipelines.len()
+    }
+
+    fn clear(&mut self) {
+        self.ids.clear();
+        self.pipelines.clear();
+        self.shader_pipelines.clear();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use wgpu::PrimitiveTopology;
+
+    use super::*;
+    use crate::{
+        material::{StandardMaterial, STANDARD_MATERIAL_SHADER_PATH},
+        mesh::mesh::{Mesh, MeshVertexBufferLayouts},
+        test_utils::headless_renderer,
+    };
+
+    fn descriptor(label: &str) -> RenderPipelineDescriptor {
+        RenderPipelineDescriptor {
+            label: Some(label.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_pipeline_slots() {
+        let mut slots = PipelineSlots::<&str>::default();
+        let insert = |slots: &mut PipelineSlots<&'static str>, label: &'static str| {
+            slots
+                .get_or_insert_with(&descriptor(label), || Ok::<_, ()>(label))
+                .unwrap()
+        };
+
+        let a = insert(&mut slots, "a");
+        assert_eq!(insert(&mut slots, "a"), a);
+        let b = insert(&mut slots, "b");
+        let c = insert(&mut slots, "c");
+        slots.associate_shader("shader.wgsl", a);
+        slots.associate_shader("shader.wgsl", c);
+        assert_eq!(slots.len(), 3);
+
+        // 只移除和着色器关联的管线
+        assert_eq!(slots.invalidate_shader("shader.wgsl"), [a, c]);
+        assert!(slots.invalidate_shader("shader.wgsl").is_empty());
+        assert_eq!(slots.get(a), None);
+        assert_eq!(slots.get(b), Some(&"b"));
+        assert_ne!(insert(&mut slots, "a"), a);
+
+        // 清空后编号不会被复用
+        slots.clear();
+        assert_eq!(slots.len(), 0);
+        let d = insert(&mut slots, "b");
+        assert!(![a, b, c].contains(&d));
+        assert_eq!(slots.get(b), None);
+        assert_eq!(slots.get(d), Some(&"b"));
+    }
+
+    #[test]
+    fn test_pipeline_cache() {
+        let Some(renderer) = headless_renderer() else {
+            return;
+        };
+        let device = renderer.device;
+        let shader = Shader {
+            path: STANDARD_MATERIAL_SHADER_PATH.to_string(),
+            source: include_str!("../material/standard_material.wgsl").to_string(),
+        };
+        let mut layouts = MeshVertexBufferLayouts::default();
+        let mesh = Mesh::new(PrimitiveTopology::TriangleList)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, vec![[0.0; 3]; 3])
+            .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0.0; 2]; 3]);
+        let layout = mesh.get_mesh_vertex_buffer_layout(&mut layouts);
+
+        let material = StandardMaterial::default();
+        let mut cache = PipelineCache::default();
+        let specialize = |cache: &mut PipelineCache, format: TextureFormat| {
+            cache
+                .specialize_material(&device, &material, &shader, &layout, format, 1)
+                .unwrap()
+        };
+
+        let a = specialize(&mut cache, TextureFormat::Bgra8UnormSrgb);
+        let b = specialize(&mut cache, TextureFormat::Bgra8UnormSrgb);
+        assert_eq!(a, b);
+        assert_eq!(cache.len(), 1);
+
+        let c = specialize(&mut cache, TextureFormat::Rgba8UnormSrgb);
+        assert_ne!(a, c);
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(a).is_some());
+        let erased = cache.get_erased(a).unwrap();
+
+        // 着色器重新加载后依赖它的管线全部失效
+        assert_eq!(
+            cache.invalidate_shader(STANDARD_MATERIAL_SHADER_PATH),
+            [a, c]
+        );
+        assert!(cache.get(a).is_none());
+        assert!(cache.is_empty());
+        // 共享的管线不会随缓存一起释放
+        assert!(erased.try_unwrap().is_some());
+
+        let d = specialize(&mut cache, TextureFormat::Bgra8UnormSrgb);
+        assert_ne!(a, d);
+        assert!(cache.get(d).is_some());
+    }
+}
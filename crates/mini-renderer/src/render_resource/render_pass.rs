@@ -0,0 +1,173 @@
+use mini_math::prelude::Color;
+use wgpu::{
+    CommandEncoder, LoadOp, Operations, RenderPass, RenderPassColorAttachment,
+    RenderPassDepthStencilAttachment, RenderPassDescriptor, StoreOp, TextureView,
+};
+
+/// 构建 [`RenderPass`]，颜色附件按照添加的顺序对应片元着色器的 `@location(n)` 输出
+#[derive(Default)]
+pub struct RenderPassBuilder<'a> {
+    label: Option<&'a str>,
+    color_attachments: Vec<Option<RenderPassColorAttachment<'a>>>,
+    depth_attachment: Option<RenderPassDepthStencilAttachment<'a>>,
+}
+
+impl<'a> RenderPassBuilder<'a> {
+    pub fn new(label: &'a str) -> Self {
+        Self {
+            label: Some(label),
+            color_attachments: vec![],
+            depth_attachment: None,
+        }
+    }
+
+    /// `clear_color` 为 `None` 时保留附件原来的内容
+    pub fn with_color_attachment(self, view: &'a TextureView, clear_color: Option<Color>) -> Self {
+        self.with_resolved_color_attachment(view, None, clear_color)
+    }
+
+    /// `view` 为多重采样纹理时，渲染结果会解析到 `resolve_target`
+    pub fn with_resolved_color_attachment(
+        mut self,
+        view: &'a TextureView,
+        resolve_target: Option<&'a TextureView>,
+        clear_color: Option<Color>,
+    ) -> Self {
+        let load = match clear_color {
+            Some(color) => LoadOp::Clear(color.as_wgpu_color()),
+            None => LoadOp::Load,
+        };
+        self.color_attachments.push(Some(RenderPassColorAttachment {
+            view,
+            resolve_target,
+            ops: Operations {
+                load,
+                store: StoreOp::Store,
+            },
+        }));
+        self
+    }
+
+    /// `clear_depth` 为 `None` 时保留原来的深度，通常每帧清除为 1.0
+    pub fn with_depth_attachment(
+        mut self,
+        view: &'a TextureView,
+        clear_depth: Option<f32>,
+    ) -> Self {
+        let load = match clear_depth {
+            Some(depth) => LoadOp::Clear(depth),
+            None => LoadOp::Load,
+        };
+        self.depth_attachment = Some(RenderPassDepthStencilAttachment {
+            view,
+            depth_ops: Some(Operations {
+                load,
+                store: StoreOp::Store,
+            }),
+            stencil_ops: None,
+        });
+        self
+    }
+
+    pub fn color_attachment_count(&self) -> usize {
+        self.color_attachments.len()
+    }
+
+    pub fn begin<'e>(&self, encoder: &'e mut CommandEncoder) -> RenderPass<'e> {
+        encoder.begin_render_pass(&RenderPassDescriptor {
+            label: self.label,
+            color_attachments: &self.color_attachments,
+            depth_stencil_attachment: self.depth_attachment.clone(),
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use wgpu::{
+        Device, Extent3d, ImageCopyBuffer, ImageDataLayout, Queue, TextureDescriptor,
+        TextureDimension, TextureFormat, TextureUsages,
+    };
+
+    use super::*;
+    use crate::test_utils::headless_renderer;
+
+    const SIZE: u32 = 4;
+
+    fn create_target(device: &Device) -> wgpu::Texture {
+        device.create_texture(&TextureDescriptor {
+            label: None,
+            size: Extent3d {
+                width: SIZE,
+                height: SIZE,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::Rgba8Unorm,
+            usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::COPY_SRC,
+            view_formats: &[],
+        })
+    }
+
+    // 读取纹理左上角的像素
+    fn read_first_pixel(device: &Device, queue: &Queue, texture: &wgpu::Texture) -> [u8; 4] {
+        let bytes_per_row = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: (bytes_per_row * SIZE) as u64,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = device.create_command_encoder(&Default::default());
+        encoder.copy_texture_to_buffer(
+            texture.as_image_copy(),
+            ImageCopyBuffer {
+                buffer: &buffer,
+                layout: ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(bytes_per_row),
+                    rows_per_image: Some(SIZE),
+                },
+            },
+            texture.size(),
+        );
+        queue.submit([encoder.finish()]);
+
+        let slice = buffer.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |result| result.unwrap());
+        device.poll(wgpu::Maintain::Wait);
+        let data = slice.get_mapped_range();
+        [data[0], data[1], data[2], data[3]]
+    }
+
+    #[test]
+    fn test_clear_multiple_targets() {
+        let Some(renderer) = headless_renderer() else {
+            return;
+        };
+        let device = renderer.device.wgpu_device();
+        let queue = &renderer.queue;
+
+        let albedo = create_target(device);
+        let normal = create_target(device);
+        let albedo_view = albedo.create_view(&Default::default());
+        let normal_view = normal.create_view(&Default::default());
+
+        let builder = RenderPassBuilder::new("mrt_pass")
+            .with_color_attachment(&albedo_view, Some(Color::RED))
+            .with_color_attachment(&normal_view, Some(Color::linear_rgb(0.0, 1.0, 0.0)));
+        assert_eq!(builder.color_attachment_count(), 2);
+
+        let mut encoder = device.create_command_encoder(&Default::default());
+        builder.begin(&mut encoder);
+        queue.submit([encoder.finish()]);
+
+        assert_eq!(read_first_pixel(device, queue, &albedo), [255, 0, 0, 255]);
+        assert_eq!(read_first_pixel(device, queue, &normal), [0, 255, 0, 255]);
+    }
+}
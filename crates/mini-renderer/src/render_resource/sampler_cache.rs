@@ -0,0 +1,88 @@
+use std::{collections::HashMap, sync::Arc};
+
+use wgpu::Sampler;
+
+use crate::{
+    renderer::RenderDevice,
+    texture::prelude::{ImageSampler, ImageSamplerDescriptor},
+};
+
+/// 相同描述符的采样器只创建一次
+#[derive(Default)]
+pub struct SamplerCache {
+    samplers: HashMap<ImageSamplerDescriptor, Arc<Sampler>>,
+}
+
+impl SamplerCache {
+    pub fn get_or_create(
+        &mut self,
+        device: &RenderDevice,
+        descriptor: &ImageSamplerDescriptor,
+    ) -> Arc<Sampler> {
+        if let Some(sampler) = self.samplers.get(descriptor) {
+            return sampler.clone();
+        }
+
+        let sampler = Arc::new(device.wgpu_device().create_sampler(&descriptor.as_wgpu()));
+        self.samplers.insert(descriptor.clone(), sampler.clone());
+        sampler
+    }
+
+    /// [`ImageSampler::Default`] 使用 [`ImageSamplerDescriptor::default`]
+    pub fn get_or_create_image_sampler(
+        &mut self,
+        device: &RenderDevice,
+        sampler: &ImageSampler,
+    ) -> Arc<Sampler> {
+        match sampler {
+            ImageSampler::Default => self.get_or_create(device, &ImageSamplerDescriptor::default()),
+            ImageSampler::Descriptor(descriptor) => self.get_or_create(device, descriptor),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.samplers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samplers.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.samplers.clear();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_utils::headless_renderer;
+
+    #[test]
+    fn test_sampler_cache() {
+        let descriptor = ImageSamplerDescriptor {
+            lod_max_clamp: 4.0,
+            ..Default::default()
+        };
+        assert_eq!(
+            ImageSamplerDescriptor::default(),
+            ImageSamplerDescriptor::default()
+        );
+        assert_ne!(descriptor, ImageSamplerDescriptor::default());
+
+        let Some(renderer) = headless_renderer() else {
+            return;
+        };
+        let device = renderer.device;
+        let mut cache = SamplerCache::default();
+
+        let a = cache.get_or_create_image_sampler(&device, &ImageSampler::Default);
+        let b = cache.get_or_create(&device, &ImageSamplerDescriptor::default());
+        assert!(Arc::ptr_eq(&a, &b));
+        assert_eq!(cache.len(), 1);
+
+        let c = cache.get_or_create(&device, &descriptor);
+        assert!(!Arc::ptr_eq(&a, &c));
+        assert_eq!(cache.len(), 2);
+    }
+}
@@ -0,0 +1,79 @@
+use std::string::FromUtf8Error;
+
+use mini_core::{
+    prelude::TypeUuidProvider,
+    thiserror::{self, Error},
+    uuid::{uuid, Uuid},
+};
+use mini_resource::prelude::{LoadContext, Reader, ResourceData, ResourceLoader};
+
+/// wgsl 着色器源码
+#[derive(TypeUuidProvider, ResourceData, Debug, Clone)]
+#[type_uuid(id = "b3a6f0c2-5d41-4e8a-9c7b-2f1d8e6a4b90")]
+pub struct Shader {
+    //加载的路径，用于错误信息
+    pub path: String,
+    pub source: String,
+}
+
+#[derive(Debug, Error)]
+pub enum ShaderLoaderError {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("shader source is not valid utf-8: {0}")]
+    Utf8(#[from] FromUtf8Error),
+}
+
+/// 加载 `.wgsl` 文件
+#[derive(Clone, Default)]
+pub struct ShaderLoader;
+
+impl ResourceLoader for ShaderLoader {
+    type ResourceData = Shader;
+    type Settings = ();
+    type Error = ShaderLoaderError;
+
+    fn extensions(&self) -> &[&str] {
+        &["wgsl"]
+    }
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut dyn Reader,
+        _settings: &'a Self::Settings,
+        load_context: &'a mut LoadContext<'_>,
+    ) -> Result<Shader, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(Shader {
+            path: load_context.path().display().to_string(),
+            source: String::from_utf8(bytes)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use mini_resource::prelude::ResourceManager;
+    use mini_task::TaskPool;
+
+    use super::*;
+    use crate::texture::prelude::ImageLoader;
+
+    #[test]
+    fn test_supported_extensions() {
+        let resource_manager = ResourceManager::new(Arc::new(TaskPool::new()));
+        resource_manager.add_loader(ImageLoader::default());
+        resource_manager.add_loader(ShaderLoader);
+
+        let extensions = resource_manager.supported_extensions();
+        assert!(extensions.contains(&"png".to_string()));
+        assert!(extensions.contains(&"wgsl".to_string()));
+
+        // 重复注册不会产生重复的扩展名
+        resource_manager.add_loader(ShaderLoader);
+        assert_eq!(resource_manager.supported_extensions(), extensions);
+    }
+}
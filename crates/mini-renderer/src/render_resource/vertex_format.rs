@@ -0,0 +1,105 @@
+use wgpu::VertexFormat;
+
+/// 顶点格式占用的字节数
+pub const fn vertex_format_size(format: VertexFormat) -> u64 {
+    match format {
+        VertexFormat::Uint8x2
+        | VertexFormat::Sint8x2
+        | VertexFormat::Unorm8x2
+        | VertexFormat::Snorm8x2 => 2,
+        VertexFormat::Uint8x4
+        | VertexFormat::Sint8x4
+        | VertexFormat::Unorm8x4
+        | VertexFormat::Snorm8x4
+        | VertexFormat::Uint16x2
+        | VertexFormat::Sint16x2
+        | VertexFormat::Unorm16x2
+        | VertexFormat::Snorm16x2
+        | VertexFormat::Float16x2
+        | VertexFormat::Float32
+        | VertexFormat::Uint32
+        | VertexFormat::Sint32
+        | VertexFormat::Unorm10_10_10_2 => 4,
+        VertexFormat::Uint16x4
+        | VertexFormat::Sint16x4
+        | VertexFormat::Unorm16x4
+        | VertexFormat::Snorm16x4
+        | VertexFormat::Float16x4
+        | VertexFormat::Float32x2
+        | VertexFormat::Uint32x2
+        | VertexFormat::Sint32x2
+        | VertexFormat::Float64 => 8,
+        VertexFormat::Float32x3 | VertexFormat::Uint32x3 | VertexFormat::Sint32x3 => 12,
+        VertexFormat::Float32x4
+        | VertexFormat::Uint32x4
+        | VertexFormat::Sint32x4
+        | VertexFormat::Float64x2 => 16,
+        VertexFormat::Float64x3 => 24,
+        VertexFormat::Float64x4 => 32,
+    }
+}
+
+/// 顶点格式的分量个数
+pub const fn vertex_format_components(format: VertexFormat) -> u32 {
+    match format {
+        VertexFormat::Float32
+        | VertexFormat::Uint32
+        | VertexFormat::Sint32
+        | VertexFormat::Float64 => 1,
+        VertexFormat::Uint8x2
+        | VertexFormat::Sint8x2
+        | VertexFormat::Unorm8x2
+        | VertexFormat::Snorm8x2
+        | VertexFormat::Uint16x2
+        | VertexFormat::Sint16x2
+        | VertexFormat::Unorm16x2
+        | VertexFormat::Snorm16x2
+        | VertexFormat::Float16x2
+        | VertexFormat::Float32x2
+        | VertexFormat::Uint32x2
+        | VertexFormat::Sint32x2
+        | VertexFormat::Float64x2 => 2,
+        VertexFormat::Float32x3
+        | VertexFormat::Uint32x3
+        | VertexFormat::Sint32x3
+        | VertexFormat::Float64x3 => 3,
+        VertexFormat::Uint8x4
+        | VertexFormat::Sint8x4
+        | VertexFormat::Unorm8x4
+        | VertexFormat::Snorm8x4
+        | VertexFormat::Uint16x4
+        | VertexFormat::Sint16x4
+        | VertexFormat::Unorm16x4
+        | VertexFormat::Snorm16x4
+        | VertexFormat::Float16x4
+        | VertexFormat::Float32x4
+        | VertexFormat::Uint32x4
+        | VertexFormat::Sint32x4
+        | VertexFormat::Float64x4
+        | VertexFormat::Unorm10_10_10_2 => 4,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_vertex_format_size() {
+        assert_eq!(vertex_format_size(VertexFormat::Float32x3), 12);
+        assert_eq!(vertex_format_size(VertexFormat::Uint16x4), 8);
+        assert_eq!(vertex_format_components(VertexFormat::Float32x3), 3);
+        assert_eq!(vertex_format_components(VertexFormat::Uint16x4), 4);
+
+        // 和 wgpu 自带的大小保持一致
+        for format in [
+            VertexFormat::Unorm8x2,
+            VertexFormat::Snorm16x4,
+            VertexFormat::Float32,
+            VertexFormat::Float64x3,
+            VertexFormat::Unorm10_10_10_2,
+        ] {
+            assert_eq!(vertex_format_size(format), format.size());
+        }
+    }
+}
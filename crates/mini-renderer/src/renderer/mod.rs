@@ -1,7 +1,10 @@
 mod render_device;
+mod render_stats;
+#[allow(clippy::module_inception)]
 mod renderer;
 mod wgpu_impl;
 
 pub use render_device::*;
+pub use render_stats::*;
 pub use renderer::*;
 pub use wgpu_impl::*;
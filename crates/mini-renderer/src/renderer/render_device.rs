@@ -1,4 +1,15 @@
-use crate::wrapper::{render_resource_wrapper, WgpuWrapper};
+use mini_core::bytemuck::{cast_slice, Pod};
+use wgpu::{
+    util::{BufferInitDescriptor, DeviceExt, TextureDataOrder},
+    BufferUsages,
+};
+
+use super::RenderQueue;
+use crate::{
+    render_resource::ErasedBuffer,
+    texture::prelude::Image,
+    wrapper::{render_resource_wrapper, WgpuWrapper},
+};
 
 render_resource_wrapper!(ErasedRenderDevice, wgpu::Device);
 
@@ -20,4 +31,74 @@ impl RenderDevice {
     pub fn wgpu_device(&self) -> &wgpu::Device {
         &self.device
     }
+
+    /// 创建纹理并上传图片数据，所有 mip 层按照 layer major 的顺序存放在 [`Image::data`] 中。
+    pub fn create_texture_with_image(&self, queue: &RenderQueue, image: &Image) -> wgpu::Texture {
+        // 压缩格式按块计算大小
+        debug_assert_eq!(
+            image.expected_data_size(),
+            Some(image.data.len()),
+            "image data does not match its descriptor",
+        );
+        self.device.create_texture_with_data(
+            queue,
+            &image.texture_descriptor,
+            TextureDataOrder::LayerMajor,
+            &image.data,
+        )
+    }
+
+    /// 创建缓冲区并写入 `contents`
+    pub fn create_buffer_with_data<T: Pod>(
+        &self,
+        label: Option<&str>,
+        contents: &[T],
+        usage: BufferUsages,
+    ) -> ErasedBuffer {
+        ErasedBuffer::new(self.device.create_buffer_init(&BufferInitDescriptor {
+            label,
+            contents: cast_slice(contents),
+            usage,
+        }))
+    }
+
+    /// 创建 uniform 缓冲区，之后可以通过 [`RenderQueue`] 写入新的数据
+    pub fn create_uniform_buffer<T: Pod>(&self, label: Option<&str>, value: &T) -> ErasedBuffer {
+        self.create_buffer_with_data(
+            label,
+            std::slice::from_ref(value),
+            BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+        )
+    }
+
+    pub fn create_vertex_buffer<T: Pod>(
+        &self,
+        label: Option<&str>,
+        vertices: &[T],
+    ) -> ErasedBuffer {
+        self.create_buffer_with_data(label, vertices, BufferUsages::VERTEX)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_utils::headless_renderer;
+
+    #[test]
+    fn test_create_vertex_buffer() {
+        let Some(renderer) = headless_renderer() else {
+            return;
+        };
+        let device = renderer.device;
+
+        let positions: Vec<[f32; 3]> = vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]];
+        let buffer = device.create_vertex_buffer(Some("positions"), &positions);
+        assert_eq!(buffer.size(), 36);
+        assert_eq!(buffer.usage(), BufferUsages::VERTEX);
+
+        let uniform = device.create_uniform_buffer(None, &[1.0f32; 4]);
+        assert_eq!(uniform.size(), 16);
+        assert!(uniform.usage().contains(BufferUsages::UNIFORM));
+    }
 }
@@ -0,0 +1,47 @@
+use std::time::{Duration, Instant};
+
+/// 一帧的渲染统计，由 [`Renderer::render`](super::Renderer::render) 收集
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RenderStats {
+    //提交命令所花费的 CPU 时间
+    pub frame_time: Duration,
+    pub draw_calls: u32,
+    pub triangles: u64,
+}
+
+impl RenderStats {
+    /// 记录一次绘制调用
+    pub fn record_draw(&mut self, triangles: u64) {
+        self.draw_calls += 1;
+        self.triangles += triangles;
+    }
+
+    /// 执行 `submit` 并把花费的时间计入 `frame_time`
+    pub fn time_submit<R>(&mut self, submit: impl FnOnce() -> R) -> R {
+        let start = Instant::now();
+        let result = submit();
+        self.frame_time += start.elapsed();
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_render_stats() {
+        let mut stats = RenderStats::default();
+        stats.record_draw(12);
+        stats.record_draw(2);
+        assert_eq!(stats.draw_calls, 2);
+        assert_eq!(stats.triangles, 14);
+
+        let value = stats.time_submit(|| {
+            std::thread::sleep(Duration::from_millis(1));
+            42
+        });
+        assert_eq!(value, 42);
+        assert!(stats.frame_time >= Duration::from_millis(1));
+    }
+}
@@ -1,9 +1,22 @@
-use mini_window::window::ErasedWindow;
-use wgpu::RenderPipeline;
+use mini_core::{tracing::warn, utils::Lazy};
+use mini_math::prelude::Color;
+use mini_resource::prelude::Resource;
+use mini_window::window::{ErasedWindow, WindowId};
+use wgpu::{CommandEncoderDescriptor, RenderPipeline, SurfaceError, TextureFormat, TextureView};
 
-use super::{RenderAdapter, RenderDevice, RenderInstance, RenderQueue};
+use super::{RenderAdapter, RenderDevice, RenderInstance, RenderQueue, RenderStats};
 
-use crate::surface_data::{SurfaceData, WindowSurfaceDatas};
+use crate::{
+    material::Material,
+    mesh::mesh::MeshVertexBufferLayoutRef,
+    render_resource::{
+        BlitPipeline, CachedPipelineId, PipelineCache, PipelineError, RenderPassBuilder,
+        SamplerCache, Shader,
+    },
+    settings::SampleCount,
+    surface_data::{SurfaceData, WindowSurfaceDatas},
+    texture::prelude::{FallbackTexture, GpuImage, Image},
+};
 
 pub struct Renderer {
     pub render_pipeline: Option<RenderPipeline>,
@@ -12,18 +25,109 @@ pub struct Renderer {
     pub instance: RenderInstance,
     pub adapter: RenderAdapter,
     pub window_surface_datas: WindowSurfaceDatas,
+    //清屏颜色
+    pub clear_color: Color,
+    pub sampler_cache: SamplerCache,
+    pub pipeline_cache: PipelineCache,
+    //之后初始化的窗口使用的采样数，不支持时回退到 1
+    pub sample_count: SampleCount,
+    //缺失纹理时使用的棋盘格纹理
+    pub fallback_texture: Option<FallbackTexture>,
+    //第一次调用 blit 时才创建
+    blit_pipeline: Lazy<BlitPipeline>,
+    //这一帧正在收集的统计
+    stats: RenderStats,
+    last_stats: RenderStats,
     //网格
 }
 
 impl Renderer {
-    pub fn render(&mut self) {
-        for surface_data in self.window_surface_datas.values_mut() {
-            surface_data.set_swapchain_texture();
+    /// 渲染所有窗口，只有无法恢复的错误（`OutOfMemory`）会返回，此时剩下的窗口不会渲染
+    pub fn render(&mut self) -> Result<(), SurfaceError> {
+        let window_ids = self
+            .window_surface_datas
+            .keys()
+            .copied()
+            .collect::<Vec<_>>();
+        let result = window_ids
+            .into_iter()
+            .try_for_each(|window_id| self.render_clear(window_id));
+
+        self.last_stats = std::mem::take(&mut self.stats);
+        result
+    }
+
+    /// 上一次 [`render`](Self::render) 的统计，包括两次 `render` 之间的 [`blit`](Self::blit) 等绘制
+    pub fn last_stats(&self) -> RenderStats {
+        self.last_stats
+    }
+
+    /// 记录这一帧的一次绘制调用
+    pub fn record_draw(&mut self, triangles: u64) {
+        self.stats.record_draw(triangles);
+    }
+
+    /// 只清屏的渲染路径：获取窗口的 swapchain 纹理，使用 `clear_color` 清屏后显示。
+    ///
+    /// 没有获取到 swapchain 纹理时跳过这一帧，只有 `OutOfMemory` 会返回错误。
+    pub fn render_clear(&mut self, window_id: WindowId) -> Result<(), SurfaceError> {
+        let Some(surface_data) = self.window_surface_datas.get_mut(&window_id) else {
+            return Ok(());
+        };
+
+        match surface_data.set_swapchain_texture(&self.device) {
+            Ok(()) => {}
+            // surface 已经重新配置，下一帧可以正常获取
+            Err(SurfaceError::Lost | SurfaceError::Outdated) => return Ok(()),
+            Err(SurfaceError::Timeout) => {
+                warn!("Timed out acquiring the swapchain texture, skipping the frame.");
+                return Ok(());
+            }
+            Err(err @ SurfaceError::OutOfMemory) => return Err(err),
         }
 
-        for surface_data in self.window_surface_datas.values_mut() {
-            surface_data.present();
+        if let Some(swap_chain_view) = surface_data.swap_chain_texture_view.as_ref() {
+            // 开启多重采样时渲染到 msaa 纹理，再解析到 swapchain 纹理
+            let (view, resolve_target) = match surface_data.msaa_view() {
+                Some(msaa_view) => (msaa_view, Some(swap_chain_view)),
+                None => (swap_chain_view, None),
+            };
+            self.stats.time_submit(|| {
+                submit_clear(
+                    &self.device,
+                    &self.queue,
+                    view,
+                    resolve_target,
+                    Some(surface_data.depth_view()),
+                    self.clear_color,
+                )
+            });
         }
+        surface_data.present();
+        Ok(())
+    }
+
+    /// 使用 `clear_color` 清空 `view`
+    pub fn clear_view(&self, view: &TextureView) {
+        submit_clear(
+            &self.device,
+            &self.queue,
+            view,
+            None,
+            None,
+            self.clear_color,
+        );
+    }
+
+    /// 把 `source` 原样绘制到 `target`，`format` 为 `target` 的格式，`source` 需要可以被采样
+    pub fn blit(&mut self, source: &TextureView, target: &TextureView, format: TextureFormat) {
+        let blit_pipeline = self
+            .blit_pipeline
+            .get_or_init(|| BlitPipeline::new(&self.device));
+        self.stats
+            .time_submit(|| blit_pipeline.blit(&self.device, &self.queue, source, target, format));
+        // 一个覆盖整个颜色附件的三角形
+        self.record_draw(1);
     }
 
     pub fn initialize_window(&mut self, window: &ErasedWindow) {
@@ -32,10 +136,83 @@ impl Renderer {
             &self.instance,
             &self.adapter,
             window,
+            self.sample_count,
         );
 
         self.window_surface_datas
-            .initialize_window(window, surface_data);
+            .initialize_window(window.id, surface_data);
+    }
+
+    /// 上传图片，采样器从 [`SamplerCache`] 中获取
+    pub fn upload_image(&mut self, image: &Image) -> GpuImage {
+        GpuImage::new(&self.device, &self.queue, &mut self.sampler_cache, image)
+    }
+
+    /// 上传纹理资源，加载失败的纹理会被替换为 [`FallbackTexture`]
+    pub fn upload_texture(&mut self, texture: &Resource<Image>) -> Option<GpuImage> {
+        let texture = match &self.fallback_texture {
+            Some(fallback_texture) => fallback_texture.resolve(texture),
+            None => texture.clone(),
+        };
+        let data = texture.data_ref();
+        let image = data.as_loaded_ref()?;
+        Some(self.upload_image(image))
+    }
+
+    /// 创建材质在窗口上渲染时使用的管线，`shader` 为 [`Material::fragment_shader`] 加载的着色器，
+    /// 颜色附件的格式和采样数和窗口的 surface 一致
+    pub fn create_material_pipeline<M: Material>(
+        &self,
+        material: &M,
+        shader: &Shader,
+        layout: &MeshVertexBufferLayoutRef,
+        window_id: WindowId,
+    ) -> Result<RenderPipeline, PipelineError> {
+        let (format, sample_count) = self.surface_targets(window_id)?;
+        let mut descriptor = M::pipeline_descriptor(shader, layout, format)?;
+        material.specialize(&mut descriptor);
+        descriptor.sample_count = sample_count;
+        descriptor.create_render_pipeline(&self.device)
+    }
+
+    /// 和 [`create_material_pipeline`](Self::create_material_pipeline) 相同，但是管线保存在 [`PipelineCache`] 中，
+    /// 通过 [`PipelineCache::get`] 获取
+    pub fn specialize_material_pipeline<M: Material>(
+        &mut self,
+        material: &M,
+        shader: &Shader,
+        layout: &MeshVertexBufferLayoutRef,
+        window_id: WindowId,
+    ) -> Result<CachedPipelineId, PipelineError> {
+        let (format, sample_count) = self.surface_targets(window_id)?;
+        self.pipeline_cache.specialize_material(
+            &self.device,
+            material,
+            shader,
+            layout,
+            format,
+            sample_count,
+        )
+    }
+
+    //窗口颜色附件的格式和采样数，采样数在初始化 surface 时已经按照适配器的支持情况回退
+    fn surface_targets(&self, window_id: WindowId) -> Result<(TextureFormat, u32), PipelineError> {
+        let surface_data = self
+            .window_surface_datas
+            .get(&window_id)
+            .ok_or(PipelineError::MissingSurface(window_id))?;
+        Ok((surface_data.view_format(), surface_data.sample_count()))
+    }
+
+    pub fn remove_window(&mut self, window_id: WindowId) {
+        self.window_surface_datas.remove_window(window_id);
+    }
+
+    /// 窗口大小改变时重新配置 surface 和深度纹理，单位为物理像素
+    pub fn resize_window(&mut self, window_id: WindowId, width: u32, height: u32) {
+        if let Some(surface_data) = self.window_surface_datas.get_mut(&window_id) {
+            surface_data.resize(&self.device, width, height);
+        }
     }
 
     pub fn new(
@@ -51,6 +228,143 @@ impl Renderer {
             instance,
             adapter,
             window_surface_datas: Default::default(),
+            fallback_texture: None,
+            clear_color: Color::BLACK,
+            sampler_cache: SamplerCache::default(),
+            pipeline_cache: PipelineCache::default(),
+            sample_count: SampleCount::default(),
+            blit_pipeline: Lazy::new(),
+            stats: RenderStats::default(),
+            last_stats: RenderStats::default(),
         }
     }
 }
+
+//有深度纹理时同时清除为 1.0
+fn submit_clear(
+    device: &RenderDevice,
+    queue: &RenderQueue,
+    view: &TextureView,
+    resolve_target: Option<&TextureView>,
+    depth_view: Option<&TextureView>,
+    color: Color,
+) {
+    let mut encoder = device
+        .wgpu_device()
+        .create_command_encoder(&CommandEncoderDescriptor {
+            label: Some("clear_encoder"),
+        });
+    let mut builder = RenderPassBuilder::new("clear_pass").with_resolved_color_attachment(
+        view,
+        resolve_target,
+        Some(color),
+    );
+    if let Some(depth_view) = depth_view {
+        builder = builder.with_depth_attachment(depth_view, Some(1.0));
+    }
+    builder.begin(&mut encoder);
+    queue.submit([encoder.finish()]);
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use wgpu::PrimitiveTopology;
+
+    use super::*;
+    use crate::{
+        material::{StandardMaterial, STANDARD_MATERIAL_SHADER_PATH},
+        mesh::mesh::{Mesh, MeshVertexBufferLayouts},
+        test_utils::{headless_renderer, read_pixel, render_target},
+    };
+
+    #[test]
+    fn test_material_pipeline_without_surface() {
+        let Some(mut renderer) = headless_renderer() else {
+            return;
+        };
+        let shader = Shader {
+            path: STANDARD_MATERIAL_SHADER_PATH.to_string(),
+            source: include_str!("../material/standard_material.wgsl").to_string(),
+        };
+        let mut layouts = MeshVertexBufferLayouts::default();
+        let mesh = Mesh::new(PrimitiveTopology::TriangleList)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, vec![[0.0; 3]; 3])
+            .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0.0; 2]; 3]);
+        let layout = mesh.get_mesh_vertex_buffer_layout(&mut layouts);
+
+        // 采样数和格式来自窗口的 surface，没有初始化的窗口不能创建管线
+        let window_id = WindowId::new(0);
+        let material = StandardMaterial::default();
+        assert!(matches!(
+            renderer.specialize_material_pipeline(&material, &shader, &layout, window_id),
+            Err(PipelineError::MissingSurface(id)) if id == window_id
+        ));
+        assert!(renderer.pipeline_cache.is_empty());
+    }
+
+    #[test]
+    fn test_clear_view() {
+        let Some(mut renderer) = headless_renderer() else {
+            return;
+        };
+        renderer.clear_color = Color::linear_rgb(0.0, 0.0, 1.0);
+
+        let texture = render_target(&renderer);
+        renderer.clear_view(&texture.create_view(&Default::default()));
+        assert_eq!(read_pixel(&renderer, &texture), [0, 0, 255, 255]);
+
+        // 没有初始化的窗口什么都不做
+        assert!(renderer.render_clear(WindowId::new(0)).is_ok());
+    }
+
+    #[test]
+    fn test_blit() {
+        let Some(mut renderer) = headless_renderer() else {
+            return;
+        };
+        renderer.clear_color = Color::linear_rgb(1.0, 0.0, 0.0);
+        let source = render_target(&renderer);
+        let source_view = source.create_view(&Default::default());
+        renderer.clear_view(&source_view);
+
+        // 着色器在第一次 blit 时才创建
+        assert!(renderer.blit_pipeline.get().is_none());
+        let target = render_target(&renderer);
+        renderer.blit(
+            &source_view,
+            &target.create_view(&Default::default()),
+            target.format(),
+        );
+        assert!(renderer.blit_pipeline.get().is_some());
+        assert_eq!(read_pixel(&renderer, &target), [255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn test_render_stats() {
+        let Some(mut renderer) = headless_renderer() else {
+            return;
+        };
+        let source = render_target(&renderer);
+        let target = render_target(&renderer);
+        let (source_view, target_view) = (
+            source.create_view(&Default::default()),
+            target.create_view(&Default::default()),
+        );
+
+        // 两帧之间的绘制计入下一次 render 的统计
+        renderer.blit(&source_view, &target_view, target.format());
+        renderer.blit(&source_view, &target_view, target.format());
+        assert_eq!(renderer.last_stats(), RenderStats::default());
+        renderer.render().unwrap();
+        let stats = renderer.last_stats();
+        assert_eq!(stats.draw_calls, 2);
+        assert_eq!(stats.triangles, 2);
+        assert!(stats.frame_time > Duration::ZERO);
+
+        // 没有绘制的帧统计为空
+        renderer.render().unwrap();
+        assert_eq!(renderer.last_stats(), RenderStats::default());
+    }
+}
@@ -0,0 +1,120 @@
+use std::path::{Path, PathBuf};
+
+use mini_core::tracing::warn;
+use wgpu::{Adapter, DeviceDescriptor, MemoryHints, TextureFormat};
+
+/// 多重采样抗锯齿的采样数，`One` 表示不开启
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum SampleCount {
+    #[default]
+    One = 1,
+    Two = 2,
+    Four = 4,
+    Eight = 8,
+}
+
+impl SampleCount {
+    pub fn count(self) -> u32 {
+        self as u32
+    }
+
+    /// 适配器在 `format` 上不支持这个采样数时回退到 `One`
+    pub fn supported(self, adapter: &Adapter, format: TextureFormat) -> SampleCount {
+        let flags = adapter.get_texture_format_features(format).flags;
+        self.supported_with(|count| flags.sample_count_supported(count), format)
+    }
+
+    fn supported_with(
+        self,
+        is_supported: impl Fn(u32) -> bool,
+        format: TextureFormat,
+    ) -> SampleCount {
+        if self == SampleCount::One || is_supported(self.count()) {
+            return self;
+        }
+
+        warn!(
+            "{:?} does not support a sample count of {}, falling back to 1.",
+            format,
+            self.count()
+        );
+        SampleCount::One
+    }
+}
+
+/// 创建渲染设备时使用的设置
+#[derive(Debug, Clone, Default)]
+pub struct RenderSettings {
+    /// wgpu 的 api trace 保存的目录，需要开启 `trace` feature
+    pub trace_path: Option<PathBuf>,
+    //创建设备时需要的 feature，例如线框模式需要 `POLYGON_MODE_LINE`
+    pub required_features: wgpu::Features,
+    //窗口颜色附件的采样数，不支持时回退到 1
+    pub sample_count: SampleCount,
+}
+
+impl RenderSettings {
+    pub fn device_descriptor(&self) -> DeviceDescriptor<'static> {
+        DeviceDescriptor {
+            required_features: self.required_features,
+            required_limits: wgpu::Limits::default(),
+            label: None,
+            memory_hints: MemoryHints::default(),
+        }
+    }
+
+    /// 传给 `request_device` 的 trace 目录，没有开启 `trace` feature 时总是返回 `None`
+    pub fn device_trace_path(&self) -> Option<&Path> {
+        let trace_path = self.trace_path.as_deref()?;
+
+        if cfg!(feature = "trace") {
+            Some(trace_path)
+        } else {
+            warn!("The trace path is ignored, enable the `trace` feature of mini-renderer to capture a trace.");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_trace_path() {
+        assert!(RenderSettings::default().device_trace_path().is_none());
+
+        let settings = RenderSettings {
+            trace_path: Some(PathBuf::from("target/wgpu-trace")),
+            ..Default::default()
+        };
+        if cfg!(feature = "trace") {
+            assert_eq!(
+                settings.device_trace_path(),
+                Some(Path::new("target/wgpu-trace"))
+            );
+        } else {
+            assert!(settings.device_trace_path().is_none());
+        }
+    }
+
+    #[test]
+    fn test_sample_count_fallback() {
+        let format = TextureFormat::Bgra8UnormSrgb;
+        assert_eq!(SampleCount::Four.count(), 4);
+
+        let supports_four = |count: u32| count == 1 || count == 4;
+        assert_eq!(
+            SampleCount::Four.supported_with(supports_four, format),
+            SampleCount::Four
+        );
+        assert_eq!(
+            SampleCount::Eight.supported_with(supports_four, format),
+            SampleCount::One
+        );
+        assert_eq!(
+            SampleCount::One.supported_with(|_| false, format),
+            SampleCount::One
+        );
+    }
+}
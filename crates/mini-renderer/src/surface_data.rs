@@ -3,44 +3,133 @@ use std::{
     ops::{Deref, DerefMut},
 };
 
-use mini_window::window::{ErasedWindow, WindowId};
+use mini_window::window::{ErasedWindow, PresentMode as WindowPresentMode, WindowId};
 use wgpu::{
-    Surface, SurfaceConfiguration, SurfaceTargetUnsafe, SurfaceTexture, TextureView,
-    TextureViewDescriptor,
+    CompositeAlphaMode, PresentMode, Surface, SurfaceConfiguration, SurfaceError,
+    SurfaceTargetUnsafe, SurfaceTexture, TextureFormat, TextureView, TextureViewDescriptor,
 };
 
 pub use crate::{
     renderer::{RenderAdapter, RenderDevice, RenderInstance},
     wrapper::WgpuWrapper,
 };
+use crate::{
+    settings::SampleCount,
+    texture::{color_space::ColorSpace, depth_texture::DepthTexture, msaa_texture::MsaaTexture},
+};
+
+// swapchain 的 view 总是使用 sRGB 格式
+const SWAP_CHAIN_COLOR_SPACE: ColorSpace = ColorSpace::Srgb;
+
+/// 调用 `acquire` 获取这一帧的纹理，`Timeout` 时重试一次，`Lost` 和 `Outdated` 时调用 `reconfigure`
+pub(crate) fn acquire_with_recovery<T>(
+    mut acquire: impl FnMut() -> Result<T, SurfaceError>,
+    reconfigure: impl FnOnce(),
+) -> Result<T, SurfaceError> {
+    let result = match acquire() {
+        Err(SurfaceError::Timeout) => acquire(),
+        result => result,
+    };
+    if let Err(SurfaceError::Lost | SurfaceError::Outdated) = result {
+        reconfigure();
+    }
+    result
+}
 
 pub struct SurfaceData {
     //画板
     pub surface: WgpuWrapper<Surface<'static>>,
     pub configuration: SurfaceConfiguration,
+    capabilities: SurfaceCapabilities,
 
     pub swap_chain_texture_view: Option<TextureView>,
 
     pub swap_chain_texture: Option<SurfaceTexture>,
+
+    //和 surface 大小一致的深度纹理
+    depth_texture: DepthTexture,
+    //采样数大于 1 时渲染到这个纹理，再解析到 swapchain 纹理
+    msaa_texture: Option<MsaaTexture>,
 }
 
 impl SurfaceData {
-    pub fn set_swapchain_texture(&mut self) {
-        let frame = self.surface.get_current_texture().unwrap();
+    /// 获取这一帧的 surface texture。
+    ///
+    /// 超时会重试一次，surface 丢失或过期时会重新配置，返回错误时这一帧需要跳过。
+    pub fn set_swapchain_texture(&mut self, device: &RenderDevice) -> Result<(), SurfaceError> {
+        let mut outdated = false;
+        let surface = &self.surface;
+        let frame = acquire_with_recovery(|| surface.get_current_texture(), || outdated = true);
+        if outdated {
+            self.reconfigure(device);
+        }
+        let frame = frame?;
 
         let texture_view_descriptor = TextureViewDescriptor {
-            format: Some(frame.texture.format().add_srgb_suffix()),
+            format: Some(SWAP_CHAIN_COLOR_SPACE.apply(frame.texture.format())),
             ..Default::default()
         };
-        self.swap_chain_texture_view = Some(TextureView::from(
-            frame.texture.create_view(&texture_view_descriptor),
-        ));
-        self.swap_chain_texture = Some(SurfaceTexture::from(frame));
+        self.swap_chain_texture_view = Some(frame.texture.create_view(&texture_view_descriptor));
+        self.swap_chain_texture = Some(frame);
+        Ok(())
+    }
+
+    pub fn reconfigure(&mut self, device: &RenderDevice) {
+        self.swap_chain_texture_view = None;
+        self.swap_chain_texture = None;
+        self.surface
+            .configure(device.wgpu_device(), &self.configuration);
+        let (width, height) = (self.configuration.width, self.configuration.height);
+        self.depth_texture.resize(device, width, height);
+        if let Some(msaa_texture) = self.msaa_texture.as_mut() {
+            msaa_texture.resize(device, width, height);
+        }
     }
 
+    /// 窗口大小改变时调用，深度纹理会随之重新创建。
+    ///
+    /// 宽高为 0（例如窗口最小化）时不做任何事。
+    pub fn resize(&mut self, device: &RenderDevice, width: u32, height: u32) {
+        if width == 0 || height == 0 {
+            return;
+        }
+        if self.configuration.width == width && self.configuration.height == height {
+            return;
+        }
+
+        self.configuration.width = width;
+        self.configuration.height = height;
+        self.reconfigure(device);
+    }
+
+    /// 深度纹理的 view，大小总是和 swapchain 纹理一致
+    pub fn depth_view(&self) -> &TextureView {
+        &self.depth_texture.view
+    }
+
+    /// 多重采样的颜色附件，采样数为 1 时为 `None`，此时直接渲染到 swapchain 纹理
+    pub fn msaa_view(&self) -> Option<&TextureView> {
+        self.msaa_texture
+            .as_ref()
+            .map(|msaa_texture| &msaa_texture.view)
+    }
+
+    /// 颜色附件实际使用的采样数，管线的 `multisample.count` 需要和它一致
+    pub fn sample_count(&self) -> u32 {
+        self.depth_texture.texture.sample_count()
+    }
+
+    /// swapchain 纹理的 view 使用的格式，也是管线颜色附件的格式
+    pub fn view_format(&self) -> TextureFormat {
+        SWAP_CHAIN_COLOR_SPACE.apply(self.configuration.format)
+    }
+
+    /// 没有获取到 surface texture 时什么都不做。
     pub fn present(&mut self) {
-        let swap_chain_texture = self.swap_chain_texture.take().unwrap();
-        swap_chain_texture.present();
+        self.swap_chain_texture_view = None;
+        if let Some(swap_chain_texture) = self.swap_chain_texture.take() {
+            swap_chain_texture.present();
+        }
     }
 
     pub fn initialize_surface_data(
@@ -48,6 +137,7 @@ impl SurfaceData {
         instance: &RenderInstance,
         adapter: &RenderAdapter,
         window: &ErasedWindow,
+        sample_count: SampleCount,
     ) -> Self {
         let size = window.window.physical_size();
 
@@ -65,64 +155,272 @@ impl SurfaceData {
                 .create_surface_unsafe(surface_target)
                 .expect("Failed to create wgpu surface")
         };
-        let caps = surface.get_capabilities(&adapter);
+        let caps = SurfaceCapabilities::from(surface.get_capabilities(adapter));
 
         let surface_format = caps
-            .formats
-            .iter()
-            .find(|f| f.is_srgb())
-            .copied()
-            .unwrap_or(caps.formats[0]);
+            .preferred_format()
+            .expect("Surface is incompatible with the adapter");
 
         let config = wgpu::SurfaceConfiguration {
             usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
             format: surface_format,
             width: size.x,
             height: size.y,
-            present_mode: caps.present_modes[0],
+            present_mode: caps.select_present_mode(window.window.present_mode),
             alpha_mode: caps.alpha_modes[0],
-            view_formats: vec![],
+            view_formats: if ColorSpace::from_format(surface_format) == SWAP_CHAIN_COLOR_SPACE {
+                vec![]
+            } else {
+                vec![SWAP_CHAIN_COLOR_SPACE.apply(surface_format)]
+            },
             desired_maximum_frame_latency: 2,
         };
 
-        surface.configure(&device.wgpu_device(), &config);
+        surface.configure(device.wgpu_device(), &config);
+        let view_format = SWAP_CHAIN_COLOR_SPACE.apply(surface_format);
+        let sample_count = sample_count.supported(adapter, view_format).count();
+        let depth_texture = DepthTexture::new(device, config.width, config.height, sample_count);
+        let msaa_texture = (sample_count > 1).then(|| {
+            MsaaTexture::new(
+                device,
+                view_format,
+                config.width,
+                config.height,
+                sample_count,
+            )
+        });
 
         Self {
             surface: WgpuWrapper::new(surface),
             configuration: config,
+            capabilities: caps,
             swap_chain_texture: None,
             swap_chain_texture_view: None,
+            depth_texture,
+            msaa_texture,
         }
     }
+
+    /// surface 在当前 adapter 上支持的格式和显示模式。
+    pub fn capabilities(&self) -> &SurfaceCapabilities {
+        &self.capabilities
+    }
 }
 
-#[derive(Default)]
-pub struct WindowSurfaceDatas {
-    surface_datas: HashMap<WindowId, SurfaceData>,
+/// [`wgpu::SurfaceCapabilities`] 的精简版本，供设置界面等工具查询。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SurfaceCapabilities {
+    pub formats: Vec<TextureFormat>,
+    pub present_modes: Vec<PresentMode>,
+    pub alpha_modes: Vec<CompositeAlphaMode>,
+}
+
+impl SurfaceCapabilities {
+    /// 优先选择 sRGB 格式，否则使用第一个支持的格式。
+    pub fn preferred_format(&self) -> Option<TextureFormat> {
+        self.formats
+            .iter()
+            .find(|f| ColorSpace::from_format(**f).is_srgb())
+            .or_else(|| self.formats.first())
+            .copied()
+    }
+
+    pub fn supports_format(&self, format: TextureFormat) -> bool {
+        self.formats.contains(&format)
+    }
+
+    pub fn supports_present_mode(&self, present_mode: PresentMode) -> bool {
+        self.present_modes.contains(&present_mode)
+    }
+
+    /// 窗口设置的显示模式对应的 wgpu 显示模式，不支持时使用 `Fifo`
+    pub fn select_present_mode(&self, present_mode: WindowPresentMode) -> PresentMode {
+        let candidates: &[PresentMode] = match present_mode {
+            WindowPresentMode::AutoVsync => &[PresentMode::FifoRelaxed],
+            WindowPresentMode::AutoNoVsync => &[PresentMode::Immediate, PresentMode::Mailbox],
+            WindowPresentMode::Fifo => &[],
+            WindowPresentMode::FifoRelaxed => &[PresentMode::FifoRelaxed],
+            WindowPresentMode::Immediate => &[PresentMode::Immediate],
+            WindowPresentMode::Mailbox => &[PresentMode::Mailbox],
+        };
+        candidates
+            .iter()
+            .copied()
+            .find(|mode| self.supports_present_mode(*mode))
+            .unwrap_or(PresentMode::Fifo)
+    }
+}
+
+impl From<wgpu::SurfaceCapabilities> for SurfaceCapabilities {
+    fn from(caps: wgpu::SurfaceCapabilities) -> Self {
+        Self {
+            formats: caps.formats,
+            present_modes: caps.present_modes,
+            alpha_modes: caps.alpha_modes,
+        }
+    }
+}
+
+/// 每个窗口的 surface，`S` 只在测试中替换为不需要系统窗口的类型
+pub struct WindowSurfaceDatas<S = SurfaceData> {
+    surface_datas: HashMap<WindowId, S>,
     initialized_windows: HashSet<WindowId>,
 }
 
-impl Deref for WindowSurfaceDatas {
-    type Target = HashMap<WindowId, SurfaceData>;
+impl<S> Default for WindowSurfaceDatas<S> {
+    fn default() -> Self {
+        Self {
+            surface_datas: Default::default(),
+            initialized_windows: Default::default(),
+        }
+    }
+}
+
+impl<S> Deref for WindowSurfaceDatas<S> {
+    type Target = HashMap<WindowId, S>;
 
     fn deref(&self) -> &Self::Target {
         &self.surface_datas
     }
 }
 
-impl DerefMut for WindowSurfaceDatas {
+impl<S> DerefMut for WindowSurfaceDatas<S> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.surface_datas
     }
 }
 
-impl WindowSurfaceDatas {
-    pub fn initialize_window(&mut self, window: &ErasedWindow, surface_data: SurfaceData) {
-        if self.initialized_windows.contains(&window.id) {
+impl<S> WindowSurfaceDatas<S> {
+    pub fn initialize_window(&mut self, window_id: WindowId, surface_data: S) {
+        if self.initialized_windows.contains(&window_id) {
             return;
         }
-        self.surface_datas.insert(window.id, surface_data);
+        self.surface_datas.insert(window_id, surface_data);
+
+        self.initialized_windows.insert(window_id);
+    }
+
+    /// 移除窗口对应的 surface。
+    pub fn remove_window(&mut self, window_id: WindowId) -> Option<S> {
+        self.initialized_windows.remove(&window_id);
+        self.surface_datas.remove(&window_id)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_capabilities_contain_preferred_format() {
+        let caps = SurfaceCapabilities::from(wgpu::SurfaceCapabilities {
+            formats: vec![TextureFormat::Bgra8Unorm, TextureFormat::Bgra8UnormSrgb],
+            present_modes: vec![PresentMode::Fifo, PresentMode::Mailbox],
+            alpha_modes: vec![CompositeAlphaMode::Opaque],
+            usages: wgpu::TextureUsages::RENDER_ATTACHMENT,
+        });
+
+        let format = caps.preferred_format().unwrap();
+        assert_eq!(format, TextureFormat::Bgra8UnormSrgb);
+        assert!(caps.supports_format(format));
+        assert!(caps.supports_present_mode(PresentMode::Mailbox));
+        assert!(!caps.supports_present_mode(PresentMode::Immediate));
+
+        let caps = SurfaceCapabilities {
+            formats: vec![TextureFormat::Rgba16Float],
+            ..caps
+        };
+        assert_eq!(caps.preferred_format(), Some(TextureFormat::Rgba16Float));
+    }
+
+    #[test]
+    fn test_select_present_mode() {
+        let caps = SurfaceCapabilities {
+            formats: vec![TextureFormat::Bgra8UnormSrgb],
+            present_modes: vec![PresentMode::Fifo, PresentMode::Mailbox],
+            alpha_modes: vec![CompositeAlphaMode::Opaque],
+        };
+
+        assert_eq!(
+            caps.select_present_mode(WindowPresentMode::default()),
+            PresentMode::Fifo
+        );
+        assert_eq!(
+            caps.select_present_mode(WindowPresentMode::AutoNoVsync),
+            PresentMode::Mailbox
+        );
+        assert_eq!(
+            caps.select_present_mode(WindowPresentMode::AutoVsync),
+            PresentMode::Fifo
+        );
+        // 不支持时回退到 Fifo
+        assert_eq!(
+            caps.select_present_mode(WindowPresentMode::Immediate),
+            PresentMode::Fifo
+        );
+    }
+
+    #[test]
+    fn test_remove_window() {
+        // SurfaceData 需要系统窗口创建 surface，这里用窗口的编号代替
+        let mut surface_datas = WindowSurfaceDatas::default();
+        let primary = WindowId::new(0);
+        let secondary = WindowId::new(1);
+        surface_datas.initialize_window(primary, "primary");
+        surface_datas.initialize_window(secondary, "secondary");
+        // 已经初始化的窗口不会被替换
+        surface_datas.initialize_window(secondary, "replaced");
+        assert_eq!(surface_datas.len(), 2);
+
+        assert_eq!(surface_datas.remove_window(secondary), Some("secondary"));
+        assert!(!surface_datas.contains_key(&secondary));
+        assert_eq!(surface_datas.get(&primary), Some(&"primary"));
+        assert_eq!(surface_datas.remove_window(secondary), None);
+
+        // 关闭之后重新创建的窗口使用新的 surface
+        surface_datas.initialize_window(secondary, "recreated");
+        assert_eq!(surface_datas.get(&secondary), Some(&"recreated"));
+        assert_eq!(surface_datas.len(), 2);
+    }
+
+    #[test]
+    fn test_acquire_with_recovery() {
+        // 按顺序返回预设的结果，记录调用次数
+        let acquire = |results: Vec<Result<u32, SurfaceError>>| {
+            let mut results = results.into_iter();
+            let mut attempts = 0;
+            let mut reconfigured = false;
+            let result = acquire_with_recovery(
+                || {
+                    attempts += 1;
+                    results.next().unwrap()
+                },
+                || reconfigured = true,
+            );
+            (result, attempts, reconfigured)
+        };
 
-        self.initialized_windows.insert(window.id);
+        assert_eq!(acquire(vec![Ok(1)]), (Ok(1), 1, false));
+        // 超时后重试一次
+        assert_eq!(
+            acquire(vec![Err(SurfaceError::Timeout), Ok(2)]),
+            (Ok(2), 2, false)
+        );
+        assert_eq!(
+            acquire(vec![Err(SurfaceError::Timeout), Err(SurfaceError::Timeout)]),
+            (Err(SurfaceError::Timeout), 2, false)
+        );
+        assert_eq!(
+            acquire(vec![Err(SurfaceError::Timeout), Err(SurfaceError::Lost)]),
+            (Err(SurfaceError::Lost), 2, true)
+        );
+        assert_eq!(
+            acquire(vec![Err(SurfaceError::Outdated)]),
+            (Err(SurfaceError::Outdated), 1, true)
+        );
+        assert_eq!(
+            acquire(vec![Err(SurfaceError::OutOfMemory)]),
+            (Err(SurfaceError::OutOfMemory), 1, false)
+        );
     }
 }
@@ -0,0 +1,93 @@
+//! 需要 GPU 的测试使用的辅助函数，其他 crate 通过 `test-utils` feature 使用。
+
+use std::{sync::Arc, thread};
+
+use mini_core::futures_lite::future::block_on;
+use wgpu::{Extent3d, Texture, TextureDimension, TextureFormat, TextureUsages};
+
+use crate::{
+    renderer::{RenderAdapter, RenderDevice, RenderInstance, RenderQueue, Renderer},
+    wrapper::WgpuWrapper,
+};
+
+/// 创建不关联窗口的 [`Renderer`]，供需要 GPU 的测试使用。
+///
+/// 没有可用的 GPU 时输出跳过的测试并返回 None。
+pub fn headless_renderer() -> Option<Renderer> {
+    let instance = wgpu::Instance::default();
+    let device = block_on(instance.request_adapter(&wgpu::RequestAdapterOptions::default()))
+        .and_then(|adapter| {
+            block_on(adapter.request_device(&wgpu::DeviceDescriptor::default(), None))
+                .ok()
+                .map(|(device, queue)| (adapter, device, queue))
+        });
+
+    let Some((adapter, device, queue)) = device else {
+        eprintln!(
+            "skipping {}: no GPU adapter available",
+            thread::current().name().unwrap_or("test")
+        );
+        return None;
+    };
+
+    Some(Renderer::new(
+        RenderDevice::from(device),
+        RenderQueue(Arc::new(WgpuWrapper::new(queue))),
+        RenderInstance(Arc::new(WgpuWrapper::new(instance))),
+        RenderAdapter(Arc::new(WgpuWrapper::new(adapter))),
+    ))
+}
+
+/// 1x1 的 `Rgba8Unorm` 颜色附件，可以被采样，也可以通过 [`read_pixel`] 读回
+pub fn render_target(renderer: &Renderer) -> Texture {
+    renderer
+        .device
+        .wgpu_device()
+        .create_texture(&wgpu::TextureDescriptor {
+            label: Some("test_render_target"),
+            size: Extent3d {
+                width: 1,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::Rgba8Unorm,
+            usage: TextureUsages::RENDER_ATTACHMENT
+                | TextureUsages::TEXTURE_BINDING
+                | TextureUsages::COPY_SRC,
+            view_formats: &[],
+        })
+}
+
+/// 读回 [`render_target`] 创建的纹理的像素，会等待之前提交的命令执行完成
+pub fn read_pixel(renderer: &Renderer, texture: &Texture) -> [u8; 4] {
+    let device = renderer.device.wgpu_device();
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: wgpu::COPY_BYTES_PER_ROW_ALIGNMENT as u64,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+    let mut encoder = device.create_command_encoder(&Default::default());
+    encoder.copy_texture_to_buffer(
+        texture.as_image_copy(),
+        wgpu::ImageCopyBuffer {
+            buffer: &buffer,
+            layout: wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT),
+                rows_per_image: Some(1),
+            },
+        },
+        texture.size(),
+    );
+    renderer.queue.submit([encoder.finish()]);
+
+    let slice = buffer.slice(..);
+    slice.map_async(wgpu::MapMode::Read, |result| result.unwrap());
+    device.poll(wgpu::Maintain::Wait);
+    let pixel = slice.get_mapped_range()[..4].try_into().unwrap();
+    pixel
+}
@@ -0,0 +1,35 @@
+use wgpu::TextureFormat;
+
+/// 纹理数据所在的颜色空间，决定了上传和采样时是否进行 sRGB 转换
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ColorSpace {
+    Srgb,
+    Linear,
+}
+
+impl ColorSpace {
+    pub fn from_is_srgb(is_srgb: bool) -> Self {
+        if is_srgb {
+            ColorSpace::Srgb
+        } else {
+            ColorSpace::Linear
+        }
+    }
+
+    /// 没有 sRGB 变体的格式都是线性的
+    pub fn from_format(format: TextureFormat) -> Self {
+        Self::from_is_srgb(format.is_srgb())
+    }
+
+    pub fn is_srgb(self) -> bool {
+        self == ColorSpace::Srgb
+    }
+
+    /// 返回 `format` 在这个颜色空间下的变体，没有对应变体时返回原格式
+    pub fn apply(self, format: TextureFormat) -> TextureFormat {
+        match self {
+            ColorSpace::Srgb => format.add_srgb_suffix(),
+            ColorSpace::Linear => format.remove_srgb_suffix(),
+        }
+    }
+}
@@ -0,0 +1,221 @@
+use std::io::Cursor;
+
+use ddsfile::{Caps2, D3DFormat, Dds, DxgiFormat, MiscFlag};
+use wgpu::{
+    Extent3d, TextureDimension, TextureFormat, TextureViewDescriptor, TextureViewDimension,
+};
+
+use super::prelude::{CompressedImageFormats, Image, TextureError, TranscodeFormat};
+
+/// 解析 DDS 容器。DDS 本身就是按 layer 主序（每一层依次存放全部 mip）存储的。
+pub fn dds_buffer_to_image(
+    buffer: &[u8],
+    supported_compressed_formats: CompressedImageFormats,
+    is_srgb: bool,
+) -> Result<Image, TextureError> {
+    let dds = Dds::read(&mut Cursor::new(buffer))
+        .map_err(|err| TextureError::InvalidData(format!("Failed to parse DDS file: {err}")))?;
+    let texture_format = dds_format_to_texture_format(&dds, is_srgb)?;
+
+    if !supported_compressed_formats.supports(texture_format) {
+        return Err(TextureError::UnsupportedTextureFormat(format!(
+            "Format not supported by this GPU: {texture_format:?}"
+        )));
+    }
+
+    let is_cubemap = dds.header.caps2.contains(Caps2::CUBEMAP)
+        || dds
+            .header10
+            .as_ref()
+            .is_some_and(|header10| header10.misc_flag.contains(MiscFlag::TEXTURECUBE));
+    let mut layer_count = dds.get_num_array_layers().max(1);
+    if is_cubemap && dds.header10.is_some() {
+        // DX10 头中的 array_size 是立方体贴图的个数
+        layer_count *= 6;
+    }
+    if is_cubemap && layer_count % 6 != 0 {
+        return Err(TextureError::IncompleteCubemap);
+    }
+
+    let depth = dds.get_depth();
+    let dimension = if depth > 1 {
+        TextureDimension::D3
+    } else {
+        TextureDimension::D2
+    };
+
+    let mut image = Image {
+        data: dds.data.clone(),
+        ..Default::default()
+    };
+    image.texture_descriptor.size = Extent3d {
+        width: dds.get_width(),
+        height: dds.get_height(),
+        depth_or_array_layers: if dimension == TextureDimension::D3 {
+            depth
+        } else {
+            layer_count
+        },
+    }
+    .physical_size(texture_format);
+    image.texture_descriptor.dimension = dimension;
+    image.texture_descriptor.format = texture_format;
+    image.texture_descriptor.mip_level_count = dds.get_num_mipmap_levels().max(1);
+
+    if is_cubemap {
+        image.texture_view_descriptor = Some(TextureViewDescriptor {
+            dimension: Some(if layer_count > 6 {
+                TextureViewDimension::CubeArray
+            } else {
+                TextureViewDimension::Cube
+            }),
+            ..Default::default()
+        });
+    } else if layer_count > 1 {
+        image.texture_view_descriptor = Some(TextureViewDescriptor {
+            dimension: Some(TextureViewDimension::D2Array),
+            ..Default::default()
+        });
+    }
+
+    Ok(image)
+}
+
+pub fn dds_format_to_texture_format(
+    dds: &Dds,
+    is_srgb: bool,
+) -> Result<TextureFormat, TextureError> {
+    let srgb = |srgb_format: TextureFormat, linear_format: TextureFormat| {
+        if is_srgb {
+            srgb_format
+        } else {
+            linear_format
+        }
+    };
+
+    Ok(if let Some(d3d_format) = dds.get_d3d_format() {
+        match d3d_format {
+            D3DFormat::A8B8G8R8 => srgb(TextureFormat::Rgba8UnormSrgb, TextureFormat::Rgba8Unorm),
+            D3DFormat::A8R8G8B8 => srgb(TextureFormat::Bgra8UnormSrgb, TextureFormat::Bgra8Unorm),
+            D3DFormat::G16R16 => TextureFormat::Rg16Uint,
+            D3DFormat::A2B10G10R10 => TextureFormat::Rgb10a2Unorm,
+            D3DFormat::A8L8 => TextureFormat::Rg8Uint,
+            D3DFormat::L16 => TextureFormat::R16Uint,
+            D3DFormat::L8 => TextureFormat::R8Uint,
+            D3DFormat::DXT1 => srgb(TextureFormat::Bc1RgbaUnormSrgb, TextureFormat::Bc1RgbaUnorm),
+            D3DFormat::DXT3 | D3DFormat::DXT2 => {
+                srgb(TextureFormat::Bc2RgbaUnormSrgb, TextureFormat::Bc2RgbaUnorm)
+            }
+            D3DFormat::DXT5 | D3DFormat::DXT4 => {
+                srgb(TextureFormat::Bc3RgbaUnormSrgb, TextureFormat::Bc3RgbaUnorm)
+            }
+            D3DFormat::A16B16G16R16 => TextureFormat::Rgba16Unorm,
+            D3DFormat::Q16W16V16U16 => TextureFormat::Rgba16Sint,
+            D3DFormat::R16F => TextureFormat::R16Float,
+            D3DFormat::G16R16F => TextureFormat::Rg16Float,
+            D3DFormat::A16B16G16R16F => TextureFormat::Rgba16Float,
+            D3DFormat::R32F => TextureFormat::R32Float,
+            D3DFormat::G32R32F => TextureFormat::Rg32Float,
+            D3DFormat::A32B32G32R32F => TextureFormat::Rgba32Float,
+            D3DFormat::R8G8B8 | D3DFormat::X8R8G8B8 | D3DFormat::X8B8G8R8 => {
+                return Err(TextureError::FormatRequiresTranscodingError(
+                    TranscodeFormat::Rgb8,
+                ));
+            }
+            format => {
+                return Err(TextureError::UnsupportedTextureFormat(format!(
+                    "{format:?}"
+                )));
+            }
+        }
+    } else if let Some(dxgi_format) = dds.get_dxgi_format() {
+        match dxgi_format {
+            DxgiFormat::R32G32B32A32_Typeless | DxgiFormat::R32G32B32A32_Float => {
+                TextureFormat::Rgba32Float
+            }
+            DxgiFormat::R32G32B32A32_UInt => TextureFormat::Rgba32Uint,
+            DxgiFormat::R32G32B32A32_SInt => TextureFormat::Rgba32Sint,
+            DxgiFormat::R16G16B16A16_Typeless | DxgiFormat::R16G16B16A16_Float => {
+                TextureFormat::Rgba16Float
+            }
+            DxgiFormat::R16G16B16A16_UNorm => TextureFormat::Rgba16Unorm,
+            DxgiFormat::R16G16B16A16_UInt => TextureFormat::Rgba16Uint,
+            DxgiFormat::R16G16B16A16_SNorm => TextureFormat::Rgba16Snorm,
+            DxgiFormat::R16G16B16A16_SInt => TextureFormat::Rgba16Sint,
+            DxgiFormat::R32G32_Typeless | DxgiFormat::R32G32_Float => TextureFormat::Rg32Float,
+            DxgiFormat::R32G32_UInt => TextureFormat::Rg32Uint,
+            DxgiFormat::R32G32_SInt => TextureFormat::Rg32Sint,
+            DxgiFormat::R10G10B10A2_Typeless | DxgiFormat::R10G10B10A2_UNorm => {
+                TextureFormat::Rgb10a2Unorm
+            }
+            DxgiFormat::R11G11B10_Float => TextureFormat::Rg11b10Float,
+            DxgiFormat::R8G8B8A8_Typeless | DxgiFormat::R8G8B8A8_UNorm => {
+                srgb(TextureFormat::Rgba8UnormSrgb, TextureFormat::Rgba8Unorm)
+            }
+            DxgiFormat::R8G8B8A8_UNorm_sRGB => TextureFormat::Rgba8UnormSrgb,
+            DxgiFormat::R8G8B8A8_UInt => TextureFormat::Rgba8Uint,
+            DxgiFormat::R8G8B8A8_SNorm => TextureFormat::Rgba8Snorm,
+            DxgiFormat::R8G8B8A8_SInt => TextureFormat::Rgba8Sint,
+            DxgiFormat::R16G16_Typeless | DxgiFormat::R16G16_Float => TextureFormat::Rg16Float,
+            DxgiFormat::R16G16_UNorm => TextureFormat::Rg16Unorm,
+            DxgiFormat::R16G16_UInt => TextureFormat::Rg16Uint,
+            DxgiFormat::R16G16_SNorm => TextureFormat::Rg16Snorm,
+            DxgiFormat::R16G16_SInt => TextureFormat::Rg16Sint,
+            DxgiFormat::R32_Typeless | DxgiFormat::R32_Float => TextureFormat::R32Float,
+            DxgiFormat::D32_Float => TextureFormat::Depth32Float,
+            DxgiFormat::R32_UInt => TextureFormat::R32Uint,
+            DxgiFormat::R32_SInt => TextureFormat::R32Sint,
+            DxgiFormat::D24_UNorm_S8_UInt => TextureFormat::Depth24PlusStencil8,
+            DxgiFormat::R8G8_Typeless | DxgiFormat::R8G8_UNorm => TextureFormat::Rg8Unorm,
+            DxgiFormat::R8G8_UInt => TextureFormat::Rg8Uint,
+            DxgiFormat::R8G8_SNorm => TextureFormat::Rg8Snorm,
+            DxgiFormat::R8G8_SInt => TextureFormat::Rg8Sint,
+            DxgiFormat::R16_Typeless | DxgiFormat::R16_Float => TextureFormat::R16Float,
+            DxgiFormat::R16_UNorm => TextureFormat::R16Unorm,
+            DxgiFormat::R16_UInt => TextureFormat::R16Uint,
+            DxgiFormat::R16_SNorm => TextureFormat::R16Snorm,
+            DxgiFormat::R16_SInt => TextureFormat::R16Sint,
+            DxgiFormat::D16_UNorm => TextureFormat::Depth16Unorm,
+            DxgiFormat::R8_Typeless | DxgiFormat::R8_UNorm => TextureFormat::R8Unorm,
+            DxgiFormat::R8_UInt => TextureFormat::R8Uint,
+            DxgiFormat::R8_SNorm => TextureFormat::R8Snorm,
+            DxgiFormat::R8_SInt => TextureFormat::R8Sint,
+            DxgiFormat::R9G9B9E5_SharedExp => TextureFormat::Rgb9e5Ufloat,
+            DxgiFormat::BC1_Typeless | DxgiFormat::BC1_UNorm => {
+                srgb(TextureFormat::Bc1RgbaUnormSrgb, TextureFormat::Bc1RgbaUnorm)
+            }
+            DxgiFormat::BC1_UNorm_sRGB => TextureFormat::Bc1RgbaUnormSrgb,
+            DxgiFormat::BC2_Typeless | DxgiFormat::BC2_UNorm => {
+                srgb(TextureFormat::Bc2RgbaUnormSrgb, TextureFormat::Bc2RgbaUnorm)
+            }
+            DxgiFormat::BC2_UNorm_sRGB => TextureFormat::Bc2RgbaUnormSrgb,
+            DxgiFormat::BC3_Typeless | DxgiFormat::BC3_UNorm => {
+                srgb(TextureFormat::Bc3RgbaUnormSrgb, TextureFormat::Bc3RgbaUnorm)
+            }
+            DxgiFormat::BC3_UNorm_sRGB => TextureFormat::Bc3RgbaUnormSrgb,
+            DxgiFormat::BC4_Typeless | DxgiFormat::BC4_UNorm => TextureFormat::Bc4RUnorm,
+            DxgiFormat::BC4_SNorm => TextureFormat::Bc4RSnorm,
+            DxgiFormat::BC5_Typeless | DxgiFormat::BC5_UNorm => TextureFormat::Bc5RgUnorm,
+            DxgiFormat::BC5_SNorm => TextureFormat::Bc5RgSnorm,
+            DxgiFormat::B8G8R8A8_UNorm | DxgiFormat::B8G8R8A8_Typeless => {
+                srgb(TextureFormat::Bgra8UnormSrgb, TextureFormat::Bgra8Unorm)
+            }
+            DxgiFormat::B8G8R8A8_UNorm_sRGB => TextureFormat::Bgra8UnormSrgb,
+            DxgiFormat::BC6H_Typeless | DxgiFormat::BC6H_UF16 => TextureFormat::Bc6hRgbUfloat,
+            DxgiFormat::BC6H_SF16 => TextureFormat::Bc6hRgbFloat,
+            DxgiFormat::BC7_Typeless | DxgiFormat::BC7_UNorm => {
+                srgb(TextureFormat::Bc7RgbaUnormSrgb, TextureFormat::Bc7RgbaUnorm)
+            }
+            DxgiFormat::BC7_UNorm_sRGB => TextureFormat::Bc7RgbaUnormSrgb,
+            format => {
+                return Err(TextureError::UnsupportedTextureFormat(format!(
+                    "{format:?}"
+                )));
+            }
+        }
+    } else {
+        return Err(TextureError::UnsupportedTextureFormat(
+            "unspecified DDS pixel format".to_string(),
+        ));
+    })
+}
@@ -0,0 +1,84 @@
+use wgpu::{
+    Extent3d, Texture, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+    TextureView,
+};
+
+use crate::renderer::RenderDevice;
+
+/// 深度纹理的格式，管线的 `depth_stencil` 需要使用相同的格式
+pub const DEPTH_FORMAT: TextureFormat = TextureFormat::Depth32Float;
+
+/// 和颜色附件大小一致的深度纹理
+pub struct DepthTexture {
+    pub texture: Texture,
+    pub view: TextureView,
+}
+
+impl DepthTexture {
+    /// 宽高为 0 时按照 1 创建，`sample_count` 需要和颜色附件一致
+    pub fn new(device: &RenderDevice, width: u32, height: u32, sample_count: u32) -> Self {
+        let texture = device.wgpu_device().create_texture(&TextureDescriptor {
+            label: Some("depth_texture"),
+            size: Extent3d {
+                width: width.max(1),
+                height: height.max(1),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count,
+            dimension: TextureDimension::D2,
+            format: DEPTH_FORMAT,
+            usage: TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&Default::default());
+
+        Self { texture, view }
+    }
+
+    pub fn size(&self) -> Extent3d {
+        self.texture.size()
+    }
+
+    /// 大小和颜色附件不一致时重新创建，返回是否重新创建
+    pub fn resize(&mut self, device: &RenderDevice, width: u32, height: u32) -> bool {
+        let size = self.size();
+        if size.width == width.max(1) && size.height == height.max(1) {
+            return false;
+        }
+
+        *self = DepthTexture::new(device, width, height, self.texture.sample_count());
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_utils::headless_renderer;
+
+    #[test]
+    fn test_depth_texture_resize() {
+        let Some(renderer) = headless_renderer() else {
+            return;
+        };
+        let device = renderer.device;
+
+        let mut depth_texture = DepthTexture::new(&device, 4, 3, 1);
+        assert_eq!(depth_texture.texture.format(), DEPTH_FORMAT);
+        assert!(!depth_texture.resize(&device, 4, 3));
+
+        assert!(depth_texture.resize(&device, 8, 6));
+        assert_eq!(
+            (depth_texture.size().width, depth_texture.size().height),
+            (8, 6)
+        );
+
+        // 最小化的窗口大小为 0
+        assert!(depth_texture.resize(&device, 0, 0));
+        assert_eq!(
+            (depth_texture.size().width, depth_texture.size().height),
+            (1, 1)
+        );
+    }
+}
@@ -0,0 +1,105 @@
+use mini_resource::prelude::{Resource, ResourceManager};
+use wgpu::{Extent3d, TextureDimension, TextureFormat};
+
+use super::image::Image;
+
+/// 内置的缺失纹理的路径
+pub const FALLBACK_TEXTURE_PATH: &str = "mini_renderer/fallback_texture.png";
+
+const FALLBACK_TEXTURE_SIZE: u32 = 16;
+const FALLBACK_TEXTURE_CELL_SIZE: u32 = 8;
+
+const MAGENTA: [u8; 4] = [255, 0, 255, 255];
+const BLACK: [u8; 4] = [0, 0, 0, 255];
+
+impl Image {
+    /// 品红和黑色相间的棋盘格，用于标记缺失的纹理
+    pub fn fallback_checkerboard() -> Self {
+        let mut data =
+            Vec::with_capacity((FALLBACK_TEXTURE_SIZE * FALLBACK_TEXTURE_SIZE * 4) as usize);
+        for y in 0..FALLBACK_TEXTURE_SIZE {
+            for x in 0..FALLBACK_TEXTURE_SIZE {
+                let cell = x / FALLBACK_TEXTURE_CELL_SIZE + y / FALLBACK_TEXTURE_CELL_SIZE;
+                if cell.is_multiple_of(2) {
+                    data.extend_from_slice(&MAGENTA);
+                } else {
+                    data.extend_from_slice(&BLACK);
+                }
+            }
+        }
+
+        Image::new(
+            Extent3d {
+                width: FALLBACK_TEXTURE_SIZE,
+                height: FALLBACK_TEXTURE_SIZE,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8UnormSrgb,
+        )
+    }
+}
+
+/// 纹理加载失败时用于替代的纹理
+#[derive(Clone)]
+pub struct FallbackTexture(pub Resource<Image>);
+
+impl FallbackTexture {
+    /// 将棋盘格注册为内置资源
+    pub fn register(resource_manager: &ResourceManager) -> Self {
+        Self(resource_manager.add_built_in(FALLBACK_TEXTURE_PATH, Image::fallback_checkerboard()))
+    }
+
+    /// 纹理加载失败时返回棋盘格，其他情况返回原纹理
+    pub fn resolve(&self, texture: &Resource<Image>) -> Resource<Image> {
+        if texture.is_load_error() {
+            self.0.clone()
+        } else {
+            texture.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use mini_core::futures_lite::future::block_on;
+    use mini_resource::prelude::{
+        Dir, MemoryAssetReader, ResourceSourceBuilder, ResourceSourceBuilders, ResourceSourceId,
+    };
+    use mini_task::TaskPool;
+
+    use super::*;
+    use crate::texture::prelude::ImageLoader;
+
+    #[test]
+    fn test_missing_texture_resolves_to_fallback() {
+        let reader = MemoryAssetReader { root: Dir::new() };
+        let mut builders = ResourceSourceBuilders::default();
+        builders.insert(
+            ResourceSourceId::Default,
+            ResourceSourceBuilder::default().with_reader(move || Box::new(reader.clone())),
+        );
+        let resource_manager = ResourceManager::with_sources(Arc::new(TaskPool::new()), builders);
+        resource_manager.add_loader(ImageLoader::default());
+        let fallback = FallbackTexture::register(&resource_manager);
+
+        let texture = block_on(resource_manager.load_async::<Image>("textures/missing.png"));
+        assert!(texture.is_load_error());
+
+        let resolved = fallback.resolve(&texture);
+        assert!(Arc::ptr_eq(&resolved.untyped.0, &fallback.0.untyped.0));
+
+        let data = resolved.data_ref();
+        let image = data.as_loaded_ref().unwrap();
+        assert_eq!(&image.data[..4], &MAGENTA);
+        let offset = (FALLBACK_TEXTURE_CELL_SIZE * 4) as usize;
+        assert_eq!(&image.data[offset..offset + 4], &BLACK);
+
+        // 内置资源可以通过路径取到
+        let built_in = block_on(resource_manager.load_async::<Image>(FALLBACK_TEXTURE_PATH));
+        assert!(Arc::ptr_eq(&built_in.untyped.0, &fallback.0.untyped.0));
+    }
+}
@@ -0,0 +1,45 @@
+use std::sync::Arc;
+
+use wgpu::{Extent3d, Sampler, Texture, TextureView};
+
+use super::image::Image;
+use crate::{
+    render_resource::SamplerCache,
+    renderer::{RenderDevice, RenderQueue},
+};
+
+/// 上传到 GPU 的图片
+pub struct GpuImage {
+    pub texture: Texture,
+    pub texture_view: TextureView,
+    //相同描述符的图片共享同一个采样器
+    pub sampler: Arc<Sampler>,
+    pub size: Extent3d,
+    pub mip_level_count: u32,
+}
+
+impl GpuImage {
+    pub fn new(
+        device: &RenderDevice,
+        queue: &RenderQueue,
+        sampler_cache: &mut SamplerCache,
+        image: &Image,
+    ) -> Self {
+        let texture = device.create_texture_with_image(queue, image);
+        let texture_view = texture.create_view(
+            image
+                .texture_view_descriptor
+                .as_ref()
+                .unwrap_or(&wgpu::TextureViewDescriptor::default()),
+        );
+        let sampler = sampler_cache.get_or_create_image_sampler(device, &image.sampler);
+
+        Self {
+            texture,
+            texture_view,
+            sampler,
+            size: image.texture_descriptor.size,
+            mip_level_count: image.texture_descriptor.mip_level_count,
+        }
+    }
+}
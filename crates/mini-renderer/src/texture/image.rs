@@ -1,3 +1,5 @@
+use std::hash::{Hash, Hasher};
+
 use mini_core::{
     bitflags, bytemuck,
     prelude::TypeUuidProvider,
@@ -5,14 +7,17 @@ use mini_core::{
 };
 use mini_resource::prelude::ResourceData;
 
-use super::prelude::TextureError;
-use crate::renderer::prelude::MiniDefault;
+use super::{
+    color_space::ColorSpace, dds::dds_buffer_to_image, ktx2::ktx2_buffer_to_image,
+    prelude::TextureError,
+};
+use crate::wrapper::MiniDefault;
 
-use image::DynamicImage;
+use image::{imageops::FilterType, DynamicImage};
 use wgpu::{Extent3d, TextureDimension, TextureFormat};
 
 ///图片资源
-#[derive(TypeUuidProvider, ResourceData, Debug)]
+#[derive(TypeUuidProvider, Debug)]
 #[type_uuid(id = "5fb10a22-4ea9-4a13-a58c-82f2734aefd8")]
 pub struct Image {
     //数据
@@ -26,6 +31,12 @@ pub struct Image {
     pub texture_view_descriptor: Option<wgpu::TextureViewDescriptor<'static>>,
 }
 
+impl ResourceData for Image {
+    fn approx_memory_size(&self) -> usize {
+        self.data.len()
+    }
+}
+
 impl Default for Image {
     /// default is a 1x1x1 all '1.0' texture
     fn default() -> Self {
@@ -66,8 +77,8 @@ impl Image {
         format: TextureFormat,
     ) -> Self {
         debug_assert_eq!(
-            size.volume() * format.pixel_size(),
-            data.len(),
+            size.data_size(format),
+            Some(data.len()),
             "Pixel data, size and format have to match",
         );
         let mut image = Self {
@@ -88,17 +99,14 @@ impl Image {
 
         let data: Vec<u8>;
         let format: TextureFormat;
+        let color_space = ColorSpace::from_is_srgb(is_srgb);
 
         match dyn_img {
             DynamicImage::ImageLuma8(image) => {
                 let i = DynamicImage::ImageLuma8(image).into_rgba8();
                 width = i.width();
                 height = i.height();
-                format = if is_srgb {
-                    TextureFormat::Rgba8UnormSrgb
-                } else {
-                    TextureFormat::Rgba8Unorm
-                };
+                format = color_space.apply(TextureFormat::Rgba8Unorm);
 
                 data = i.into_raw();
             }
@@ -106,11 +114,7 @@ impl Image {
                 let i = DynamicImage::ImageLumaA8(image).into_rgba8();
                 width = i.width();
                 height = i.height();
-                format = if is_srgb {
-                    TextureFormat::Rgba8UnormSrgb
-                } else {
-                    TextureFormat::Rgba8Unorm
-                };
+                format = color_space.apply(TextureFormat::Rgba8Unorm);
 
                 data = i.into_raw();
             }
@@ -118,22 +122,14 @@ impl Image {
                 let i = DynamicImage::ImageRgb8(image).into_rgba8();
                 width = i.width();
                 height = i.height();
-                format = if is_srgb {
-                    TextureFormat::Rgba8UnormSrgb
-                } else {
-                    TextureFormat::Rgba8Unorm
-                };
+                format = color_space.apply(TextureFormat::Rgba8Unorm);
 
                 data = i.into_raw();
             }
             DynamicImage::ImageRgba8(image) => {
                 width = image.width();
                 height = image.height();
-                format = if is_srgb {
-                    TextureFormat::Rgba8UnormSrgb
-                } else {
-                    TextureFormat::Rgba8Unorm
-                };
+                format = color_space.apply(TextureFormat::Rgba8Unorm);
 
                 data = image.into_raw();
             }
@@ -212,7 +208,7 @@ impl Image {
                 let image = dyn_img.into_rgba8();
                 width = image.width();
                 height = image.height();
-                format = TextureFormat::Rgba8UnormSrgb;
+                format = color_space.apply(TextureFormat::Rgba8Unorm);
 
                 data = image.into_raw();
             }
@@ -233,7 +229,7 @@ impl Image {
     pub fn from_buffer(
         buffer: &[u8],
         image_type: ImageType,
-        #[allow(unused_variables)] supported_compressed_formats: CompressedImageFormats,
+        supported_compressed_formats: CompressedImageFormats,
         is_srgb: bool,
         image_sampler: ImageSampler,
     ) -> Result<Image, TextureError> {
@@ -246,6 +242,10 @@ impl Image {
         // cases.
 
         let mut image = match format {
+            ImageFormat::Dds => dds_buffer_to_image(buffer, supported_compressed_formats, is_srgb)?,
+            ImageFormat::Ktx2 => {
+                ktx2_buffer_to_image(buffer, supported_compressed_formats, is_srgb)?
+            }
             _ => {
                 let image_crate_format = format
                     .as_image_crate_format()
@@ -260,24 +260,392 @@ impl Image {
         image.sampler = image_sampler;
         Ok(image)
     }
+
+    /// 图片的宽高（忽略深度和层数）。
+    /// 由纹理格式决定的颜色空间
+    pub fn color_space(&self) -> ColorSpace {
+        ColorSpace::from_format(self.texture_descriptor.format)
+    }
+
+    pub fn size_2d(&self) -> (u32, u32) {
+        let size = self.texture_descriptor.size;
+        (size.width, size.height)
+    }
+
+    /// 所有 layer 和 mip 的数据一共需要的字节数，压缩格式按块计算。
+    ///
+    /// 格式没有固定的块大小或者大小溢出时返回 `None`。
+    pub fn expected_data_size(&self) -> Option<usize> {
+        let descriptor = &self.texture_descriptor;
+        (0..descriptor.mip_level_count).try_fold(0usize, |total, level| {
+            let size = descriptor.mip_level_size(level)?;
+            total.checked_add(size.data_size(descriptor.format)?)
+        })
+    }
+
+    /// 只有单层、单 mip、非压缩的 2D 图片才能在 CPU 上处理。
+    fn is_plain_2d(&self) -> bool {
+        let descriptor = &self.texture_descriptor;
+        descriptor.dimension == TextureDimension::D2
+            && descriptor.size.depth_or_array_layers == 1
+            && descriptor.mip_level_count == 1
+            && descriptor.format.block_dimensions() == (1, 1)
+            && self.data.len() == descriptor.size.volume() * descriptor.format.pixel_size()
+    }
+
+    /// Converts this [`Image`] back into a [`DynamicImage`], if the format is supported.
+    pub fn try_into_dynamic(&self) -> Option<DynamicImage> {
+        if !self.is_plain_2d() {
+            return None;
+        }
+
+        let (width, height) = self.size_2d();
+        let data = self.data.clone();
+        match self.texture_descriptor.format {
+            TextureFormat::R8Unorm => {
+                image::GrayImage::from_raw(width, height, data).map(DynamicImage::ImageLuma8)
+            }
+            TextureFormat::Rg8Unorm => {
+                image::GrayAlphaImage::from_raw(width, height, data).map(DynamicImage::ImageLumaA8)
+            }
+            TextureFormat::Rgba8Unorm | TextureFormat::Rgba8UnormSrgb => {
+                image::RgbaImage::from_raw(width, height, data).map(DynamicImage::ImageRgba8)
+            }
+            TextureFormat::Bgra8Unorm | TextureFormat::Bgra8UnormSrgb => {
+                image::RgbaImage::from_raw(width, height, swap_red_blue(data))
+                    .map(DynamicImage::ImageRgba8)
+            }
+            TextureFormat::R16Uint | TextureFormat::R16Unorm => {
+                image::ImageBuffer::from_raw(width, height, bytemuck::pod_collect_to_vec(&data))
+                    .map(DynamicImage::ImageLuma16)
+            }
+            TextureFormat::Rg16Uint | TextureFormat::Rg16Unorm => {
+                image::ImageBuffer::from_raw(width, height, bytemuck::pod_collect_to_vec(&data))
+                    .map(DynamicImage::ImageLumaA16)
+            }
+            TextureFormat::Rgba16Unorm => {
+                image::ImageBuffer::from_raw(width, height, bytemuck::pod_collect_to_vec(&data))
+                    .map(DynamicImage::ImageRgba16)
+            }
+            TextureFormat::Rgba32Float => {
+                image::ImageBuffer::from_raw(width, height, bytemuck::pod_collect_to_vec(&data))
+                    .map(DynamicImage::ImageRgba32F)
+            }
+            _ => None,
+        }
+    }
+
+    /// Resizes the image on the CPU using [`FilterType::Triangle`].
+    pub fn resize(&mut self, size: Extent3d) -> Result<(), TextureError> {
+        self.resize_with_filter(size, FilterType::Triangle)
+    }
+
+    /// Resizes the image on the CPU, keeping its [`TextureFormat`].
+    ///
+    /// Only the width and height of `size` are used; the image has to stay a single 2D layer.
+    pub fn resize_with_filter(
+        &mut self,
+        size: Extent3d,
+        filter: FilterType,
+    ) -> Result<(), TextureError> {
+        let format = self.texture_descriptor.format;
+        let unsupported = || TextureError::UnsupportedTextureFormat(format!("{format:?}"));
+
+        if size.depth_or_array_layers != 1 || size.width == 0 || size.height == 0 {
+            return Err(TextureError::InvalidData(format!(
+                "can not resize an image to {size:?}"
+            )));
+        }
+
+        let dyn_img = self.try_into_dynamic().ok_or_else(unsupported)?;
+        let resized = dyn_img.resize_exact(size.width, size.height, filter);
+        let data = dynamic_to_data(resized, format).ok_or_else(unsupported)?;
+
+        debug_assert_eq!(data.len(), size.volume() * format.pixel_size());
+        self.data = data;
+        self.texture_descriptor.size = size;
+        Ok(())
+    }
+
+    /// Converts the image to `new_format`, returning `None` if the conversion is not supported.
+    ///
+    /// Conversions between the sRGB and linear variants of a format only relabel the data.
+    pub fn convert(&self, new_format: TextureFormat) -> Option<Image> {
+        let format = self.texture_descriptor.format;
+        if format == new_format {
+            return Some(Image {
+                data: self.data.clone(),
+                texture_descriptor: self.texture_descriptor.clone(),
+                sampler: self.sampler.clone(),
+                texture_view_descriptor: self.texture_view_descriptor.clone(),
+            });
+        }
+
+        let data = if format.remove_srgb_suffix() == new_format.remove_srgb_suffix() {
+            if !self.is_plain_2d() {
+                return None;
+            }
+            self.data.clone()
+        } else {
+            dynamic_to_data(self.try_into_dynamic()?, new_format)?
+        };
+
+        let size = self.texture_descriptor.size;
+        if data.len() != size.volume() * new_format.pixel_size() {
+            return None;
+        }
+
+        let mut image = Image::new(size, TextureDimension::D2, data, new_format);
+        image.sampler = self.sampler.clone();
+        Some(image)
+    }
+}
+
+impl Image {
+    /// Generates the full mip chain on the CPU with a 2x2 box filter and appends every level to
+    /// [`Image::data`].
+    ///
+    /// Only uncompressed, single layer 2D images without existing mip levels are supported.
+    /// sRGB data is averaged as is, without converting to linear first.
+    pub fn generate_mipmaps(&mut self) -> Result<(), TextureError> {
+        let descriptor = &self.texture_descriptor;
+        let format = descriptor.format;
+
+        if descriptor.format.block_dimensions() != (1, 1) {
+            return Err(TextureError::MipmapGenerationError(format!(
+                "compressed format {format:?} is not supported"
+            )));
+        }
+        if descriptor.dimension != TextureDimension::D2
+            || descriptor.size.depth_or_array_layers != 1
+        {
+            return Err(TextureError::MipmapGenerationError(format!(
+                "only single layer 2D images are supported, got {:?} with {} layers",
+                descriptor.dimension, descriptor.size.depth_or_array_layers
+            )));
+        }
+        if descriptor.mip_level_count != 1 {
+            return Err(TextureError::MipmapGenerationError(format!(
+                "the image already has {} mip levels",
+                descriptor.mip_level_count
+            )));
+        }
+
+        let channel = MipChannel::from_format(format).ok_or_else(|| {
+            TextureError::MipmapGenerationError(format!("format {format:?} is not supported"))
+        })?;
+        let pixel_size = format.pixel_size();
+        let (mut width, mut height) = self.size_2d();
+        if self.data.len() != width as usize * height as usize * pixel_size {
+            return Err(TextureError::InvalidData(
+                "pixel data does not match the image size".to_string(),
+            ));
+        }
+
+        let mut level_start = 0;
+        let mut mip_level_count = 1;
+        while width > 1 || height > 1 {
+            let next_width = (width / 2).max(1);
+            let next_height = (height / 2).max(1);
+
+            let level_end = self.data.len();
+            let next = box_filter(
+                &self.data[level_start..level_end],
+                (width as usize, height as usize),
+                (next_width as usize, next_height as usize),
+                pixel_size,
+                channel,
+            );
+            self.data.extend_from_slice(&next);
+
+            level_start = level_end;
+            width = next_width;
+            height = next_height;
+            mip_level_count += 1;
+        }
+
+        self.texture_descriptor.mip_level_count = mip_level_count;
+        Ok(())
+    }
+}
+
+/// 生成 mipmap 时每个通道的类型。
+#[derive(Clone, Copy)]
+enum MipChannel {
+    U8,
+    U16,
+    F32,
+}
+
+impl MipChannel {
+    fn from_format(format: TextureFormat) -> Option<Self> {
+        match format {
+            TextureFormat::R8Unorm
+            | TextureFormat::Rg8Unorm
+            | TextureFormat::Rgba8Unorm
+            | TextureFormat::Rgba8UnormSrgb
+            | TextureFormat::Bgra8Unorm
+            | TextureFormat::Bgra8UnormSrgb => Some(MipChannel::U8),
+            TextureFormat::R16Uint
+            | TextureFormat::R16Unorm
+            | TextureFormat::Rg16Uint
+            | TextureFormat::Rg16Unorm
+            | TextureFormat::Rgba16Uint
+            | TextureFormat::Rgba16Unorm => Some(MipChannel::U16),
+            TextureFormat::R32Float | TextureFormat::Rg32Float | TextureFormat::Rgba32Float => {
+                Some(MipChannel::F32)
+            }
+            _ => None,
+        }
+    }
+
+    fn size(&self) -> usize {
+        match self {
+            MipChannel::U8 => 1,
+            MipChannel::U16 => 2,
+            MipChannel::F32 => 4,
+        }
+    }
+
+    fn read(&self, bytes: &[u8]) -> f32 {
+        match self {
+            MipChannel::U8 => bytes[0] as f32,
+            MipChannel::U16 => u16::from_ne_bytes([bytes[0], bytes[1]]) as f32,
+            MipChannel::F32 => f32::from_ne_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+        }
+    }
+
+    fn write(&self, value: f32, out: &mut Vec<u8>) {
+        match self {
+            MipChannel::U8 => out.push(value.round() as u8),
+            MipChannel::U16 => out.extend_from_slice(&(value.round() as u16).to_ne_bytes()),
+            MipChannel::F32 => out.extend_from_slice(&value.to_ne_bytes()),
+        }
+    }
+}
+
+/// 用 2x2 的盒式滤波将一层 mip 缩小到下一层，奇数尺寸时边缘像素被重复采样。
+fn box_filter(
+    src: &[u8],
+    (width, height): (usize, usize),
+    (next_width, next_height): (usize, usize),
+    pixel_size: usize,
+    channel: MipChannel,
+) -> Vec<u8> {
+    let mut out = Vec::with_capacity(next_width * next_height * pixel_size);
+    let channels = pixel_size / channel.size();
+    for y in 0..next_height {
+        for x in 0..next_width {
+            let xs = [(x * 2).min(width - 1), (x * 2 + 1).min(width - 1)];
+            let ys = [(y * 2).min(height - 1), (y * 2 + 1).min(height - 1)];
+            for c in 0..channels {
+                let mut sum = 0.0;
+                for sy in ys {
+                    for sx in xs {
+                        let offset = (sy * width + sx) * pixel_size + c * channel.size();
+                        sum += channel.read(&src[offset..]);
+                    }
+                }
+                channel.write(sum / 4.0, &mut out);
+            }
+        }
+    }
+    out
+}
+
+/// 将 [`DynamicImage`] 转换为 `format` 对应的字节数据。
+fn dynamic_to_data(dyn_img: DynamicImage, format: TextureFormat) -> Option<Vec<u8>> {
+    Some(match format {
+        TextureFormat::R8Unorm => dyn_img.into_luma8().into_raw(),
+        TextureFormat::Rg8Unorm => dyn_img.into_luma_alpha8().into_raw(),
+        TextureFormat::Rgba8Unorm | TextureFormat::Rgba8UnormSrgb => {
+            dyn_img.into_rgba8().into_raw()
+        }
+        TextureFormat::Bgra8Unorm | TextureFormat::Bgra8UnormSrgb => {
+            swap_red_blue(dyn_img.into_rgba8().into_raw())
+        }
+        TextureFormat::R16Uint | TextureFormat::R16Unorm => {
+            bytemuck::cast_slice(&dyn_img.into_luma16().into_raw()).to_owned()
+        }
+        TextureFormat::Rg16Uint | TextureFormat::Rg16Unorm => {
+            bytemuck::cast_slice(&dyn_img.into_luma_alpha16().into_raw()).to_owned()
+        }
+        TextureFormat::Rgba16Unorm => {
+            bytemuck::cast_slice(&dyn_img.into_rgba16().into_raw()).to_owned()
+        }
+        TextureFormat::Rgba32Float => {
+            bytemuck::cast_slice(&dyn_img.into_rgba32f().into_raw()).to_owned()
+        }
+        _ => return None,
+    })
+}
+
+fn swap_red_blue(mut data: Vec<u8>) -> Vec<u8> {
+    for pixel in data.chunks_exact_mut(4) {
+        pixel.swap(0, 2);
+    }
+    data
 }
 
 /// Used to calculate the volume of an item.
 pub trait Volume {
-    fn volume(&self) -> usize;
+    /// Panics if the volume does not fit in a `usize`, see [`Volume::try_volume`].
+    fn volume(&self) -> usize {
+        self.try_volume().expect("volume overflows usize")
+    }
+
+    /// Returns `None` if the volume does not fit in a `usize`.
+    fn try_volume(&self) -> Option<usize>;
 }
 
 impl Volume for Extent3d {
-    /// Calculates the volume of the [`Extent3d`].
-    fn volume(&self) -> usize {
-        (self.width * self.height * self.depth_or_array_layers) as usize
+    /// Calculates the volume of the [`Extent3d`] without wrapping around on 32-bit targets.
+    fn try_volume(&self) -> Option<usize> {
+        let volume = (self.width as u64)
+            .checked_mul(self.height as u64)?
+            .checked_mul(self.depth_or_array_layers as u64)?;
+        usize::try_from(volume).ok()
+    }
+}
+
+/// Extends the wgpu [`Extent3d`] with the size of its data in a given [`TextureFormat`].
+pub trait ExtentDataSize {
+    /// Returns the number of bytes of one mip level. Compressed formats round the width and
+    /// height up to whole blocks, so a 5x5 BC1 level is stored as 2x2 blocks.
+    ///
+    /// Returns `None` for formats without a single copy size, such as combined depth-stencil
+    /// formats, or if the size does not fit in a `usize`.
+    fn data_size(&self, format: TextureFormat) -> Option<usize>;
+}
+
+impl ExtentDataSize for Extent3d {
+    fn data_size(&self, format: TextureFormat) -> Option<usize> {
+        let (_, block_height) = format.block_dimensions();
+        let rows = Extent3d {
+            width: 1,
+            height: self.height.div_ceil(block_height),
+            depth_or_array_layers: self.depth_or_array_layers,
+        };
+        rows.try_volume()?
+            .checked_mul(format.row_bytes(self.width)?)
     }
 }
 
 /// Extends the wgpu [`TextureFormat`] with information about the pixel.
 pub trait TextureFormatPixelInfo {
     /// Returns the size of a pixel in bytes of the format.
+    ///
+    /// Panics for compressed formats, use [`TextureFormatPixelInfo::row_bytes`] instead.
     fn pixel_size(&self) -> usize;
+
+    /// Returns the size of a block in bytes, which is a single pixel for uncompressed formats.
+    ///
+    /// Returns `None` for formats without a single copy size, such as combined depth-stencil
+    /// formats.
+    fn block_size_bytes(&self) -> Option<usize>;
+
+    /// Returns the number of bytes of a row of blocks covering `width` pixels.
+    fn row_bytes(&self, width: u32) -> Option<usize>;
 }
 
 impl TextureFormatPixelInfo for TextureFormat {
@@ -288,6 +656,15 @@ impl TextureFormatPixelInfo for TextureFormat {
             _ => panic!("Using pixel_size for compressed textures is invalid"),
         }
     }
+
+    fn block_size_bytes(&self) -> Option<usize> {
+        self.block_copy_size(None).map(|size| size as usize)
+    }
+
+    fn row_bytes(&self, width: u32) -> Option<usize> {
+        let (block_width, _) = self.block_dimensions();
+        (width.div_ceil(block_width) as usize).checked_mul(self.block_size_bytes()?)
+    }
 }
 
 bitflags::bitflags! {
@@ -301,6 +678,55 @@ bitflags::bitflags! {
     }
 }
 
+impl CompressedImageFormats {
+    /// 根据 wgpu 的设备特性推导出可用的压缩格式。
+    pub fn from_features(features: wgpu::Features) -> Self {
+        let mut supported_compressed_formats = Self::default();
+        if features.contains(wgpu::Features::TEXTURE_COMPRESSION_ASTC) {
+            supported_compressed_formats |= Self::ASTC_LDR;
+        }
+        if features.contains(wgpu::Features::TEXTURE_COMPRESSION_BC) {
+            supported_compressed_formats |= Self::BC;
+        }
+        if features.contains(wgpu::Features::TEXTURE_COMPRESSION_ETC2) {
+            supported_compressed_formats |= Self::ETC2;
+        }
+        supported_compressed_formats
+    }
+
+    /// 非压缩格式总是支持的，压缩格式需要设置对应的标记。
+    pub fn supports(&self, format: TextureFormat) -> bool {
+        match format {
+            TextureFormat::Bc1RgbaUnorm
+            | TextureFormat::Bc1RgbaUnormSrgb
+            | TextureFormat::Bc2RgbaUnorm
+            | TextureFormat::Bc2RgbaUnormSrgb
+            | TextureFormat::Bc3RgbaUnorm
+            | TextureFormat::Bc3RgbaUnormSrgb
+            | TextureFormat::Bc4RUnorm
+            | TextureFormat::Bc4RSnorm
+            | TextureFormat::Bc5RgUnorm
+            | TextureFormat::Bc5RgSnorm
+            | TextureFormat::Bc6hRgbUfloat
+            | TextureFormat::Bc6hRgbFloat
+            | TextureFormat::Bc7RgbaUnorm
+            | TextureFormat::Bc7RgbaUnormSrgb => self.contains(CompressedImageFormats::BC),
+            TextureFormat::Etc2Rgb8Unorm
+            | TextureFormat::Etc2Rgb8UnormSrgb
+            | TextureFormat::Etc2Rgb8A1Unorm
+            | TextureFormat::Etc2Rgb8A1UnormSrgb
+            | TextureFormat::Etc2Rgba8Unorm
+            | TextureFormat::Etc2Rgba8UnormSrgb
+            | TextureFormat::EacR11Unorm
+            | TextureFormat::EacR11Snorm
+            | TextureFormat::EacRg11Unorm
+            | TextureFormat::EacRg11Snorm => self.contains(CompressedImageFormats::ETC2),
+            TextureFormat::Astc { .. } => self.contains(CompressedImageFormats::ASTC_LDR),
+            _ => true,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum ImageType<'a> {
     /// The mime type of an image, for example `"image/png"`.
@@ -410,6 +836,36 @@ impl ImageFormat {
         })
     }
 
+    /// 根据文件开头的魔数判断格式。
+    ///
+    /// 先检查 `image` 不能识别的压缩容器，其余格式交给 [`image::guess_format`]。
+    /// 识别出的格式没有可用的解码器时返回 [`TextureError::UnsupportedTextureFormat`]。
+    pub fn guess(bytes: &[u8]) -> Result<ImageFormat, TextureError> {
+        const KTX2_MAGIC: &[u8] = &[
+            0xAB, b'K', b'T', b'X', b' ', b'2', b'0', 0xBB, b'\r', b'\n', 0x1A, b'\n',
+        ];
+        const DDS_MAGIC: &[u8] = b"DDS ";
+        //Basis Universal 文件头的 `sig` 字段，小端的 0x4273
+        const BASIS_MAGIC: &[u8] = b"sB";
+
+        if bytes.starts_with(KTX2_MAGIC) {
+            return Ok(ImageFormat::Ktx2);
+        }
+        if bytes.starts_with(DDS_MAGIC) {
+            return Ok(ImageFormat::Dds);
+        }
+        if bytes.starts_with(BASIS_MAGIC) {
+            return Err(TextureError::UnsupportedTextureFormat(format!(
+                "Basis (header {:02x?})",
+                &bytes[..BASIS_MAGIC.len()]
+            )));
+        }
+
+        let format = image::guess_format(bytes)?;
+        ImageFormat::from_image_crate_format(format)
+            .ok_or_else(|| TextureError::UnsupportedTextureFormat(format!("{format:?}")))
+    }
+
     pub fn from_image_crate_format(format: image::ImageFormat) -> Option<ImageFormat> {
         Some(match format {
             image::ImageFormat::Avif => ImageFormat::Avif,
@@ -431,7 +887,7 @@ impl ImageFormat {
     }
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
 pub enum ImageSampler {
     #[default]
     Default,
@@ -465,6 +921,62 @@ pub struct ImageSamplerDescriptor {
     pub border_color: Option<ImageSamplerBorderColor>,
 }
 
+// 浮点数按位比较，这样描述符可以作为缓存的键
+impl PartialEq for ImageSamplerDescriptor {
+    fn eq(&self, other: &Self) -> bool {
+        self.label == other.label
+            && self.address_mode_u == other.address_mode_u
+            && self.address_mode_v == other.address_mode_v
+            && self.address_mode_w == other.address_mode_w
+            && self.mag_filter == other.mag_filter
+            && self.min_filter == other.min_filter
+            && self.mipmap_filter == other.mipmap_filter
+            && self.lod_min_clamp.to_bits() == other.lod_min_clamp.to_bits()
+            && self.lod_max_clamp.to_bits() == other.lod_max_clamp.to_bits()
+            && self.compare == other.compare
+            && self.anisotropy_clamp == other.anisotropy_clamp
+            && self.border_color == other.border_color
+    }
+}
+
+impl Eq for ImageSamplerDescriptor {}
+
+impl Hash for ImageSamplerDescriptor {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.label.hash(state);
+        self.address_mode_u.hash(state);
+        self.address_mode_v.hash(state);
+        self.address_mode_w.hash(state);
+        self.mag_filter.hash(state);
+        self.min_filter.hash(state);
+        self.mipmap_filter.hash(state);
+        self.lod_min_clamp.to_bits().hash(state);
+        self.lod_max_clamp.to_bits().hash(state);
+        self.compare.hash(state);
+        self.anisotropy_clamp.hash(state);
+        self.border_color.hash(state);
+    }
+}
+
+impl ImageSamplerDescriptor {
+    pub fn as_wgpu(&self) -> wgpu::SamplerDescriptor<'_> {
+        wgpu::SamplerDescriptor {
+            label: self.label.as_deref(),
+            address_mode_u: self.address_mode_u.clone().into(),
+            address_mode_v: self.address_mode_v.clone().into(),
+            address_mode_w: self.address_mode_w.clone().into(),
+            mag_filter: self.mag_filter.into(),
+            min_filter: self.min_filter.into(),
+            mipmap_filter: self.mipmap_filter.into(),
+            lod_min_clamp: self.lod_min_clamp,
+            lod_max_clamp: self.lod_max_clamp,
+            compare: self.compare.map(Into::into),
+            anisotropy_clamp: self.anisotropy_clamp,
+            border_color: self.border_color.map(Into::into),
+        }
+    }
+}
+
 impl Default for ImageSamplerDescriptor {
     fn default() -> Self {
         Self {
@@ -487,7 +999,7 @@ impl Default for ImageSamplerDescriptor {
 /// Comparison function used for depth and stencil operations.
 ///
 /// This type mirrors [`wgpu::CompareFunction`].
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum ImageCompareFunction {
     /// Function never passes
     Never,
@@ -514,7 +1026,7 @@ pub enum ImageCompareFunction {
 /// Color variation to use when the sampler addressing mode is [`ImageAddressMode::ClampToBorder`].
 ///
 /// This type mirrors [`wgpu::SamplerBorderColor`].
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum ImageSamplerBorderColor {
     /// RGBA color `[0, 0, 0, 0]`.
     TransparentBlack,
@@ -533,7 +1045,7 @@ pub enum ImageSamplerBorderColor {
 /// Texel mixing mode when sampling between texels.
 ///
 /// This type mirrors [`wgpu::FilterMode`].
-#[derive(Clone, Copy, Debug, Default)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 pub enum ImageFilterMode {
     /// Nearest neighbor sampling.
     ///
@@ -546,7 +1058,7 @@ pub enum ImageFilterMode {
     Linear,
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
 pub enum ImageAddressMode {
     /// Clamp the value to the edge of the texture.
     ///
@@ -571,3 +1083,355 @@ pub enum ImageAddressMode {
     /// 1.25 -> border
     ClampToBorder,
 }
+
+impl From<ImageAddressMode> for wgpu::AddressMode {
+    fn from(value: ImageAddressMode) -> Self {
+        match value {
+            ImageAddressMode::ClampToEdge => wgpu::AddressMode::ClampToEdge,
+            ImageAddressMode::Repeat => wgpu::AddressMode::Repeat,
+            ImageAddressMode::MirrorRepeat => wgpu::AddressMode::MirrorRepeat,
+            ImageAddressMode::ClampToBorder => wgpu::AddressMode::ClampToBorder,
+        }
+    }
+}
+
+impl From<ImageFilterMode> for wgpu::FilterMode {
+    fn from(value: ImageFilterMode) -> Self {
+        match value {
+            ImageFilterMode::Nearest => wgpu::FilterMode::Nearest,
+            ImageFilterMode::Linear => wgpu::FilterMode::Linear,
+        }
+    }
+}
+
+impl From<ImageCompareFunction> for wgpu::CompareFunction {
+    fn from(value: ImageCompareFunction) -> Self {
+        match value {
+            ImageCompareFunction::Never => wgpu::CompareFunction::Never,
+            ImageCompareFunction::Less => wgpu::CompareFunction::Less,
+            ImageCompareFunction::Equal => wgpu::CompareFunction::Equal,
+            ImageCompareFunction::LessEqual => wgpu::CompareFunction::LessEqual,
+            ImageCompareFunction::Greater => wgpu::CompareFunction::Greater,
+            ImageCompareFunction::NotEqual => wgpu::CompareFunction::NotEqual,
+            ImageCompareFunction::GreaterEqual => wgpu::CompareFunction::GreaterEqual,
+            ImageCompareFunction::Always => wgpu::CompareFunction::Always,
+        }
+    }
+}
+
+impl From<ImageSamplerBorderColor> for wgpu::SamplerBorderColor {
+    fn from(value: ImageSamplerBorderColor) -> Self {
+        match value {
+            ImageSamplerBorderColor::TransparentBlack => wgpu::SamplerBorderColor::TransparentBlack,
+            ImageSamplerBorderColor::OpaqueBlack => wgpu::SamplerBorderColor::OpaqueBlack,
+            ImageSamplerBorderColor::OpaqueWhite => wgpu::SamplerBorderColor::OpaqueWhite,
+            ImageSamplerBorderColor::Zero => wgpu::SamplerBorderColor::Zero,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_extent_volume() {
+        let size = Extent3d {
+            width: 4,
+            height: 4,
+            depth_or_array_layers: 2,
+        };
+        assert_eq!(size.volume(), 32);
+        assert_eq!(size.try_volume(), Some(32));
+
+        let huge = Extent3d {
+            width: u32::MAX,
+            height: u32::MAX,
+            depth_or_array_layers: u32::MAX,
+        };
+        assert_eq!(huge.try_volume(), None);
+    }
+
+    #[test]
+    fn test_extent_data_size() {
+        let size = Extent3d {
+            width: 10,
+            height: 10,
+            depth_or_array_layers: 1,
+        };
+        assert_eq!(size.data_size(TextureFormat::Rgba8Unorm), Some(400));
+        // 对齐到 4x4 的块，每块 8 字节
+        assert_eq!(size.data_size(TextureFormat::Bc1RgbaUnorm), Some(3 * 3 * 8));
+        assert_eq!(size.data_size(TextureFormat::Depth24PlusStencil8), None);
+    }
+
+    #[test]
+    fn test_compressed_format_sizes() {
+        assert_eq!(TextureFormat::Rgba8Unorm.block_size_bytes(), Some(4));
+        assert_eq!(TextureFormat::Bc1RgbaUnorm.block_size_bytes(), Some(8));
+        assert_eq!(TextureFormat::Bc7RgbaUnorm.block_size_bytes(), Some(16));
+
+        assert_eq!(TextureFormat::Rgba8Unorm.row_bytes(10), Some(40));
+        assert_eq!(TextureFormat::Bc1RgbaUnorm.row_bytes(10), Some(24));
+        assert_eq!(TextureFormat::Bc1RgbaUnorm.row_bytes(1), Some(8));
+
+        // 4x4 的 BC1 纹理有 3 级 mip，每级至少一个块
+        let mut image = Image::default();
+        image.texture_descriptor.size = Extent3d {
+            width: 4,
+            height: 4,
+            depth_or_array_layers: 1,
+        };
+        image.texture_descriptor.format = TextureFormat::Bc1RgbaUnorm;
+        image.texture_descriptor.mip_level_count = 3;
+        assert_eq!(image.expected_data_size(), Some(3 * 8));
+
+        image.texture_descriptor.format = TextureFormat::Rgba8Unorm;
+        assert_eq!(image.expected_data_size(), Some((16 + 4 + 1) * 4));
+    }
+
+    #[test]
+    fn test_guess_format() {
+        let ktx2 = [
+            0xAB, b'K', b'T', b'X', b' ', b'2', b'0', 0xBB, b'\r', b'\n', 0x1A, b'\n', 0, 0,
+        ];
+        assert!(matches!(ImageFormat::guess(&ktx2), Ok(ImageFormat::Ktx2)));
+        assert!(matches!(
+            ImageFormat::guess(b"DDS \x7c\0\0\0"),
+            Ok(ImageFormat::Dds)
+        ));
+        assert!(matches!(
+            ImageFormat::guess(b"\x89PNG\r\n\x1a\n"),
+            Ok(ImageFormat::Png)
+        ));
+
+        // 没有 Basis 的解码器
+        let Err(TextureError::UnsupportedTextureFormat(header)) =
+            ImageFormat::guess(b"sB\x13\0\x4d\0")
+        else {
+            panic!("basis files can not be decoded");
+        };
+        assert!(header.starts_with("Basis"));
+
+        assert!(ImageFormat::guess(b"unknown").is_err());
+    }
+
+    #[test]
+    fn test_color_space() {
+        let srgb = Image::from_dynamic(DynamicImage::new_rgba8(2, 2), true);
+        assert_eq!(srgb.color_space(), ColorSpace::Srgb);
+        let linear = Image::from_dynamic(DynamicImage::new_rgba8(2, 2), false);
+        assert_eq!(linear.texture_descriptor.format, TextureFormat::Rgba8Unorm);
+        assert_eq!(linear.color_space(), ColorSpace::Linear);
+
+        // 浮点格式没有 sRGB 变体
+        let float = Image::from_dynamic(DynamicImage::new_rgba32f(2, 2), true);
+        assert_eq!(float.color_space(), ColorSpace::Linear);
+        assert_eq!(
+            ColorSpace::Srgb.apply(TextureFormat::Rgba32Float),
+            TextureFormat::Rgba32Float
+        );
+        assert_eq!(
+            ColorSpace::Linear.apply(TextureFormat::Bgra8UnormSrgb),
+            TextureFormat::Bgra8Unorm
+        );
+    }
+
+    #[test]
+    fn test_from_dynamic_round_trip() {
+        let rgb =
+            image::RgbImage::from_fn(3, 2, |x, y| image::Rgb([x as u8 * 80, y as u8 * 120, 7]));
+        let expected = DynamicImage::ImageRgb8(rgb.clone()).into_rgba8();
+
+        for is_srgb in [true, false] {
+            let image = Image::from_dynamic(DynamicImage::ImageRgb8(rgb.clone()), is_srgb);
+            assert_eq!(image.color_space(), ColorSpace::from_is_srgb(is_srgb));
+            assert_eq!(image.size_2d(), (3, 2));
+
+            let round_trip = image.try_into_dynamic().unwrap().into_rgba8();
+            assert_eq!(round_trip, expected);
+        }
+    }
+
+    fn rgba8_image(width: u32, height: u32) -> Image {
+        let data = (0..width * height * 4).map(|value| value as u8).collect();
+        Image::new(
+            Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8UnormSrgb,
+        )
+    }
+
+    #[test]
+    fn test_generate_mipmaps() {
+        let mut image = rgba8_image(4, 4);
+        image.generate_mipmaps().unwrap();
+
+        assert_eq!(image.texture_descriptor.mip_level_count, 3);
+        assert_eq!(image.data.len(), 4 * 4 * 4 + 2 * 2 * 4 + 4);
+
+        // 1x1 的最后一层是整张图的平均值
+        let base = rgba8_image(4, 4).data;
+        let average = |c: usize| {
+            let sum: u32 = base.chunks_exact(4).map(|pixel| pixel[c] as u32).sum();
+            sum as f32 / 16.0
+        };
+        let last = &image.data[image.data.len() - 4..];
+        for (c, value) in last.iter().enumerate() {
+            assert!((*value as f32 - average(c)).abs() <= 1.0);
+        }
+
+        assert!(matches!(
+            image.generate_mipmaps(),
+            Err(TextureError::MipmapGenerationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_resize() {
+        let mut image = rgba8_image(4, 4);
+        let size = Extent3d {
+            width: 2,
+            height: 3,
+            depth_or_array_layers: 1,
+        };
+        image.resize(size).unwrap();
+
+        assert_eq!(image.texture_descriptor.size, size);
+        assert_eq!(image.data.len(), 2 * 3 * 4);
+        assert_eq!(
+            image.texture_descriptor.format,
+            TextureFormat::Rgba8UnormSrgb
+        );
+    }
+
+    #[test]
+    fn test_convert() {
+        let image = rgba8_image(2, 2);
+
+        let linear = image.convert(TextureFormat::Rgba8Unorm).unwrap();
+        assert_eq!(linear.texture_descriptor.format, TextureFormat::Rgba8Unorm);
+        assert_eq!(linear.data, image.data);
+
+        let wide = image.convert(TextureFormat::Rgba16Unorm).unwrap();
+        assert_eq!(wide.data.len(), 2 * 2 * 8);
+        let narrow = wide.convert(TextureFormat::Rgba8Unorm).unwrap();
+        assert_eq!(narrow.data, image.data);
+
+        assert!(image.convert(TextureFormat::Bc1RgbaUnorm).is_none());
+        assert!(image.convert(TextureFormat::Depth32Float).is_none());
+    }
+
+    fn ktx2_bytes(vk_format: u32, width: u32, height: u32, levels: &[Vec<u8>]) -> Vec<u8> {
+        const HEADER_LEN: usize = 80;
+        const LEVEL_INDEX_LEN: usize = 24;
+
+        let mut bytes = vec![
+            0xAB, 0x4B, 0x54, 0x58, 0x20, 0x32, 0x30, 0xBB, 0x0D, 0x0A, 0x1A, 0x0A,
+        ];
+        for value in [vk_format, 1, width, height, 0, 0, 1, levels.len() as u32, 0] {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        // dfd / kvd / sgd 都为空
+        bytes.extend_from_slice(&[0; 32]);
+
+        let mut offset = (HEADER_LEN + LEVEL_INDEX_LEN * levels.len()) as u64;
+        for level in levels {
+            let len = level.len() as u64;
+            for value in [offset, len, len] {
+                bytes.extend_from_slice(&value.to_le_bytes());
+            }
+            offset += len;
+        }
+        for level in levels {
+            bytes.extend_from_slice(level);
+        }
+        bytes
+    }
+
+    #[test]
+    fn test_ktx2_mip_levels() {
+        // VK_FORMAT_R8G8B8A8_UNORM
+        let bytes = ktx2_bytes(37, 2, 2, &[vec![1; 16], vec![2; 4]]);
+        let image = Image::from_buffer(
+            &bytes,
+            ImageType::Format(ImageFormat::Ktx2),
+            CompressedImageFormats::NONE,
+            false,
+            ImageSampler::Default,
+        )
+        .unwrap();
+
+        assert_eq!(image.texture_descriptor.format, TextureFormat::Rgba8Unorm);
+        assert_eq!(image.texture_descriptor.mip_level_count, 2);
+        assert_eq!(image.data.len(), 20);
+    }
+
+    #[test]
+    fn test_ktx2_compressed_requires_flag() {
+        // VK_FORMAT_BC1_RGBA_UNORM_BLOCK
+        let bytes = ktx2_bytes(133, 4, 4, &[vec![0; 8]]);
+        let load = |formats| {
+            Image::from_buffer(
+                &bytes,
+                ImageType::Extension("ktx2"),
+                formats,
+                false,
+                ImageSampler::Default,
+            )
+        };
+
+        assert!(matches!(
+            load(CompressedImageFormats::NONE),
+            Err(TextureError::UnsupportedTextureFormat(_))
+        ));
+        let image = load(CompressedImageFormats::BC).unwrap();
+        assert_eq!(image.texture_descriptor.format, TextureFormat::Bc1RgbaUnorm);
+    }
+
+    #[test]
+    fn test_dds() {
+        let dds = ddsfile::Dds::new_dxgi(ddsfile::NewDxgiParams {
+            height: 4,
+            width: 4,
+            depth: None,
+            format: ddsfile::DxgiFormat::BC7_UNorm_sRGB,
+            mipmap_levels: Some(3),
+            array_layers: None,
+            caps2: None,
+            is_cubemap: false,
+            resource_dimension: ddsfile::D3D10ResourceDimension::Texture2D,
+            alpha_mode: ddsfile::AlphaMode::Straight,
+        })
+        .unwrap();
+        let mut bytes = Vec::new();
+        dds.write(&mut bytes).unwrap();
+
+        let load = |formats| {
+            Image::from_buffer(
+                &bytes,
+                ImageType::Extension("dds"),
+                formats,
+                true,
+                ImageSampler::Default,
+            )
+        };
+
+        assert!(matches!(
+            load(CompressedImageFormats::ETC2),
+            Err(TextureError::UnsupportedTextureFormat(_))
+        ));
+        let image = load(CompressedImageFormats::BC).unwrap();
+        assert_eq!(
+            image.texture_descriptor.format,
+            TextureFormat::Bc7RgbaUnormSrgb
+        );
+        assert_eq!(image.texture_descriptor.mip_level_count, 3);
+        assert_eq!(image.data.len(), dds.data.len());
+    }
+}
@@ -1,8 +1,25 @@
 use super::prelude::{CompressedImageFormats, Image, ImageFormat, ImageSampler, ImageType};
 use mini_core::thiserror::{self, Error};
-use mini_resource::prelude::{LoadContext, Reader, ResourceError, ResourceLoader};
+use mini_resource::prelude::{
+    LoadContext, LoaderCapabilities, Reader, ResourceError, ResourceLoader,
+};
+use wgpu::TextureFormat;
 
-pub(crate) const IMG_FILE_EXTENSIONS: &[&str] = &["png"];
+//所有能够解码的扩展名，和 `ImageFormat::from_extension` 一致，`avif` 和 `basis` 没有解码器
+pub(crate) const IMG_FILE_EXTENSIONS: &[&str] = &[
+    "bmp", "dds", "ff", "farbfeld", "gif", "exr", "hdr", "ico", "jpg", "jpeg", "ktx2", "pbm",
+    "pam", "ppm", "pgm", "png", "tga", "tif", "tiff", "webp",
+];
+
+//从 `image` 解码的图片可能输出的格式，压缩格式由 `supported_compressed_formats` 决定
+const IMG_OUTPUT_FORMATS: &[TextureFormat] = &[
+    TextureFormat::Rgba8Unorm,
+    TextureFormat::Rgba8UnormSrgb,
+    TextureFormat::R16Uint,
+    TextureFormat::Rg16Uint,
+    TextureFormat::Rgba16Unorm,
+    TextureFormat::Rgba32Float,
+];
 
 /// Loader for images that can be read by the `image` crate.
 #[derive(Clone, Default)]
@@ -10,6 +27,15 @@ pub struct ImageLoader {
     supported_compressed_formats: CompressedImageFormats,
 }
 
+impl ImageLoader {
+    /// 创建一个加载器，只接受 `supported_compressed_formats` 中的压缩格式。
+    pub fn new(supported_compressed_formats: CompressedImageFormats) -> Self {
+        Self {
+            supported_compressed_formats,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct ImageLoaderSettings {
     pub format: ImageFormatSetting,
@@ -50,6 +76,8 @@ pub enum TextureError {
     /// Only cubemaps with six faces are supported.
     #[error("only cubemaps with six faces are supported")]
     IncompleteCubemap,
+    #[error("can not generate mipmaps: {0}")]
+    MipmapGenerationError(String),
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -104,22 +132,23 @@ impl ResourceLoader for ImageLoader {
         let image_type = match settings.format {
             ImageFormatSetting::FromExtension => {
                 // use the file extension for the image type
-                let ext = load_context.path().extension().unwrap().to_str().unwrap();
+                let ext =
+                    load_context
+                        .resource_path()
+                        .extension()
+                        .ok_or_else(|| FileTextureError {
+                            error: TextureError::InvalidImageExtension(String::new()),
+                            path: format!("{}", load_context.path().display()),
+                        })?;
                 ImageType::Extension(ext)
             }
             ImageFormatSetting::Format(format) => ImageType::Format(format),
-            ImageFormatSetting::Guess => {
-                let format = image::guess_format(&bytes).map_err(|err| FileTextureError {
-                    error: err.into(),
+            ImageFormatSetting::Guess => ImageType::Format(ImageFormat::guess(&bytes).map_err(
+                |error| FileTextureError {
+                    error,
                     path: format!("{}", load_context.path().display()),
-                })?;
-                ImageType::Format(ImageFormat::from_image_crate_format(format).ok_or_else(
-                    || FileTextureError {
-                        error: TextureError::UnsupportedTextureFormat(format!("{format:?}")),
-                        path: format!("{}", load_context.path().display()),
-                    },
-                )?)
-            }
+                },
+            )?),
         };
         Ok(Image::from_buffer(
             &bytes,
@@ -137,6 +166,17 @@ impl ResourceLoader for ImageLoader {
     fn extensions(&self) -> &[&str] {
         IMG_FILE_EXTENSIONS
     }
+
+    fn capabilities(&self) -> LoaderCapabilities {
+        // ktx2 和 dds 文件可以包含 mipmap
+        LoaderCapabilities::default()
+            .with_output_formats(
+                IMG_OUTPUT_FORMATS
+                    .iter()
+                    .map(|format| format!("{format:?}")),
+            )
+            .with_mipmaps(true)
+    }
 }
 
 #[derive(Default, Debug, Clone)]
@@ -146,3 +186,119 @@ pub enum ImageFormatSetting {
     Format(ImageFormat),
     Guess,
 }
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use mini_core::{futures_lite::future::block_on, type_uuid::TypeUuidProvider};
+    use mini_resource::prelude::{
+        Dir, MemoryAssetReader, ResourceManager, ResourceSourceBuilder, ResourceSourceBuilders,
+        ResourceSourceId,
+    };
+    use mini_task::TaskPool;
+
+    use super::*;
+
+    #[test]
+    fn test_image_loader_capabilities() {
+        let resource_manager = ResourceManager::new(Arc::new(TaskPool::new()));
+        assert!(resource_manager
+            .loader_capabilities(Image::type_uuid())
+            .is_none());
+
+        resource_manager.add_loader(ImageLoader::default());
+
+        let capabilities = resource_manager
+            .loader_capabilities(Image::type_uuid())
+            .unwrap();
+        assert!(capabilities.supports_output_format("Rgba8UnormSrgb"));
+        assert!(capabilities.supports_output_format("Rgba8Unorm"));
+        assert!(!capabilities.supports_output_format("Depth32Float"));
+        assert!(capabilities.mipmaps);
+    }
+
+    fn png_bytes(width: u32, height: u32) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        image::DynamicImage::new_rgba8(width, height)
+            .write_to(
+                &mut std::io::Cursor::new(&mut bytes),
+                image::ImageFormat::Png,
+            )
+            .unwrap();
+        bytes
+    }
+
+    fn memory_resource_manager(dir: Dir) -> ResourceManager {
+        let reader = MemoryAssetReader { root: dir };
+        let mut builders = ResourceSourceBuilders::default();
+        builders.insert(
+            ResourceSourceId::Default,
+            ResourceSourceBuilder::default().with_reader(move || Box::new(reader.clone())),
+        );
+        let resource_manager = ResourceManager::with_sources(Arc::new(TaskPool::new()), builders);
+        resource_manager.add_loader(ImageLoader::default());
+        resource_manager
+    }
+
+    #[test]
+    fn test_load_image_extensions() {
+        use image::{DynamicImage, ImageFormat as Format};
+
+        let rgb = DynamicImage::new_rgb8(2, 2);
+        let cases = [
+            ("bmp", Format::Bmp, rgb.clone()),
+            ("ff", Format::Farbfeld, rgb.to_rgba16().into()),
+            ("gif", Format::Gif, rgb.to_rgba8().into()),
+            ("exr", Format::OpenExr, rgb.to_rgba32f().into()),
+            ("hdr", Format::Hdr, rgb.to_rgb32f().into()),
+            ("ico", Format::Ico, rgb.to_rgba8().into()),
+            ("jpg", Format::Jpeg, rgb.clone()),
+            ("JPEG", Format::Jpeg, rgb.clone()),
+            ("ppm", Format::Pnm, rgb.clone()),
+            ("png", Format::Png, rgb.clone()),
+            ("tga", Format::Tga, rgb.clone()),
+            ("tiff", Format::Tiff, rgb.clone()),
+            ("webp", Format::WebP, rgb.to_rgba8().into()),
+        ];
+
+        let dir = Dir::new();
+        for (extension, format, image) in cases.iter() {
+            let mut bytes = Vec::new();
+            image
+                .write_to(&mut std::io::Cursor::new(&mut bytes), *format)
+                .unwrap();
+            dir.insert_asset(format!("image.{extension}"), bytes);
+        }
+        let resource_manager = memory_resource_manager(dir);
+
+        for (extension, ..) in cases.iter() {
+            let image =
+                block_on(resource_manager.load_async::<Image>(format!("image.{extension}")));
+            let image = image
+                .try_data_ref()
+                .unwrap_or_else(|err| panic!("failed to load .{extension}: {err}"));
+            assert_eq!(image.size_2d(), (2, 2), "{extension}");
+        }
+    }
+
+    #[test]
+    fn test_image_stats() {
+        let dir = Dir::new();
+        dir.insert_asset("a.png", png_bytes(2, 2));
+        dir.insert_asset("b.png", png_bytes(4, 4));
+        let resource_manager = memory_resource_manager(dir);
+
+        let _a = block_on(resource_manager.load_async::<Image>("a.png"));
+        let _b = block_on(resource_manager.load_async::<Image>("b.png"));
+
+        let stats = resource_manager.stats_by_type();
+        let image_stats = stats
+            .iter()
+            .find(|stats| stats.uuid == Image::type_uuid())
+            .unwrap();
+        assert_eq!(image_stats.name, std::any::type_name::<Image>());
+        assert_eq!(image_stats.count, 2);
+        assert_eq!(image_stats.approx_bytes, (2 * 2 + 4 * 4) * 4);
+    }
+}
@@ -0,0 +1,272 @@
+use ktx2::{Format, SupercompressionScheme};
+use wgpu::{
+    AstcBlock, AstcChannel, Extent3d, TextureDimension, TextureFormat, TextureViewDescriptor,
+    TextureViewDimension,
+};
+
+use super::prelude::{CompressedImageFormats, DataFormat, Image, TextureError, TranscodeFormat};
+
+/// 解析 KTX2 容器，返回按 layer 主序排列（每一层依次存放全部 mip）的图片数据。
+pub fn ktx2_buffer_to_image(
+    buffer: &[u8],
+    supported_compressed_formats: CompressedImageFormats,
+    is_srgb: bool,
+) -> Result<Image, TextureError> {
+    let ktx2 = ktx2::Reader::new(buffer)
+        .map_err(|err| TextureError::InvalidData(format!("Failed to parse ktx2 file: {err:?}")))?;
+    let header = ktx2.header();
+
+    if let Some(scheme) = header.supercompression_scheme {
+        return Err(match scheme {
+            SupercompressionScheme::BasisLZ => {
+                TextureError::FormatRequiresTranscodingError(TranscodeFormat::Etc1s)
+            }
+            _ => TextureError::SuperCompressionNotSupported(format!("{scheme:?}")),
+        });
+    }
+
+    let Some(ktx2_format) = header.format else {
+        // 没有 vkFormat 的 KTX2 是 Basis Universal (UASTC) 数据，需要转码
+        return Err(TextureError::FormatRequiresTranscodingError(
+            TranscodeFormat::Uastc(DataFormat::Rgba),
+        ));
+    };
+    let texture_format = ktx2_format_to_texture_format(ktx2_format, is_srgb)?;
+
+    if !supported_compressed_formats.supports(texture_format) {
+        return Err(TextureError::UnsupportedTextureFormat(format!(
+            "Format not supported by this GPU: {texture_format:?}"
+        )));
+    }
+
+    let face_count = header.face_count;
+    if face_count != 1 && face_count != 6 {
+        return Err(TextureError::IncompleteCubemap);
+    }
+    let layer_count = header.layer_count.max(1);
+    let level_count = header.level_count.max(1);
+    let slice_count = (layer_count * face_count) as usize;
+
+    // ktx2 按 mip 分组存储 (level -> layer -> face)，wgpu 需要 layer -> level 的顺序
+    let levels = ktx2.levels().collect::<Vec<_>>();
+    let mut data = Vec::with_capacity(levels.iter().map(|level| level.len()).sum());
+    for slice in 0..slice_count {
+        for level in levels.iter() {
+            if level.len() % slice_count != 0 {
+                return Err(TextureError::InvalidData(format!(
+                    "Mip level of {} bytes cannot be split into {slice_count} layers",
+                    level.len()
+                )));
+            }
+            let slice_len = level.len() / slice_count;
+            data.extend_from_slice(&level[slice * slice_len..(slice + 1) * slice_len]);
+        }
+    }
+
+    let dimension = if header.pixel_depth > 1 {
+        TextureDimension::D3
+    } else if header.pixel_height == 0 {
+        TextureDimension::D1
+    } else {
+        TextureDimension::D2
+    };
+
+    let mut image = Image {
+        data,
+        ..Default::default()
+    };
+    image.texture_descriptor.size = Extent3d {
+        width: header.pixel_width,
+        height: header.pixel_height.max(1),
+        depth_or_array_layers: if dimension == TextureDimension::D3 {
+            header.pixel_depth
+        } else {
+            slice_count as u32
+        },
+    }
+    .physical_size(texture_format);
+    image.texture_descriptor.dimension = dimension;
+    image.texture_descriptor.format = texture_format;
+    image.texture_descriptor.mip_level_count = level_count;
+
+    if face_count == 6 {
+        image.texture_view_descriptor = Some(TextureViewDescriptor {
+            dimension: Some(if layer_count > 1 {
+                TextureViewDimension::CubeArray
+            } else {
+                TextureViewDimension::Cube
+            }),
+            ..Default::default()
+        });
+    } else if layer_count > 1 {
+        image.texture_view_descriptor = Some(TextureViewDescriptor {
+            dimension: Some(TextureViewDimension::D2Array),
+            ..Default::default()
+        });
+    }
+
+    Ok(image)
+}
+
+pub fn ktx2_format_to_texture_format(
+    ktx2_format: Format,
+    is_srgb: bool,
+) -> Result<TextureFormat, TextureError> {
+    let srgb = |srgb_format: TextureFormat, linear_format: TextureFormat| {
+        if is_srgb {
+            srgb_format
+        } else {
+            linear_format
+        }
+    };
+
+    Ok(match ktx2_format {
+        Format::R8_UNORM | Format::R8_SRGB => TextureFormat::R8Unorm,
+        Format::R8_SNORM => TextureFormat::R8Snorm,
+        Format::R8_UINT => TextureFormat::R8Uint,
+        Format::R8_SINT => TextureFormat::R8Sint,
+        Format::R8G8_UNORM | Format::R8G8_SRGB => TextureFormat::Rg8Unorm,
+        Format::R8G8_SNORM => TextureFormat::Rg8Snorm,
+        Format::R8G8_UINT => TextureFormat::Rg8Uint,
+        Format::R8G8_SINT => TextureFormat::Rg8Sint,
+        Format::R8G8B8_UNORM | Format::R8G8B8_SRGB | Format::B8G8R8_UNORM | Format::B8G8R8_SRGB => {
+            return Err(TextureError::FormatRequiresTranscodingError(
+                TranscodeFormat::Rgb8,
+            ));
+        }
+        Format::R8G8B8A8_UNORM => srgb(TextureFormat::Rgba8UnormSrgb, TextureFormat::Rgba8Unorm),
+        Format::R8G8B8A8_SRGB => TextureFormat::Rgba8UnormSrgb,
+        Format::R8G8B8A8_SNORM => TextureFormat::Rgba8Snorm,
+        Format::R8G8B8A8_UINT => TextureFormat::Rgba8Uint,
+        Format::R8G8B8A8_SINT => TextureFormat::Rgba8Sint,
+        Format::B8G8R8A8_UNORM => srgb(TextureFormat::Bgra8UnormSrgb, TextureFormat::Bgra8Unorm),
+        Format::B8G8R8A8_SRGB => TextureFormat::Bgra8UnormSrgb,
+        Format::A2B10G10R10_UNORM_PACK32 => TextureFormat::Rgb10a2Unorm,
+        Format::A2B10G10R10_UINT_PACK32 => TextureFormat::Rgb10a2Uint,
+        Format::R16_UNORM => TextureFormat::R16Unorm,
+        Format::R16_SNORM => TextureFormat::R16Snorm,
+        Format::R16_UINT => TextureFormat::R16Uint,
+        Format::R16_SINT => TextureFormat::R16Sint,
+        Format::R16_SFLOAT => TextureFormat::R16Float,
+        Format::R16G16_UNORM => TextureFormat::Rg16Unorm,
+        Format::R16G16_SNORM => TextureFormat::Rg16Snorm,
+        Format::R16G16_UINT => TextureFormat::Rg16Uint,
+        Format::R16G16_SINT => TextureFormat::Rg16Sint,
+        Format::R16G16_SFLOAT => TextureFormat::Rg16Float,
+        Format::R16G16B16A16_UNORM => TextureFormat::Rgba16Unorm,
+        Format::R16G16B16A16_SNORM => TextureFormat::Rgba16Snorm,
+        Format::R16G16B16A16_UINT => TextureFormat::Rgba16Uint,
+        Format::R16G16B16A16_SINT => TextureFormat::Rgba16Sint,
+        Format::R16G16B16A16_SFLOAT => TextureFormat::Rgba16Float,
+        Format::R32_UINT => TextureFormat::R32Uint,
+        Format::R32_SINT => TextureFormat::R32Sint,
+        Format::R32_SFLOAT => TextureFormat::R32Float,
+        Format::R32G32_UINT => TextureFormat::Rg32Uint,
+        Format::R32G32_SINT => TextureFormat::Rg32Sint,
+        Format::R32G32_SFLOAT => TextureFormat::Rg32Float,
+        Format::R32G32B32A32_UINT => TextureFormat::Rgba32Uint,
+        Format::R32G32B32A32_SINT => TextureFormat::Rgba32Sint,
+        Format::R32G32B32A32_SFLOAT => TextureFormat::Rgba32Float,
+        Format::B10G11R11_UFLOAT_PACK32 => TextureFormat::Rg11b10Float,
+        Format::E5B9G9R9_UFLOAT_PACK32 => TextureFormat::Rgb9e5Ufloat,
+        Format::D16_UNORM => TextureFormat::Depth16Unorm,
+        Format::D32_SFLOAT => TextureFormat::Depth32Float,
+        Format::S8_UINT => TextureFormat::Stencil8,
+        Format::D24_UNORM_S8_UINT => TextureFormat::Depth24PlusStencil8,
+        Format::D32_SFLOAT_S8_UINT => TextureFormat::Depth32FloatStencil8,
+
+        Format::BC1_RGB_UNORM_BLOCK | Format::BC1_RGBA_UNORM_BLOCK => {
+            srgb(TextureFormat::Bc1RgbaUnormSrgb, TextureFormat::Bc1RgbaUnorm)
+        }
+        Format::BC1_RGB_SRGB_BLOCK | Format::BC1_RGBA_SRGB_BLOCK => TextureFormat::Bc1RgbaUnormSrgb,
+        Format::BC2_UNORM_BLOCK => {
+            srgb(TextureFormat::Bc2RgbaUnormSrgb, TextureFormat::Bc2RgbaUnorm)
+        }
+        Format::BC2_SRGB_BLOCK => TextureFormat::Bc2RgbaUnormSrgb,
+        Format::BC3_UNORM_BLOCK => {
+            srgb(TextureFormat::Bc3RgbaUnormSrgb, TextureFormat::Bc3RgbaUnorm)
+        }
+        Format::BC3_SRGB_BLOCK => TextureFormat::Bc3RgbaUnormSrgb,
+        Format::BC4_UNORM_BLOCK => TextureFormat::Bc4RUnorm,
+        Format::BC4_SNORM_BLOCK => TextureFormat::Bc4RSnorm,
+        Format::BC5_UNORM_BLOCK => TextureFormat::Bc5RgUnorm,
+        Format::BC5_SNORM_BLOCK => TextureFormat::Bc5RgSnorm,
+        Format::BC6H_UFLOAT_BLOCK => TextureFormat::Bc6hRgbUfloat,
+        Format::BC6H_SFLOAT_BLOCK => TextureFormat::Bc6hRgbFloat,
+        Format::BC7_UNORM_BLOCK => {
+            srgb(TextureFormat::Bc7RgbaUnormSrgb, TextureFormat::Bc7RgbaUnorm)
+        }
+        Format::BC7_SRGB_BLOCK => TextureFormat::Bc7RgbaUnormSrgb,
+
+        Format::ETC2_R8G8B8_UNORM_BLOCK => srgb(
+            TextureFormat::Etc2Rgb8UnormSrgb,
+            TextureFormat::Etc2Rgb8Unorm,
+        ),
+        Format::ETC2_R8G8B8_SRGB_BLOCK => TextureFormat::Etc2Rgb8UnormSrgb,
+        Format::ETC2_R8G8B8A1_UNORM_BLOCK => srgb(
+            TextureFormat::Etc2Rgb8A1UnormSrgb,
+            TextureFormat::Etc2Rgb8A1Unorm,
+        ),
+        Format::ETC2_R8G8B8A1_SRGB_BLOCK => TextureFormat::Etc2Rgb8A1UnormSrgb,
+        Format::ETC2_R8G8B8A8_UNORM_BLOCK => srgb(
+            TextureFormat::Etc2Rgba8UnormSrgb,
+            TextureFormat::Etc2Rgba8Unorm,
+        ),
+        Format::ETC2_R8G8B8A8_SRGB_BLOCK => TextureFormat::Etc2Rgba8UnormSrgb,
+        Format::EAC_R11_UNORM_BLOCK => TextureFormat::EacR11Unorm,
+        Format::EAC_R11_SNORM_BLOCK => TextureFormat::EacR11Snorm,
+        Format::EAC_R11G11_UNORM_BLOCK => TextureFormat::EacRg11Unorm,
+        Format::EAC_R11G11_SNORM_BLOCK => TextureFormat::EacRg11Snorm,
+
+        format => {
+            if let Some((block, srgb_block)) = ktx2_astc_block(format) {
+                TextureFormat::Astc {
+                    block,
+                    channel: if srgb_block || is_srgb {
+                        AstcChannel::UnormSrgb
+                    } else {
+                        AstcChannel::Unorm
+                    },
+                }
+            } else {
+                return Err(TextureError::UnsupportedTextureFormat(format!(
+                    "{format:?}"
+                )));
+            }
+        }
+    })
+}
+
+fn ktx2_astc_block(format: Format) -> Option<(AstcBlock, bool)> {
+    Some(match format {
+        Format::ASTC_4x4_UNORM_BLOCK => (AstcBlock::B4x4, false),
+        Format::ASTC_4x4_SRGB_BLOCK => (AstcBlock::B4x4, true),
+        Format::ASTC_5x4_UNORM_BLOCK => (AstcBlock::B5x4, false),
+        Format::ASTC_5x4_SRGB_BLOCK => (AstcBlock::B5x4, true),
+        Format::ASTC_5x5_UNORM_BLOCK => (AstcBlock::B5x5, false),
+        Format::ASTC_5x5_SRGB_BLOCK => (AstcBlock::B5x5, true),
+        Format::ASTC_6x5_UNORM_BLOCK => (AstcBlock::B6x5, false),
+        Format::ASTC_6x5_SRGB_BLOCK => (AstcBlock::B6x5, true),
+        Format::ASTC_6x6_UNORM_BLOCK => (AstcBlock::B6x6, false),
+        Format::ASTC_6x6_SRGB_BLOCK => (AstcBlock::B6x6, true),
+        Format::ASTC_8x5_UNORM_BLOCK => (AstcBlock::B8x5, false),
+        Format::ASTC_8x5_SRGB_BLOCK => (AstcBlock::B8x5, true),
+        Format::ASTC_8x6_UNORM_BLOCK => (AstcBlock::B8x6, false),
+        Format::ASTC_8x6_SRGB_BLOCK => (AstcBlock::B8x6, true),
+        Format::ASTC_8x8_UNORM_BLOCK => (AstcBlock::B8x8, false),
+        Format::ASTC_8x8_SRGB_BLOCK => (AstcBlock::B8x8, true),
+        Format::ASTC_10x5_UNORM_BLOCK => (AstcBlock::B10x5, false),
+        Format::ASTC_10x5_SRGB_BLOCK => (AstcBlock::B10x5, true),
+        Format::ASTC_10x6_UNORM_BLOCK => (AstcBlock::B10x6, false),
+        Format::ASTC_10x6_SRGB_BLOCK => (AstcBlock::B10x6, true),
+        Format::ASTC_10x8_UNORM_BLOCK => (AstcBlock::B10x8, false),
+        Format::ASTC_10x8_SRGB_BLOCK => (AstcBlock::B10x8, true),
+        Format::ASTC_10x10_UNORM_BLOCK => (AstcBlock::B10x10, false),
+        Format::ASTC_10x10_SRGB_BLOCK => (AstcBlock::B10x10, true),
+        Format::ASTC_12x10_UNORM_BLOCK => (AstcBlock::B12x10, false),
+        Format::ASTC_12x10_SRGB_BLOCK => (AstcBlock::B12x10, true),
+        Format::ASTC_12x12_UNORM_BLOCK => (AstcBlock::B12x12, false),
+        Format::ASTC_12x12_SRGB_BLOCK => (AstcBlock::B12x12, true),
+        _ => return None,
+    })
+}
@@ -1,7 +1,21 @@
+pub mod color_space;
+pub mod dds;
+pub mod depth_texture;
+pub mod fallback;
+pub mod gpu_image;
 pub mod image;
 pub mod image_loader;
+pub mod ktx2;
+pub mod msaa_texture;
 
 pub mod prelude {
-    pub use super::image::{CompressedImageFormats, Image, ImageFormat, ImageSampler, ImageType};
+    pub use super::color_space::ColorSpace;
+    pub use super::depth_texture::{DepthTexture, DEPTH_FORMAT};
+    pub use super::fallback::{FallbackTexture, FALLBACK_TEXTURE_PATH};
+    pub use super::gpu_image::GpuImage;
+    pub use super::image::{
+        CompressedImageFormats, Image, ImageFormat, ImageSampler, ImageSamplerDescriptor, ImageType,
+    };
     pub use super::image_loader::*;
+    pub use super::msaa_texture::MsaaTexture;
 }
@@ -0,0 +1,58 @@
+use wgpu::{
+    Extent3d, Texture, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+    TextureView,
+};
+
+use crate::renderer::RenderDevice;
+
+/// 多重采样的颜色附件，渲染后解析到 swapchain 纹理
+pub struct MsaaTexture {
+    pub texture: Texture,
+    pub view: TextureView,
+}
+
+impl MsaaTexture {
+    /// `format` 需要和解析目标的格式一致，宽高为 0 时按照 1 创建
+    pub fn new(
+        device: &RenderDevice,
+        format: TextureFormat,
+        width: u32,
+        height: u32,
+        sample_count: u32,
+    ) -> Self {
+        let texture = device.wgpu_device().create_texture(&TextureDescriptor {
+            label: Some("msaa_texture"),
+            size: Extent3d {
+                width: width.max(1),
+                height: height.max(1),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count,
+            dimension: TextureDimension::D2,
+            format,
+            usage: TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&Default::default());
+
+        Self { texture, view }
+    }
+
+    /// 大小和解析目标不一致时重新创建，返回是否重新创建
+    pub fn resize(&mut self, device: &RenderDevice, width: u32, height: u32) -> bool {
+        let size = self.texture.size();
+        if size.width == width.max(1) && size.height == height.max(1) {
+            return false;
+        }
+
+        *self = MsaaTexture::new(
+            device,
+            self.texture.format(),
+            width,
+            height,
+            self.texture.sample_count(),
+        );
+        true
+    }
+}
@@ -1,4 +1,5 @@
 mod resource_macros;
+#[allow(clippy::module_inception)]
 mod wrapper;
 
 pub use wrapper::*;
@@ -6,16 +6,38 @@
 // due to `evaluate_obligations`. we should check if this can be removed after a fix lands for
 // https://github.com/rust-lang/rust/issues/99188 (and after other `evaluate_obligations`-related changes).
 #[cfg(debug_assertions)]
+/// Generates a reference-counted wrapper around `$wgpu_type`. Cloning the wrapper is cheap
+/// and shares the wrapped value, so the same resource can be stored in several caches.
+///
+/// Attributes (such as doc comments) before the wrapper name are applied to the generated struct.
+///
+/// ```
+/// mini_renderer::render_resource_wrapper!(
+///     /// A shared label.
+///     ErasedLabel,
+///     String
+/// );
+///
+/// let label = ErasedLabel::new("main_pass".to_string());
+/// let cloned = label.clone();
+/// assert_eq!(cloned.as_str(), "main_pass");
+///
+/// // `try_unwrap` only succeeds for the last reference
+/// assert!(label.try_unwrap().is_none());
+/// assert_eq!(cloned.try_unwrap().as_deref(), Some("main_pass"));
+/// ```
 #[macro_export]
 macro_rules! render_resource_wrapper {
-    ($wrapper_type:ident, $wgpu_type:ty) => {
+    ($(#[$attr:meta])* $wrapper_type:ident, $wgpu_type:ty $(,)?) => {
         #[cfg(not(all(target_arch = "wasm32", target_feature = "atomics")))]
         #[derive(Debug)]
+        $(#[$attr])*
         // SAFETY: while self is live, self.0 comes from `into_raw` of an Arc<$wgpu_type> with a strong ref.
         pub struct $wrapper_type(*const ());
 
         #[cfg(all(target_arch = "wasm32", target_feature = "atomics"))]
         #[derive(Debug)]
+        $(#[$attr])*
         pub struct $wrapper_type(send_wrapper::SendWrapper<*const ()>);
 
         impl $wrapper_type {
@@ -73,6 +95,7 @@ macro_rules! render_resource_wrapper {
         unsafe impl Sync for $wrapper_type {}
         #[cfg(not(all(target_arch = "wasm32", target_feature = "atomics")))]
         const _: () = {
+            #[allow(dead_code)]
             trait AssertSendSyncBound: Send + Sync {}
             impl AssertSendSyncBound for $wgpu_type {}
         };
@@ -101,14 +124,36 @@ macro_rules! render_resource_wrapper {
 }
 
 #[cfg(not(debug_assertions))]
+/// Generates a reference-counted wrapper around `$wgpu_type`. Cloning the wrapper is cheap
+/// and shares the wrapped value, so the same resource can be stored in several caches.
+///
+/// Attributes (such as doc comments) before the wrapper name are applied to the generated struct.
+///
+/// ```
+/// mini_renderer::render_resource_wrapper!(
+///     /// A shared label.
+///     ErasedLabel,
+///     String
+/// );
+///
+/// let label = ErasedLabel::new("main_pass".to_string());
+/// let cloned = label.clone();
+/// assert_eq!(cloned.as_str(), "main_pass");
+///
+/// // `try_unwrap` only succeeds for the last reference
+/// assert!(label.try_unwrap().is_none());
+/// assert_eq!(cloned.try_unwrap().as_deref(), Some("main_pass"));
+/// ```
 #[macro_export]
 macro_rules! render_resource_wrapper {
-    ($wrapper_type:ident, $wgpu_type:ty) => {
+    ($(#[$attr:meta])* $wrapper_type:ident, $wgpu_type:ty $(,)?) => {
         #[cfg(not(all(target_arch = "wasm32", target_feature = "atomics")))]
         #[derive(Clone, Debug)]
+        $(#[$attr])*
         pub struct $wrapper_type(std::sync::Arc<$wgpu_type>);
         #[cfg(all(target_arch = "wasm32", target_feature = "atomics"))]
         #[derive(Clone, Debug)]
+        $(#[$attr])*
         pub struct $wrapper_type(std::sync::Arc<send_wrapper::SendWrapper<$wgpu_type>>);
 
         impl $wrapper_type {
@@ -139,6 +184,7 @@ macro_rules! render_resource_wrapper {
 
         #[cfg(not(all(target_arch = "wasm32", target_feature = "atomics")))]
         const _: () = {
+            #[allow(dead_code)]
             trait AssertSendSyncBound: Send + Sync {}
             impl AssertSendSyncBound for $wgpu_type {}
         };
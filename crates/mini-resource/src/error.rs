@@ -1,5 +1,11 @@
-use crate::io::{AssetReaderError, MissingAssetSourceError};
-use mini_core::thiserror::Error;
+use crate::{
+    io::{
+        AssetReaderError, AssetWriterError, MissingAssetSourceError, MissingAssetWriterError,
+        ResourcePath,
+    },
+    resource::ResourceKind,
+};
+use mini_core::{downcast::Downcast, thiserror::Error, uuid::Uuid};
 use std::{fmt::Debug, sync::Arc};
 
 #[derive(Debug, Error)]
@@ -8,6 +14,48 @@ pub enum ResourceError {
     MissingAssetSourceError(#[from] MissingAssetSourceError),
     #[error(transparent)]
     AssetReaderError(#[from] AssetReaderError),
+    #[error(transparent)]
+    MissingAssetWriterError(#[from] MissingAssetWriterError),
+    #[error(transparent)]
+    AssetWriterError(#[from] AssetWriterError),
+    #[error("could not serialize {path}: {message}")]
+    Serialize {
+        path: ResourcePath<'static>,
+        message: String,
+    },
+    #[error("resource {path} failed to load: {error:?}")]
+    Load {
+        path: ResourcePath<'static>,
+        error: LoadError,
+    },
+    #[error("can not move {from} to {to}, they belong to different sources")]
+    RenameAcrossSources {
+        from: ResourcePath<'static>,
+        to: ResourcePath<'static>,
+    },
+}
+
+/// [`Resource::try_data_ref`](crate::resource::Resource::try_data_ref) 失败的原因
+#[derive(Debug, Clone, Error)]
+pub enum ResourceAccessError {
+    #[error("resource {0} is still loading")]
+    Pending(ResourceKind),
+    #[error("resource {kind} failed to load: {error:?}")]
+    LoadError {
+        kind: ResourceKind,
+        error: LoadError,
+    },
+    #[error("resource {0} holds data of another type")]
+    TypeMismatch(ResourceKind),
+}
+
+/// [`Resource::try_new`](crate::resource::Resource::try_new) 的资源数据不是期望的类型
+#[derive(Debug, Clone, Error)]
+#[error("resource {kind} holds data of type {actual}, expected {expected}")]
+pub struct TypeMismatchError {
+    pub kind: ResourceKind,
+    pub expected: Uuid,
+    pub actual: Uuid,
 }
 
 #[derive(Debug, Clone)]
@@ -18,6 +66,22 @@ impl LoadError {
     pub fn new<T: ResourceLoadError>(value: T) -> Self {
         Self(Some(Arc::new(value)))
     }
+
+    /// 加载被 [`ResourceManager::cancel_load`](crate::manager::ResourceManager::cancel_load) 取消
+    pub fn cancelled() -> Self {
+        Self::new(LoadCancelled)
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0
+            .as_ref()
+            .is_some_and(|error| <dyn ResourceLoadError>::as_any(&**error).is::<LoadCancelled>())
+    }
 }
 
-pub trait ResourceLoadError: 'static + Debug + Send + Sync {}
+/// 加载被取消时提交的错误，见 [`LoadError::cancelled`]
+#[derive(Debug, Clone, Copy, Error)]
+#[error("resource loading was cancelled")]
+pub struct LoadCancelled;
+
+pub trait ResourceLoadError: 'static + Debug + Send + Sync + Downcast {}
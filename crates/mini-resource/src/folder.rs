@@ -0,0 +1,14 @@
+use mini_core::{
+    type_uuid::TypeUuidProvider,
+    uuid::{uuid, Uuid},
+};
+
+use crate::resource::{ResourceData, UntypedResource};
+
+/// [`ResourceManager::load_folder`](crate::manager::ResourceManager::load_folder) 加载的目录，
+/// 包含目录和子目录中所有能够加载的资源。
+#[derive(Debug, TypeUuidProvider, ResourceData)]
+#[type_uuid(id = "8f2c5d1e-3b7a-4c9e-a0d4-6e1f27b93c58")]
+pub struct LoadedFolder {
+    pub resources: Vec<UntypedResource>,
+}
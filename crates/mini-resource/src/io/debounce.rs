@@ -0,0 +1,78 @@
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+/// 默认的去抖时间窗口
+pub const DEFAULT_DEBOUNCE_WINDOW: Duration = Duration::from_millis(250);
+
+/// 合并短时间内同一路径的多次修改事件。
+///
+/// 编辑器保存文件时经常先清空再写入，会连续产生多个事件。路径的第一个事件开启一个时间窗口，
+/// 窗口内的后续事件都会被合并，窗口结束后该路径只会被 [`Debouncer::drain_ready`] 返回一次。
+#[derive(Debug)]
+pub struct Debouncer {
+    window: Duration,
+    pending: HashMap<PathBuf, Instant>,
+}
+
+impl Default for Debouncer {
+    fn default() -> Self {
+        Self::new(DEFAULT_DEBOUNCE_WINDOW)
+    }
+}
+
+impl Debouncer {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            pending: HashMap::new(),
+        }
+    }
+
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    /// 记录一次修改事件
+    pub fn push(&mut self, path: impl Into<PathBuf>) {
+        self.push_at(path, Instant::now());
+    }
+
+    pub fn push_at(&mut self, path: impl Into<PathBuf>, now: Instant) {
+        self.pending.entry(path.into()).or_insert(now);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// 取出时间窗口已经结束的路径
+    pub fn drain_ready(&mut self) -> Vec<PathBuf> {
+        self.drain_ready_at(Instant::now())
+    }
+
+    pub fn drain_ready_at(&mut self, now: Instant) -> Vec<PathBuf> {
+        let window = self.window;
+        let mut ready = Vec::new();
+        self.pending.retain(|path, first_event| {
+            if now.saturating_duration_since(*first_event) >= window {
+                ready.push(path.clone());
+                false
+            } else {
+                true
+            }
+        });
+        ready.sort();
+        ready
+    }
+
+    /// 距离下一个路径就绪还需要等待的时间，没有等待中的路径时返回 `None`。
+    pub fn next_deadline(&self, now: Instant) -> Option<Duration> {
+        self.pending
+            .values()
+            .map(|first_event| (*first_event + self.window).saturating_duration_since(now))
+            .min()
+    }
+}
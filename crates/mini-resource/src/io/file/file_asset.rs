@@ -1,14 +1,14 @@
 use crate::io::{
     get_meta_path, AssetReader, AssetReaderError, AssetWriter, AssetWriterError, PathStream,
-    Reader, Writer,
+    Reader, VecReader, Writer,
 };
 
 use mini_core::{
     async_fs::{self, read_dir, File},
-    futures_lite::StreamExt,
+    futures_lite::{AsyncReadExt, AsyncSeekExt, StreamExt},
 };
 
-use std::path::Path;
+use std::{io::SeekFrom, ops::Range, path::Path};
 
 use super::{FileAssetReader, FileAssetWriter};
 
@@ -79,6 +79,22 @@ impl AssetReader for FileAssetReader {
             .map_err(|_e| AssetReaderError::NotFound(path.to_owned()))?;
         Ok(metadata.file_type().is_dir())
     }
+
+    // 只读取范围内的字节，不需要读取整个文件
+    async fn read_range<'a>(
+        &'a self,
+        path: &'a Path,
+        range: Range<u64>,
+    ) -> Result<impl Reader + 'a, AssetReaderError> {
+        let mut file = self.read(path).await?;
+        file.seek(SeekFrom::Start(range.start)).await?;
+
+        let mut bytes = Vec::new();
+        file.take(range.end.saturating_sub(range.start))
+            .read_to_end(&mut bytes)
+            .await?;
+        Ok(VecReader::new(bytes))
+    }
 }
 
 impl AssetWriter for FileAssetWriter {
@@ -168,3 +184,47 @@ impl AssetWriter for FileAssetWriter {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use mini_core::futures_lite::future::block_on;
+
+    use super::*;
+    use crate::io::{Dir, ErasedAssetReader, MemoryAssetReader};
+
+    fn read_range(reader: &dyn ErasedAssetReader, range: Range<u64>) -> Vec<u8> {
+        block_on(async {
+            let mut reader = reader.read_range(Path::new("data.bin"), range).await?;
+            let mut bytes = Vec::new();
+            Reader::read_to_end(&mut reader, &mut bytes).await?;
+            Ok::<_, AssetReaderError>(bytes)
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_read_range() {
+        let data = (0..32).collect::<Vec<u8>>();
+
+        let root_path = std::env::temp_dir().join(format!("mini-resource-{}", std::process::id()));
+        std::fs::create_dir_all(&root_path).unwrap();
+        std::fs::write(root_path.join("data.bin"), &data).unwrap();
+        let file_reader = FileAssetReader {
+            root_path: root_path.clone(),
+        };
+
+        // 默认实现读取整个文件后截取
+        let dir = Dir::new();
+        dir.insert_asset("data.bin", data.clone());
+        let memory_reader = MemoryAssetReader { root: dir };
+
+        for reader in [&file_reader as &dyn ErasedAssetReader, &memory_reader] {
+            assert_eq!(read_range(reader, 4..8), [4, 5, 6, 7]);
+            assert_eq!(read_range(reader, 0..32), data);
+            assert_eq!(read_range(reader, 28..100), [28, 29, 30, 31]);
+            assert!(read_range(reader, 40..50).is_empty());
+        }
+
+        std::fs::remove_dir_all(root_path).unwrap();
+    }
+}
@@ -0,0 +1,151 @@
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::Sender,
+        Arc,
+    },
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use mini_core::tracing::error;
+
+use crate::io::{AssetSourceEvent, AssetWatcher, Debouncer};
+
+/// [`FileWatcher`] 默认的轮询间隔
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// 轮询文件的修改时间来监听目录的变化，被释放时停止监听。
+///
+/// 修改事件经过 [`Debouncer`] 合并，事件中的路径是相对于根目录的路径。
+pub struct FileWatcher {
+    running: Arc<AtomicBool>,
+}
+
+impl FileWatcher {
+    /// 根目录不存在时返回错误
+    pub fn new(
+        root: PathBuf,
+        sender: Sender<AssetSourceEvent>,
+        poll_interval: Duration,
+        debounce_window: Duration,
+    ) -> io::Result<Self> {
+        let mut snapshot = scan(&root)?;
+        let running = Arc::new(AtomicBool::new(true));
+        let flag = running.clone();
+
+        thread::Builder::new()
+            .name("file watcher".to_string())
+            .spawn(move || {
+                let mut debouncer = Debouncer::new(debounce_window);
+                while flag.load(Ordering::Relaxed) {
+                    thread::sleep(poll_interval);
+                    let current = match scan(&root) {
+                        Ok(current) => current,
+                        Err(e) => {
+                            error!("Failed to scan {}: {e}", root.display());
+                            continue;
+                        }
+                    };
+
+                    let mut events = Vec::new();
+                    for (path, modified) in current.iter() {
+                        match snapshot.get(path) {
+                            None => events.push(AssetSourceEvent::AddedAsset(path.clone())),
+                            Some(old) if old != modified => debouncer.push(path.clone()),
+                            _ => {}
+                        }
+                    }
+                    for path in snapshot.keys() {
+                        if !current.contains_key(path) {
+                            events.push(AssetSourceEvent::RemovedAsset(path.clone()));
+                        }
+                    }
+                    events.extend(
+                        debouncer
+                            .drain_ready()
+                            .into_iter()
+                            .filter(|path| current.contains_key(path))
+                            .map(AssetSourceEvent::ModifiedAsset),
+                    );
+                    snapshot = current;
+
+                    for event in events {
+                        // 接收端被释放后不再监听
+                        if sender.send(event).is_err() {
+                            return;
+                        }
+                    }
+                }
+            })?;
+
+        Ok(Self { running })
+    }
+}
+
+impl AssetWatcher for FileWatcher {}
+
+impl Drop for FileWatcher {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+    }
+}
+
+//根目录下所有文件的修改时间
+fn scan(root: &Path) -> io::Result<HashMap<PathBuf, SystemTime>> {
+    let mut files = HashMap::new();
+    let mut dirs = vec![root.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        for entry in fs::read_dir(&dir)? {
+            let entry = entry?;
+            let metadata = entry.metadata()?;
+            let path = entry.path();
+            if metadata.is_dir() {
+                dirs.push(path);
+            } else if let Ok(relative) = path.strip_prefix(root) {
+                files.insert(relative.to_path_buf(), metadata.modified()?);
+            }
+        }
+    }
+    Ok(files)
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::mpsc;
+
+    use super::*;
+
+    #[test]
+    fn test_file_watcher_events() {
+        let root = std::env::temp_dir().join(format!("mini-watcher-{}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+        let (sender, receiver) = mpsc::channel();
+        let watcher = FileWatcher::new(
+            root.clone(),
+            sender,
+            Duration::from_millis(10),
+            Duration::ZERO,
+        )
+        .unwrap();
+
+        let timeout = Duration::from_secs(5);
+        fs::write(root.join("a.txt"), b"a").unwrap();
+        assert_eq!(
+            receiver.recv_timeout(timeout).unwrap(),
+            AssetSourceEvent::AddedAsset("a.txt".into())
+        );
+
+        fs::remove_file(root.join("a.txt")).unwrap();
+        assert_eq!(
+            receiver.recv_timeout(timeout).unwrap(),
+            AssetSourceEvent::RemovedAsset("a.txt".into())
+        );
+
+        drop(watcher);
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -1,4 +1,9 @@
 mod file_asset;
+#[cfg(feature = "file_watcher")]
+mod file_watcher;
+
+#[cfg(feature = "file_watcher")]
+pub use file_watcher::*;
 
 use std::{
     env,
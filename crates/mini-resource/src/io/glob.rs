@@ -0,0 +1,56 @@
+use super::ResourcePath;
+
+/// Returns a predicate for [`ResourceManager::load_folder_filtered`](crate::manager::ResourceManager::load_folder_filtered)
+/// that matches the path (without source and label) against a glob `pattern`.
+///
+/// * `?` matches any single character except `/`
+/// * `*` matches any number of characters except `/`
+/// * `**` matches any number of characters, including `/`
+///
+/// Matching is case sensitive and `\` in paths is treated as `/`.
+pub fn glob(pattern: impl Into<String>) -> impl Fn(&ResourcePath) -> bool + Send + Sync + 'static {
+    let pattern = pattern.into();
+    move |path: &ResourcePath| {
+        let path = path.path().to_string_lossy().replace('\\', "/");
+        glob_match(pattern.as_bytes(), path.as_bytes())
+    }
+}
+
+fn glob_match(pattern: &[u8], text: &[u8]) -> bool {
+    match pattern {
+        [] => text.is_empty(),
+        [b'*', b'*', rest @ ..] => {
+            // `**/` also matches no directory at all
+            let rest_without_slash = rest.strip_prefix(b"/").unwrap_or(rest);
+            (0..=text.len())
+                .any(|i| glob_match(rest, &text[i..]) || glob_match(rest_without_slash, &text[i..]))
+        }
+        [b'*', rest @ ..] => {
+            let segment_len = text.iter().position(|c| *c == b'/').unwrap_or(text.len());
+            (0..=segment_len).any(|i| glob_match(rest, &text[i..]))
+        }
+        [b'?', rest @ ..] => matches!(text, [c, text @ ..] if *c != b'/' && glob_match(rest, text)),
+        [p, rest @ ..] => matches!(text, [c, text @ ..] if c == p && glob_match(rest, text)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_glob() {
+        let matches = |pattern: &str, path: &'static str| glob(pattern)(&ResourcePath::from(path));
+
+        assert!(matches("textures/*.png", "textures/a.png"));
+        assert!(!matches("textures/*.png", "textures/sub/a.png"));
+        assert!(!matches("textures/*.png", "textures/a.jpg"));
+        assert!(matches("textures/**/*.png", "textures/sub/deep/a.png"));
+        assert!(matches("textures/**/*.png", "textures/a.png"));
+        assert!(matches("**.png", "remote://textures/a.png#label"));
+        assert!(matches("textures/?.png", "textures/a.png"));
+        assert!(!matches("textures/?.png", "textures/ab.png"));
+        assert!(matches("*", "a.png"));
+        assert!(!matches("*", "textures/a.png"));
+    }
+}
@@ -0,0 +1,263 @@
+use std::{
+    collections::BTreeMap,
+    io::{Error, ErrorKind},
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use mini_core::{futures_io::AsyncWrite, futures_lite::stream, parking_lot::RwLock};
+
+use super::{
+    get_meta_path, AssetReader, AssetReaderError, AssetWriter, AssetWriterError, PathStream,
+    Reader, VecReader, Writer,
+};
+
+/// 内存中的目录，资源按照相对路径保存。克隆后共享同一份数据。
+#[derive(Default, Clone, Debug)]
+pub struct Dir(Arc<RwLock<DirInternal>>);
+
+#[derive(Default, Debug)]
+struct DirInternal {
+    files: BTreeMap<PathBuf, Arc<[u8]>>,
+}
+
+impl Dir {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert_asset(&self, path: impl Into<PathBuf>, value: impl Into<Arc<[u8]>>) {
+        self.0.write().files.insert(path.into(), value.into());
+    }
+
+    pub fn insert_meta(&self, path: &Path, value: impl Into<Arc<[u8]>>) {
+        self.insert_asset(get_meta_path(path), value);
+    }
+
+    pub fn remove_asset(&self, path: &Path) -> Option<Arc<[u8]>> {
+        self.0.write().files.remove(path)
+    }
+
+    /// 移动 `old_path` 的文件到 `new_path`，文件不存在时返回 false
+    pub fn rename(&self, old_path: &Path, new_path: &Path) -> bool {
+        let mut dir = self.0.write();
+        let Some(value) = dir.files.remove(old_path) else {
+            return false;
+        };
+        dir.files.insert(new_path.to_path_buf(), value);
+        true
+    }
+
+    /// 删除 `path` 目录下的所有文件
+    pub fn remove_directory(&self, path: &Path) {
+        self.0
+            .write()
+            .files
+            .retain(|file, _| !file.starts_with(path));
+    }
+
+    pub fn get_asset(&self, path: &Path) -> Option<Arc<[u8]>> {
+        self.0.read().files.get(path).cloned()
+    }
+
+    pub fn get_meta(&self, path: &Path) -> Option<Arc<[u8]>> {
+        self.get_asset(&get_meta_path(path))
+    }
+
+    /// 根目录或者包含文件的路径都视为目录
+    pub fn is_directory(&self, path: &Path) -> bool {
+        path.as_os_str().is_empty()
+            || self
+                .0
+                .read()
+                .files
+                .keys()
+                .any(|file| file != path && file.starts_with(path))
+    }
+
+    /// `path` 下的直接子项（文件和目录），不包含 meta 文件。
+    pub fn entries(&self, path: &Path) -> Vec<PathBuf> {
+        let mut entries = Vec::new();
+        for file in self.0.read().files.keys() {
+            let Ok(relative) = file.strip_prefix(path) else {
+                continue;
+            };
+            let Some(first) = relative.components().next() else {
+                continue;
+            };
+            let entry = path.join(first);
+            let is_meta = entry == *file
+                && entry
+                    .extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("meta"));
+            if !is_meta && !entries.contains(&entry) {
+                entries.push(entry);
+            }
+        }
+        entries
+    }
+}
+
+/// 从 [`Dir`] 中读取资源的 [`AssetReader`]，主要用于测试。
+#[derive(Default, Clone)]
+pub struct MemoryAssetReader {
+    pub root: Dir,
+}
+
+impl AssetReader for MemoryAssetReader {
+    async fn read<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        self.root
+            .get_asset(path)
+            .map(|data| VecReader::new(data.to_vec()))
+            .ok_or_else(|| AssetReaderError::NotFound(path.to_path_buf()))
+    }
+
+    async fn read_meta<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        self.root
+            .get_meta(path)
+            .map(|data| VecReader::new(data.to_vec()))
+            .ok_or_else(|| AssetReaderError::NotFound(get_meta_path(path)))
+    }
+
+    async fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> Result<Box<PathStream>, AssetReaderError> {
+        if !self.root.is_directory(path) {
+            return Err(AssetReaderError::NotFound(path.to_path_buf()));
+        }
+        let stream: Box<PathStream> = Box::new(stream::iter(self.root.entries(path)));
+        Ok(stream)
+    }
+
+    async fn is_directory<'a>(&'a self, path: &'a Path) -> Result<bool, AssetReaderError> {
+        Ok(self.root.is_directory(path))
+    }
+}
+
+/// 写入 [`Dir`] 的 [`AssetWriter`]，主要用于测试。
+#[derive(Default, Clone)]
+pub struct MemoryAssetWriter {
+    pub root: Dir,
+}
+
+/// 写入的数据在 flush 或 close 时保存到 [`Dir`] 中
+struct MemoryWriter {
+    root: Dir,
+    path: PathBuf,
+    bytes: Vec<u8>,
+}
+
+impl MemoryWriter {
+    fn commit(&self) {
+        self.root
+            .insert_asset(self.path.clone(), self.bytes.as_slice());
+    }
+}
+
+impl AsyncWrite for MemoryWriter {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        self.bytes.extend_from_slice(buf);
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        self.commit();
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        self.commit();
+        Poll::Ready(Ok(()))
+    }
+}
+
+fn not_found(path: &Path) -> AssetWriterError {
+    Error::new(ErrorKind::NotFound, path.display().to_string()).into()
+}
+
+impl MemoryAssetWriter {
+    fn writer(&self, path: PathBuf) -> Box<Writer> {
+        Box::new(MemoryWriter {
+            root: self.root.clone(),
+            path,
+            bytes: Vec::new(),
+        })
+    }
+
+    fn remove_file(&self, path: &Path) -> Result<(), AssetWriterError> {
+        self.root
+            .remove_asset(path)
+            .map(|_| ())
+            .ok_or_else(|| not_found(path))
+    }
+
+    fn rename_file(&self, old_path: &Path, new_path: &Path) -> Result<(), AssetWriterError> {
+        if self.root.rename(old_path, new_path) {
+            Ok(())
+        } else {
+            Err(not_found(old_path))
+        }
+    }
+}
+
+impl AssetWriter for MemoryAssetWriter {
+    async fn write<'a>(&'a self, path: &'a Path) -> Result<Box<Writer>, AssetWriterError> {
+        Ok(self.writer(path.to_path_buf()))
+    }
+
+    async fn write_meta<'a>(&'a self, path: &'a Path) -> Result<Box<Writer>, AssetWriterError> {
+        Ok(self.writer(get_meta_path(path)))
+    }
+
+    async fn remove<'a>(&'a self, path: &'a Path) -> Result<(), AssetWriterError> {
+        self.remove_file(path)
+    }
+
+    async fn remove_meta<'a>(&'a self, path: &'a Path) -> Result<(), AssetWriterError> {
+        self.remove_file(&get_meta_path(path))
+    }
+
+    async fn rename<'a>(
+        &'a self,
+        old_path: &'a Path,
+        new_path: &'a Path,
+    ) -> Result<(), AssetWriterError> {
+        self.rename_file(old_path, new_path)
+    }
+
+    async fn rename_meta<'a>(
+        &'a self,
+        old_path: &'a Path,
+        new_path: &'a Path,
+    ) -> Result<(), AssetWriterError> {
+        self.rename_file(&get_meta_path(old_path), &get_meta_path(new_path))
+    }
+
+    async fn remove_directory<'a>(&'a self, path: &'a Path) -> Result<(), AssetWriterError> {
+        self.root.remove_directory(path);
+        Ok(())
+    }
+
+    async fn remove_empty_directory<'a>(&'a self, path: &'a Path) -> Result<(), AssetWriterError> {
+        if self.root.entries(path).is_empty() {
+            Ok(())
+        } else {
+            Err(Error::other("directory is not empty").into())
+        }
+    }
+
+    async fn remove_assets_in_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> Result<(), AssetWriterError> {
+        self.root.remove_directory(path);
+        Ok(())
+    }
+}
@@ -1,12 +1,18 @@
 use std::path::{Path, PathBuf};
 
+mod debounce;
 mod file;
+mod glob;
+mod memory;
 mod path;
 mod reader;
 mod source;
 mod writer;
 
+pub use debounce::*;
 pub use file::*;
+pub use glob::*;
+pub use memory::*;
 pub use path::*;
 pub use reader::*;
 pub use source::*;
@@ -20,3 +26,17 @@ pub(crate) fn get_meta_path(path: &Path) -> PathBuf {
     meta_path.set_extension(extension);
     meta_path
 }
+
+/// Returns a predicate for [`ResourceManager::read_directory_filtered`](crate::manager::ResourceManager::read_directory_filtered)
+/// that matches paths with one of the given `extensions`, ignoring case and a leading `.`.
+pub fn with_extensions<'a>(extensions: &'a [&'a str]) -> impl Fn(&Path) -> bool + 'a {
+    move |path| {
+        path.extension()
+            .and_then(|extension| extension.to_str())
+            .is_some_and(|extension| {
+                extensions
+                    .iter()
+                    .any(|e| e.trim_start_matches('.').eq_ignore_ascii_case(extension))
+            })
+    }
+}
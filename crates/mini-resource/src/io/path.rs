@@ -12,32 +12,23 @@ use thiserror::Error;
 ///
 /// Asset paths consist of three main parts:
 /// * [`ResourcePath::source`]: The name of the [`ResourceSource`](crate::io::ResourceSource) to load the asset from.
-///     This is optional. If one is not set the default source will be used (which is the `assets` folder by default).
+///   This is optional. If one is not set the default source will be used (which is the `assets` folder by default).
 /// * [`ResourcePath::path`]: The "virtual filesystem path" pointing to an asset source file.
 /// * [`ResourcePath::label`]: An optional "named sub asset". When assets are loaded, they are
-///     allowed to load "sub assets" of any type, which are identified by a named "label".
+///   allowed to load "sub assets" of any type, which are identified by a named "label".
 ///
 /// Asset paths are generally constructed (and visualized) as strings:
 ///
 /// ```no_run
-/// # use bevy_asset::{Asset, AssetServer, Handle};
-/// # use bevy_reflect::TypePath;
-/// #
-/// # #[derive(Asset, TypePath, Default)]
-/// # struct Mesh;
-/// #
-/// # #[derive(Asset, TypePath, Default)]
-/// # struct Scene;
-/// #
-/// # let asset_server: AssetServer = panic!();
-/// // This loads the `my_scene.scn` base asset from the default asset source.
-/// let scene: Handle<Scene> = asset_server.load("my_scene.scn");
+/// # use mini_resource::prelude::ResourcePath;
+/// // The `my_scene.scn` base asset from the default asset source.
+/// let scene = ResourcePath::parse("my_scene.scn");
 ///
-/// // This loads the `PlayerMesh` labeled asset from the `my_scene.scn` base asset in the default asset source.
-/// let mesh: Handle<Mesh> = asset_server.load("my_scene.scn#PlayerMesh");
+/// // The `PlayerMesh` labeled asset from the `my_scene.scn` base asset in the default asset source.
+/// let mesh = ResourcePath::parse("my_scene.scn#PlayerMesh");
 ///
-/// // This loads the `my_scene.scn` base asset from a custom 'remote' asset source.
-/// let scene: Handle<Scene> = asset_server.load("remote://my_scene.scn");
+/// // The `my_scene.scn` base asset from a custom 'remote' asset source.
+/// let remote_scene = ResourcePath::parse("remote://my_scene.scn");
 /// ```
 ///
 /// [`ResourcePath`] implements [`From`] for `&'static str`, `&'static Path`, and `&'a String`,
@@ -143,42 +134,28 @@ impl<'a> ResourcePath<'a> {
         // 3. Set the `path_range` to be everything in between the `source_range` and `label_range`,
         //  excluding the `://` substring and `#` character.
         // 4. Verify that there are no `#` characters in the `ResourcePath::source` and no `://` substrings in the `ResourcePath::label`
-        let mut source_delimiter_chars_matched = 0;
         let mut last_found_source_index = 0;
         for (index, char) in chars {
             match char {
-                ':' => {
-                    source_delimiter_chars_matched = 1;
-                }
-                '/' => {
-                    match source_delimiter_chars_matched {
-                        1 => {
-                            source_delimiter_chars_matched = 2;
-                        }
-                        2 => {
-                            // If we haven't found our first `ResourcePath::source` yet, check to make sure it is valid and then store it.
-                            if source_range.is_none() {
-                                // If the `ResourcePath::source` contains a `#` character, it is invalid.
-                                if label_range.is_some() {
-                                    return Err(ParseAssetPathError::InvalidSourceSyntax);
-                                }
-                                source_range = Some(0..index - 2);
-                                path_range.start = index + 1;
-                            }
-                            last_found_source_index = index - 2;
-                            source_delimiter_chars_matched = 0;
+                // Only a full `://` delimits the source. A lone `:` (e.g. the drive letter in `C:/tex.png`
+                // or `C:\tex.png`) is part of the path.
+                ':' if asset_path[index..].starts_with("://") => {
+                    // If we haven't found our first `ResourcePath::source` yet, check to make sure it is valid and then store it.
+                    if source_range.is_none() {
+                        // If the `ResourcePath::source` contains a `#` character, it is invalid.
+                        if label_range.is_some() {
+                            return Err(ParseAssetPathError::InvalidSourceSyntax);
                         }
-                        _ => {}
+                        source_range = Some(0..index);
+                        path_range.start = index + 3;
                     }
+                    last_found_source_index = index;
                 }
                 '#' => {
                     path_range.end = index;
                     label_range = Some(index + 1..asset_path.len());
-                    source_delimiter_chars_matched = 0;
-                }
-                _ => {
-                    source_delimiter_chars_matched = 0;
                 }
+                _ => {}
             }
         }
         // If we found an `ResourcePath::label`
@@ -228,7 +205,7 @@ impl<'a> ResourcePath<'a> {
     /// Gets the "asset source", if one was defined. If none was defined, the default source
     /// will be used.
     #[inline]
-    pub fn source(&self) -> &ResourceSourceId {
+    pub fn source(&self) -> &ResourceSourceId<'_> {
         &self.source
     }
 
@@ -335,7 +312,7 @@ impl<'a> ResourcePath<'a> {
     /// is resolved relative to this "base" path.
     ///
     /// ```
-    /// # use bevy_asset::ResourcePath;
+    /// # use mini_resource::prelude::ResourcePath;
     /// assert_eq!(ResourcePath::parse("a/b").resolve("c"), Ok(ResourcePath::parse("a/b/c")));
     /// assert_eq!(ResourcePath::parse("a/b").resolve("./c"), Ok(ResourcePath::parse("a/b/c")));
     /// assert_eq!(ResourcePath::parse("a/b").resolve("../c"), Ok(ResourcePath::parse("a/c")));
@@ -385,7 +362,7 @@ impl<'a> ResourcePath<'a> {
     /// which are relative to the asset in which they are contained.
     ///
     /// ```
-    /// # use bevy_asset::ResourcePath;
+    /// # use mini_resource::prelude::ResourcePath;
     /// assert_eq!(ResourcePath::parse("a/b").resolve_embed("c"), Ok(ResourcePath::parse("a/c")));
     /// assert_eq!(ResourcePath::parse("a/b").resolve_embed("./c"), Ok(ResourcePath::parse("a/c")));
     /// assert_eq!(ResourcePath::parse("a/b").resolve_embed("../c"), Ok(ResourcePath::parse("c")));
@@ -461,7 +438,41 @@ impl<'a> ResourcePath<'a> {
         Some(extension)
     }
 
-    pub(crate) fn iter_secondary_extensions(full_extension: &str) -> impl Iterator<Item = &str> {
+    /// Returns the file name of the path, without any query parameters.
+    /// Ex: Returns `"my_asset.config.ron"` for `"dir/my_asset.config.ron?v=2"`
+    pub fn file_name(&self) -> Option<&str> {
+        let file_name = self.path().file_name()?.to_str()?;
+        // `split` always yields at least one item
+        let file_name = file_name.split('?').next().unwrap();
+        (!file_name.is_empty()).then_some(file_name)
+    }
+
+    /// Returns the file name without its last extension.
+    /// Ex: Returns `"my_asset.config"` for `"my_asset.config.ron"`
+    ///
+    /// Like [`Path::file_stem`], a name starting with its only `.` (e.g. `".hidden"`) has no extension.
+    pub fn file_stem(&self) -> Option<&str> {
+        let file_name = self.file_name()?;
+        match file_name.rfind('.') {
+            Some(0) | None => Some(file_name),
+            Some(index) => Some(&file_name[..index]),
+        }
+    }
+
+    /// Returns the last extension, without changing its case.
+    /// Ex: Returns `"ron"` for `"my_asset.config.ron"`
+    ///
+    /// Also strips out anything following a `?` to handle query parameters in URIs
+    pub fn extension(&self) -> Option<&str> {
+        let file_name = self.file_name()?;
+        match file_name.rfind('.') {
+            Some(0) | None => None,
+            Some(index) => Some(&file_name[index + 1..]),
+        }
+    }
+
+    /// Iterates over the secondary extensions of a full extension, e.g. `"a.b.c"` yields `"b.c"` and `"c"`.
+    pub fn iter_secondary_extensions(full_extension: &str) -> impl Iterator<Item = &str> {
         full_extension.chars().enumerate().filter_map(|(i, c)| {
             if c == '.' {
                 Some(&full_extension[i + 1..])
@@ -550,3 +561,65 @@ pub(crate) fn normalize_path(path: &Path) -> PathBuf {
     }
     result_path
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_file_name_accessors() {
+        let path = ResourcePath::parse("levels/my_asset.config.ron");
+        assert_eq!(path.file_name(), Some("my_asset.config.ron"));
+        assert_eq!(path.file_stem(), Some("my_asset.config"));
+        assert_eq!(path.extension(), Some("ron"));
+        assert_eq!(path.get_full_extension().as_deref(), Some("config.ron"));
+
+        let path = ResourcePath::parse("remote://textures/Grass.PNG?version=2.1#albedo");
+        assert_eq!(path.file_name(), Some("Grass.PNG"));
+        assert_eq!(path.file_stem(), Some("Grass"));
+        assert_eq!(path.extension(), Some("PNG"));
+
+        let path = ResourcePath::parse("scripts/.hidden");
+        assert_eq!(path.file_stem(), Some(".hidden"));
+        assert_eq!(path.extension(), None);
+
+        let path = ResourcePath::parse("README");
+        assert_eq!(path.file_stem(), Some("README"));
+        assert_eq!(path.extension(), None);
+
+        let path = ResourcePath::parse("archive.tar.");
+        assert_eq!(path.file_stem(), Some("archive.tar"));
+        assert_eq!(path.extension(), Some(""));
+
+        assert_eq!(ResourcePath::parse("").file_name(), None);
+    }
+
+    #[test]
+    fn test_parse_windows_and_file_paths() {
+        let path = ResourcePath::parse("C:/tex.png");
+        assert_eq!(path.source(), &ResourceSourceId::Default);
+        assert_eq!(path.path(), Path::new("C:/tex.png"));
+
+        let path = ResourcePath::parse("C:\\tex.png#albedo");
+        assert_eq!(path.source(), &ResourceSourceId::Default);
+        assert_eq!(path.path(), Path::new("C:\\tex.png"));
+        assert_eq!(path.label(), Some("albedo"));
+
+        let path = ResourcePath::parse("file:///abs/path.png");
+        assert_eq!(path.source(), &ResourceSourceId::Name("file".into()));
+        assert_eq!(path.path(), Path::new("/abs/path.png"));
+
+        let path = ResourcePath::parse("http://localhost:8080/a:b.png");
+        assert_eq!(path.source(), &ResourceSourceId::Name("http".into()));
+        assert_eq!(path.path(), Path::new("localhost:8080/a:b.png"));
+
+        assert_eq!(
+            ResourcePath::try_parse("://tex.png"),
+            Err(ParseAssetPathError::MissingSource)
+        );
+        assert_eq!(
+            ResourcePath::try_parse("remote://a.png#label://b"),
+            Err(ParseAssetPathError::InvalidLabelSyntax)
+        );
+    }
+}
@@ -0,0 +1,313 @@
+use std::{
+    io::SeekFrom,
+    ops::Range,
+    path::Path,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use mini_core::{
+    future::BoxedFuture,
+    futures_io::{self, AsyncRead, AsyncSeek},
+    futures_lite::{future::poll_fn, ready},
+    stackfuture::StackFuture,
+};
+
+use super::{AssetReaderError, ErasedAssetReader, PathStream, Reader, STACK_FUTURE_SIZE};
+
+/// The default buffer size of a [`BufferedReader`].
+pub const DEFAULT_BUFFER_SIZE: usize = 64 * 1024;
+
+/// Wraps a [`Reader`] and reads it in chunks of a configurable size, so many small reads
+/// only hit the underlying reader once per chunk.
+pub struct BufferedReader<R> {
+    inner: R,
+    buffer: Box<[u8]>,
+    pos: usize,
+    filled: usize,
+}
+
+impl<R: Reader> BufferedReader<R> {
+    /// Create a new [`BufferedReader`] with a [`DEFAULT_BUFFER_SIZE`] buffer.
+    pub fn new(inner: R) -> Self {
+        Self::with_capacity(DEFAULT_BUFFER_SIZE, inner)
+    }
+
+    /// Create a new [`BufferedReader`] with a buffer of `capacity` bytes.
+    pub fn with_capacity(capacity: usize, inner: R) -> Self {
+        Self {
+            inner,
+            buffer: vec![0; capacity.max(1)].into_boxed_slice(),
+            pos: 0,
+            filled: 0,
+        }
+    }
+
+    /// The size of the internal buffer.
+    pub fn capacity(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Returns the wrapped reader. Any buffered data is lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn discard_buffer(&mut self) {
+        self.pos = 0;
+        self.filled = 0;
+    }
+}
+
+impl<R: Reader> AsyncRead for BufferedReader<R> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<futures_io::Result<usize>> {
+        let this = &mut *self;
+
+        // 缓冲区为空且请求的数据比缓冲区大，直接读取
+        if this.pos == this.filled && buf.len() >= this.buffer.len() {
+            this.discard_buffer();
+            return Pin::new(&mut this.inner).poll_read(cx, buf);
+        }
+
+        if this.pos == this.filled {
+            let n = ready!(Pin::new(&mut this.inner).poll_read(cx, &mut this.buffer))?;
+            this.pos = 0;
+            this.filled = n;
+        }
+
+        let available = &this.buffer[this.pos..this.filled];
+        let n = available.len().min(buf.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        this.pos += n;
+        Poll::Ready(Ok(n))
+    }
+}
+
+impl<R: Reader> AsyncSeek for BufferedReader<R> {
+    fn poll_seek(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        pos: SeekFrom,
+    ) -> Poll<std::io::Result<u64>> {
+        let this = &mut *self;
+        let pos = match pos {
+            // 底层 reader 的位置比逻辑位置多出缓冲区中未读的部分
+            SeekFrom::Current(offset) => {
+                let remaining = (this.filled - this.pos) as i64;
+                SeekFrom::Current(offset - remaining)
+            }
+            pos => pos,
+        };
+        let result = ready!(Pin::new(&mut this.inner).poll_seek(cx, pos));
+        this.discard_buffer();
+        Poll::Ready(result)
+    }
+}
+
+impl<R: Reader> Reader for BufferedReader<R> {
+    fn read_to_end<'a>(
+        &'a mut self,
+        buf: &'a mut Vec<u8>,
+    ) -> StackFuture<'a, std::io::Result<usize>, STACK_FUTURE_SIZE> {
+        let mut read = 0;
+        StackFuture::from(poll_fn(move |cx| loop {
+            if self.pos < self.filled {
+                buf.extend_from_slice(&self.buffer[self.pos..self.filled]);
+                read += self.filled - self.pos;
+                self.discard_buffer();
+            }
+
+            let start = buf.len();
+            buf.resize(start + self.buffer.len(), 0);
+            let result = Pin::new(&mut self.inner).poll_read(cx, &mut buf[start..]);
+            match result {
+                Poll::Ready(Ok(n)) => {
+                    buf.truncate(start + n);
+                    read += n;
+                    if n == 0 {
+                        return Poll::Ready(Ok(read));
+                    }
+                }
+                Poll::Ready(Err(err)) => {
+                    buf.truncate(start);
+                    return Poll::Ready(Err(err));
+                }
+                Poll::Pending => {
+                    buf.truncate(start);
+                    return Poll::Pending;
+                }
+            }
+        }))
+    }
+}
+
+/// An [`ErasedAssetReader`] that wraps every reader returned by [`ErasedAssetReader::read`]
+/// in a [`BufferedReader`].
+pub struct BufferedAssetReader {
+    inner: Box<dyn ErasedAssetReader>,
+    buffer_size: usize,
+}
+
+impl BufferedAssetReader {
+    pub fn new(inner: Box<dyn ErasedAssetReader>, buffer_size: usize) -> Self {
+        Self { inner, buffer_size }
+    }
+}
+
+impl ErasedAssetReader for BufferedAssetReader {
+    fn read<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<dyn Reader + 'a>, AssetReaderError>> {
+        Box::pin(async move {
+            let reader = self.inner.read(path).await?;
+            Ok(
+                Box::new(BufferedReader::with_capacity(self.buffer_size, reader))
+                    as Box<dyn Reader>,
+            )
+        })
+    }
+
+    fn read_meta<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<dyn Reader + 'a>, AssetReaderError>> {
+        self.inner.read_meta(path)
+    }
+
+    fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<PathStream>, AssetReaderError>> {
+        self.inner.read_directory(path)
+    }
+
+    fn is_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<bool, AssetReaderError>> {
+        self.inner.is_directory(path)
+    }
+
+    fn read_meta_bytes<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Vec<u8>, AssetReaderError>> {
+        self.inner.read_meta_bytes(path)
+    }
+
+    fn read_range<'a>(
+        &'a self,
+        path: &'a Path,
+        range: Range<u64>,
+    ) -> BoxedFuture<'a, Result<Box<dyn Reader + 'a>, AssetReaderError>> {
+        self.inner.read_range(path, range)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use mini_core::futures_lite::{future::block_on, AsyncReadExt, AsyncSeekExt};
+
+    use super::*;
+    use crate::io::VecReader;
+
+    struct CountingReader {
+        inner: VecReader,
+        poll_reads: Arc<AtomicUsize>,
+    }
+
+    impl AsyncRead for CountingReader {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut [u8],
+        ) -> Poll<futures_io::Result<usize>> {
+            self.poll_reads.fetch_add(1, Ordering::Relaxed);
+            Pin::new(&mut self.inner).poll_read(cx, buf)
+        }
+    }
+
+    impl AsyncSeek for CountingReader {
+        fn poll_seek(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            pos: SeekFrom,
+        ) -> Poll<std::io::Result<u64>> {
+            Pin::new(&mut self.inner).poll_seek(cx, pos)
+        }
+    }
+
+    impl Reader for CountingReader {}
+
+    fn counting_reader(bytes: Vec<u8>) -> (CountingReader, Arc<AtomicUsize>) {
+        let poll_reads = Arc::new(AtomicUsize::new(0));
+        let reader = CountingReader {
+            inner: VecReader::new(bytes),
+            poll_reads: poll_reads.clone(),
+        };
+        (reader, poll_reads)
+    }
+
+    fn read_in_small_chunks(reader: &mut (impl Reader + ?Sized)) -> Vec<u8> {
+        let mut out = Vec::new();
+        let mut chunk = [0; 16];
+        block_on(async {
+            loop {
+                let n = reader.read(&mut chunk).await.unwrap();
+                if n == 0 {
+                    break;
+                }
+                out.extend_from_slice(&chunk[..n]);
+            }
+        });
+        out
+    }
+
+    #[test]
+    fn test_fewer_poll_reads() {
+        let bytes = (0..1024 * 1024).map(|i| i as u8).collect::<Vec<_>>();
+
+        let (mut direct, direct_reads) = counting_reader(bytes.clone());
+        assert_eq!(read_in_small_chunks(&mut direct), bytes);
+
+        let (inner, buffered_reads) = counting_reader(bytes.clone());
+        let mut buffered = BufferedReader::new(inner);
+        assert_eq!(read_in_small_chunks(&mut buffered), bytes);
+
+        let direct_reads = direct_reads.load(Ordering::Relaxed);
+        let buffered_reads = buffered_reads.load(Ordering::Relaxed);
+        assert_eq!(buffered_reads, bytes.len() / DEFAULT_BUFFER_SIZE + 1);
+        assert!(buffered_reads * 1000 < direct_reads);
+    }
+
+    #[test]
+    fn test_read_to_end_and_seek() {
+        let bytes = (0..1000).map(|i| i as u8).collect::<Vec<_>>();
+        let (inner, _) = counting_reader(bytes.clone());
+        let mut reader = BufferedReader::with_capacity(64, inner);
+
+        block_on(async {
+            let mut head = [0; 10];
+            reader.read_exact(&mut head).await.unwrap();
+            assert_eq!(&head, &bytes[..10]);
+
+            // 相对位置需要扣除缓冲区中还没读取的数据
+            assert_eq!(reader.seek(SeekFrom::Current(5)).await.unwrap(), 15);
+
+            let mut rest = Vec::new();
+            let n = Reader::read_to_end(&mut reader, &mut rest).await.unwrap();
+            assert_eq!(n, bytes.len() - 15);
+            assert_eq!(rest, &bytes[15..]);
+        });
+    }
+}
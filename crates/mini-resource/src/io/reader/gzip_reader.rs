@@ -0,0 +1,256 @@
+use std::{
+    io::{SeekFrom, Write},
+    ops::Range,
+    path::Path,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use flate2::write::GzDecoder;
+use mini_core::{
+    future::BoxedFuture,
+    futures_io::{self, AsyncRead, AsyncSeek},
+    futures_lite::ready,
+    utils::cmp_strings_case_insensitive,
+};
+
+use super::{
+    reader::slice_range, AssetReaderError, ErasedAssetReader, PathStream, Reader, VecReader,
+};
+
+/// The extension of gzip compressed assets.
+pub const GZIP_EXTENSION: &str = "gz";
+
+const COMPRESSED_CHUNK_SIZE: usize = 8 * 1024;
+
+/// Wraps a [`Reader`] of gzip compressed bytes and decompresses them on the fly.
+///
+/// Raw gzip streams can't be seeked, so every decompressed byte is cached. Seeking backward
+/// reads from the cache, seeking forward decompresses until the target is reached.
+pub struct GzipReader<R> {
+    inner: R,
+    decoder: GzDecoder<Vec<u8>>,
+    chunk: Box<[u8]>,
+    finished: bool,
+    pos: usize,
+}
+
+impl<R: Reader> GzipReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            decoder: GzDecoder::new(Vec::new()),
+            chunk: vec![0; COMPRESSED_CHUNK_SIZE].into_boxed_slice(),
+            finished: false,
+            pos: 0,
+        }
+    }
+
+    /// The bytes decompressed so far.
+    pub fn decompressed(&self) -> &[u8] {
+        self.decoder.get_ref()
+    }
+
+    // 从底层 reader 读取一块压缩数据并解压到缓存中
+    fn poll_fill(&mut self, cx: &mut Context<'_>) -> Poll<futures_io::Result<()>> {
+        let n = ready!(Pin::new(&mut self.inner).poll_read(cx, &mut self.chunk))?;
+        if n == 0 {
+            self.decoder.try_finish()?;
+            self.finished = true;
+        } else {
+            self.decoder.write_all(&self.chunk[..n])?;
+        }
+        Poll::Ready(Ok(()))
+    }
+
+    // 解压直到缓存中至少有 `len` 个字节，或者数据已经读完
+    fn poll_fill_to(&mut self, cx: &mut Context<'_>, len: usize) -> Poll<futures_io::Result<()>> {
+        while self.decompressed().len() < len && !self.finished {
+            ready!(self.poll_fill(cx))?;
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<R: Reader> AsyncRead for GzipReader<R> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<futures_io::Result<usize>> {
+        let this = &mut *self;
+        let pos = this.pos;
+        ready!(this.poll_fill_to(cx, pos.saturating_add(1)))?;
+
+        let decompressed = this.decoder.get_ref();
+        let available = decompressed.get(pos..).unwrap_or_default();
+        let n = available.len().min(buf.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        this.pos += n;
+        Poll::Ready(Ok(n))
+    }
+}
+
+impl<R: Reader> AsyncSeek for GzipReader<R> {
+    fn poll_seek(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        pos: SeekFrom,
+    ) -> Poll<std::io::Result<u64>> {
+        let this = &mut *self;
+        let target = match pos {
+            SeekFrom::Start(offset) => Some(offset as i64),
+            SeekFrom::Current(offset) => (this.pos as i64).checked_add(offset),
+            SeekFrom::End(offset) => {
+                ready!(this.poll_fill_to(cx, usize::MAX))?;
+                (this.decompressed().len() as i64).checked_add(offset)
+            }
+        };
+
+        match target {
+            Some(target) if target >= 0 => {
+                ready!(this.poll_fill_to(cx, target as usize))?;
+                this.pos = target as usize;
+                Poll::Ready(Ok(target as u64))
+            }
+            _ => Poll::Ready(Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            ))),
+        }
+    }
+}
+
+impl<R: Reader> Reader for GzipReader<R> {}
+
+/// An [`ErasedAssetReader`] that transparently decompresses assets ending in `.gz`.
+/// Other assets are passed through unchanged.
+pub struct GzipAssetReader {
+    inner: Box<dyn ErasedAssetReader>,
+}
+
+impl GzipAssetReader {
+    pub fn new(inner: Box<dyn ErasedAssetReader>) -> Self {
+        Self { inner }
+    }
+}
+
+/// Returns true if the path ends with `.gz`.
+pub fn is_gzip_path(path: &Path) -> bool {
+    path.extension().is_some_and(|extension| {
+        cmp_strings_case_insensitive(extension.to_string_lossy(), GZIP_EXTENSION)
+    })
+}
+
+impl ErasedAssetReader for GzipAssetReader {
+    fn read<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<dyn Reader + 'a>, AssetReaderError>> {
+        Box::pin(async move {
+            let reader = self.inner.read(path).await?;
+            if is_gzip_path(path) {
+                Ok(Box::new(GzipReader::new(reader)) as Box<dyn Reader>)
+            } else {
+                Ok(reader)
+            }
+        })
+    }
+
+    fn read_meta<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<dyn Reader + 'a>, AssetReaderError>> {
+        self.inner.read_meta(path)
+    }
+
+    fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<PathStream>, AssetReaderError>> {
+        self.inner.read_directory(path)
+    }
+
+    fn is_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<bool, AssetReaderError>> {
+        self.inner.is_directory(path)
+    }
+
+    fn read_meta_bytes<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Vec<u8>, AssetReaderError>> {
+        self.inner.read_meta_bytes(path)
+    }
+
+    // 范围是解压后的字节，压缩的文件只能解压整个文件后截取
+    fn read_range<'a>(
+        &'a self,
+        path: &'a Path,
+        range: Range<u64>,
+    ) -> BoxedFuture<'a, Result<Box<dyn Reader + 'a>, AssetReaderError>> {
+        if !is_gzip_path(path) {
+            return self.inner.read_range(path, range);
+        }
+
+        Box::pin(async move {
+            let mut reader = self.read(path).await?;
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            Ok(Box::new(VecReader::new(slice_range(bytes, range))) as Box<dyn Reader>)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use flate2::{write::GzEncoder, Compression};
+    use mini_core::futures_lite::{future::block_on, AsyncReadExt, AsyncSeekExt};
+
+    use super::*;
+
+    fn gzip(bytes: &[u8]) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(bytes).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn test_is_gzip_path() {
+        assert!(is_gzip_path(Path::new("notes.txt.gz")));
+        assert!(is_gzip_path(Path::new("notes.txt.GZ")));
+        assert!(!is_gzip_path(Path::new("notes.txt")));
+        assert!(!is_gzip_path(Path::new("gz")));
+    }
+
+    #[test]
+    fn test_gzip_reader_seek() {
+        let bytes = (0..100_000).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+        let mut reader = GzipReader::new(VecReader::new(gzip(&bytes)));
+
+        block_on(async {
+            let mut head = [0; 10];
+            reader.read_exact(&mut head).await.unwrap();
+            assert_eq!(&head, &bytes[..10]);
+
+            assert_eq!(reader.seek(SeekFrom::Start(50_000)).await.unwrap(), 50_000);
+            reader.read_exact(&mut head).await.unwrap();
+            assert_eq!(&head, &bytes[50_000..50_010]);
+
+            // 向后 seek 时从缓存中读取
+            assert_eq!(reader.seek(SeekFrom::Current(-20)).await.unwrap(), 49_990);
+            let mut rest = Vec::new();
+            let n = Reader::read_to_end(&mut reader, &mut rest).await.unwrap();
+            assert_eq!(n, bytes.len() - 49_990);
+            assert_eq!(rest, &bytes[49_990..]);
+
+            assert_eq!(
+                reader.seek(SeekFrom::End(-1)).await.unwrap(),
+                bytes.len() as u64 - 1
+            );
+            assert!(reader.seek(SeekFrom::Current(-200_000)).await.is_err());
+        });
+    }
+}
@@ -1,7 +1,12 @@
+mod buffered_reader;
+mod gzip_reader;
+#[allow(clippy::module_inception)]
 mod reader;
 mod slice_reader;
 mod vec_reader;
 
+pub use buffered_reader::*;
+pub use gzip_reader::*;
 pub use reader::*;
 pub use slice_reader::*;
 pub use vec_reader::*;
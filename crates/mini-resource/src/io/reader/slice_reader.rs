@@ -50,31 +50,27 @@ impl<'a> AsyncSeek for SliceReader<'a> {
         _cx: &mut Context<'_>,
         pos: SeekFrom,
     ) -> Poll<std::io::Result<u64>> {
-        let result = match pos {
-            SeekFrom::Start(offset) => offset.try_into(),
-            SeekFrom::End(offset) => self.bytes.len().try_into().map(|len: i64| len - offset),
-            SeekFrom::Current(offset) => self
-                .bytes_read
-                .try_into()
-                .map(|bytes_read: i64| bytes_read + offset),
+        // 超出 i64 的范围或者移动到开头之前时返回错误
+        let new_pos = match pos {
+            SeekFrom::Start(offset) => i64::try_from(offset).ok(),
+            SeekFrom::End(offset) => i64::try_from(self.bytes.len())
+                .ok()
+                .and_then(|len| len.checked_add(offset)),
+            SeekFrom::Current(offset) => i64::try_from(self.bytes_read)
+                .ok()
+                .and_then(|bytes_read| bytes_read.checked_add(offset)),
         };
 
-        if let Ok(new_pos) = result {
-            if new_pos < 0 {
-                Poll::Ready(Err(std::io::Error::new(
-                    std::io::ErrorKind::InvalidInput,
-                    "seek position is out of range",
-                )))
-            } else {
+        match new_pos {
+            Some(new_pos) if new_pos >= 0 => {
                 self.bytes_read = new_pos as _;
 
                 Poll::Ready(Ok(new_pos as _))
             }
-        } else {
-            Poll::Ready(Err(std::io::Error::new(
+            _ => Poll::Ready(Err(std::io::Error::new(
                 std::io::ErrorKind::InvalidInput,
                 "seek position is out of range",
-            )))
+            ))),
         }
     }
 }
@@ -96,3 +92,26 @@ impl Reader for SliceReader<'_> {
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use mini_core::futures_lite::{future::block_on, AsyncSeekExt};
+
+    use super::*;
+
+    #[test]
+    fn test_slice_reader_seek_from_end() {
+        let bytes = (0..10).collect::<Vec<u8>>();
+        let mut reader = SliceReader::new(&bytes);
+
+        block_on(async {
+            assert_eq!(reader.seek(SeekFrom::End(-3)).await.unwrap(), 7);
+            let mut rest = Vec::new();
+            Reader::read_to_end(&mut reader, &mut rest).await.unwrap();
+            assert_eq!(rest, [7, 8, 9]);
+
+            assert!(reader.seek(SeekFrom::End(-11)).await.is_err());
+            assert!(reader.seek(SeekFrom::End(i64::MAX)).await.is_err());
+        });
+    }
+}
@@ -26,6 +26,15 @@ impl VecReader {
             bytes,
         }
     }
+
+    /// 数据的总字节数，和当前读取的位置无关
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
 }
 
 impl AsyncRead for VecReader {
@@ -50,31 +59,27 @@ impl AsyncSeek for VecReader {
         _cx: &mut Context<'_>,
         pos: SeekFrom,
     ) -> Poll<std::io::Result<u64>> {
-        let result = match pos {
-            SeekFrom::Start(offset) => offset.try_into(),
-            SeekFrom::End(offset) => self.bytes.len().try_into().map(|len: i64| len - offset),
-            SeekFrom::Current(offset) => self
-                .bytes_read
-                .try_into()
-                .map(|bytes_read: i64| bytes_read + offset),
+        // 超出 i64 的范围或者移动到开头之前时返回错误
+        let new_pos = match pos {
+            SeekFrom::Start(offset) => i64::try_from(offset).ok(),
+            SeekFrom::End(offset) => i64::try_from(self.bytes.len())
+                .ok()
+                .and_then(|len| len.checked_add(offset)),
+            SeekFrom::Current(offset) => i64::try_from(self.bytes_read)
+                .ok()
+                .and_then(|bytes_read| bytes_read.checked_add(offset)),
         };
 
-        if let Ok(new_pos) = result {
-            if new_pos < 0 {
-                Poll::Ready(Err(std::io::Error::new(
-                    std::io::ErrorKind::InvalidInput,
-                    "seek position is out of range",
-                )))
-            } else {
+        match new_pos {
+            Some(new_pos) if new_pos >= 0 => {
                 self.bytes_read = new_pos as _;
 
                 Poll::Ready(Ok(new_pos as _))
             }
-        } else {
-            Poll::Ready(Err(std::io::Error::new(
+            _ => Poll::Ready(Err(std::io::Error::new(
                 std::io::ErrorKind::InvalidInput,
                 "seek position is out of range",
-            )))
+            ))),
         }
     }
 }
@@ -87,6 +92,11 @@ impl Reader for VecReader {
         StackFuture::from(async {
             if self.bytes_read >= self.bytes.len() {
                 Ok(0)
+            } else if self.bytes_read == 0 && buf.is_empty() {
+                // 还没有读取过时直接复制整个 Vec，不需要逐段扩容
+                buf.clone_from(&self.bytes);
+                self.bytes_read = self.bytes.len();
+                Ok(buf.len())
             } else {
                 buf.extend_from_slice(&self.bytes[self.bytes_read..]);
                 let n = self.bytes.len() - self.bytes_read;
@@ -96,3 +106,56 @@ impl Reader for VecReader {
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use mini_core::futures_lite::{future::block_on, AsyncReadExt, AsyncSeekExt};
+
+    use super::*;
+
+    #[test]
+    fn test_vec_reader_seek() {
+        let mut reader = VecReader::new((0..10).collect());
+        assert_eq!(reader.len(), 10);
+        assert!(!reader.is_empty());
+        assert!(VecReader::new(Vec::new()).is_empty());
+
+        block_on(async {
+            let mut head = [0; 4];
+            reader.read_exact(&mut head).await.unwrap();
+            assert_eq!(head, [0, 1, 2, 3]);
+
+            // 相对当前位置向前移动
+            assert_eq!(reader.seek(SeekFrom::Current(-2)).await.unwrap(), 2);
+            let mut rest = Vec::new();
+            assert_eq!(
+                Reader::read_to_end(&mut reader, &mut rest).await.unwrap(),
+                8
+            );
+            assert_eq!(rest, (2..10).collect::<Vec<_>>());
+
+            assert!(reader.seek(SeekFrom::Current(-11)).await.is_err());
+
+            // 移动到末尾之后读取不到数据
+            assert_eq!(reader.seek(SeekFrom::Start(20)).await.unwrap(), 20);
+            let mut buf = [0; 4];
+            assert_eq!(reader.read(&mut buf).await.unwrap(), 0);
+            let mut rest = Vec::new();
+            assert_eq!(
+                Reader::read_to_end(&mut reader, &mut rest).await.unwrap(),
+                0
+            );
+
+            // 相对末尾的偏移是负数
+            assert!(reader.seek(SeekFrom::End(-11)).await.is_err());
+            assert_eq!(reader.seek(SeekFrom::End(-3)).await.unwrap(), 7);
+            let mut rest = vec![42];
+            assert_eq!(
+                Reader::read_to_end(&mut reader, &mut rest).await.unwrap(),
+                3
+            );
+            assert_eq!(rest, [42, 7, 8, 9]);
+            assert_eq!(reader.len(), 10);
+        });
+    }
+}
@@ -1,13 +1,26 @@
-use std::{collections::HashMap, fmt::Display, hash::Hash, time::Duration};
-
-use mini_core::{cow_arc::CowArc, thiserror::Error};
-
-use super::{ErasedAssetReader, ErasedAssetWriter};
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    fmt::Display,
+    hash::Hash,
+    path::{Path, PathBuf},
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc,
+    },
+};
+
+use mini_core::{cow_arc::CowArc, parking_lot::Mutex, thiserror::Error, tracing::warn};
+
+use super::{
+    is_gzip_path, BufferedAssetReader, ErasedAssetReader, ErasedAssetWriter, GzipAssetReader,
+};
 
 /// A reference to an "asset source", which maps to an [`AssetReader`] and/or [`AssetWriter`].
 ///
 /// * [`ResourceSourceId::Default`] corresponds to "default asset paths" that don't specify a source: `/path/to/asset.png`
 /// * [`ResourceSourceId::Name`] corresponds to asset paths that _do_ specify a source: `remote://path/to/asset.png`, where `remote` is the name.
+///
 /// 资源路径的映射
 #[derive(Default, Clone, Debug, Eq)]
 pub enum ResourceSourceId<'a> {
@@ -100,28 +113,87 @@ impl<'a> PartialEq for ResourceSourceId<'a> {
     }
 }
 
+/// A function producing new [`AssetReader`] instances for a source.
+pub type ReaderBuilder = Box<dyn FnMut() -> Box<dyn ErasedAssetReader> + Send + Sync>;
+
+/// A function producing new [`AssetWriter`] instances for a source. The flag asks the writer to create its root.
+pub type WriterBuilder = Box<dyn FnMut(bool) -> Option<Box<dyn ErasedAssetWriter>> + Send + Sync>;
+
+/// A function producing a new [`AssetWatcher`] for a source. Events are sent through the given [`Sender`].
+/// Returns [`None`] if the source can not be watched.
+pub type WatcherBuilder =
+    Box<dyn FnMut(Sender<AssetSourceEvent>) -> Option<Box<dyn AssetWatcher>> + Send + Sync>;
+
+/// Watches an asset source for changes. Watching stops when the watcher is dropped.
+pub trait AssetWatcher: Send + Sync + 'static {}
+
+/// A change in an asset source. Paths are relative to the root of the source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AssetSourceEvent {
+    AddedAsset(PathBuf),
+    ModifiedAsset(PathBuf),
+    RemovedAsset(PathBuf),
+}
+
 /// Metadata about an "asset source", such as how to construct the [`AssetReader`] and [`AssetWriter`] for the source,
 /// and whether or not the source is processed.
 #[derive(Default)]
 pub struct ResourceSourceBuilder {
-    pub reader: Option<Box<dyn FnMut() -> Box<dyn ErasedAssetReader> + Send + Sync>>,
-    pub writer: Option<Box<dyn FnMut(bool) -> Option<Box<dyn ErasedAssetWriter>> + Send + Sync>>,
+    pub reader: Option<ReaderBuilder>,
+    pub writer: Option<WriterBuilder>,
+    pub processed_reader: Option<ReaderBuilder>,
+    pub processed_writer: Option<WriterBuilder>,
+    pub watcher: Option<WatcherBuilder>,
 
     pub watch_warning: Option<&'static str>,
+
+    /// If set, readers of this source are wrapped in a [`BufferedReader`](super::BufferedReader) of this size.
+    pub read_buffer_size: Option<usize>,
+
+    /// If true, assets ending in `.gz` are decompressed by a [`GzipReader`](super::GzipReader).
+    pub gzip: bool,
 }
 
 impl ResourceSourceBuilder {
     /// Builds a new [`ResourceSource`] with the given `id`. If `watch` is true, the unprocessed source will watch for changes.
     /// If `watch_processed` is true, the processed source will watch for changes.
-    pub fn build(&mut self, id: ResourceSourceId<'static>) -> Option<ResourceSource> {
-        let reader = self.reader.as_mut()?();
+    pub fn build(&mut self, id: ResourceSourceId<'static>, watch: bool) -> Option<ResourceSource> {
+        let mut reader = self.reader.as_mut()?();
+        if let Some(buffer_size) = self.read_buffer_size {
+            reader = Box::new(BufferedAssetReader::new(reader, buffer_size));
+        }
+        if self.gzip {
+            reader = Box::new(GzipAssetReader::new(reader));
+        }
         let writer = self.writer.as_mut().and_then(|w| w(false));
+        let processed_reader = self.processed_reader.as_mut().map(|r| r());
+        let processed_writer = self.processed_writer.as_mut().and_then(|w| w(true));
         let mut source = ResourceSource {
             id: id.clone(),
             reader,
             writer,
+            processed_reader,
+            processed_writer,
+            watcher: None,
+            event_receiver: None,
+            gzip: self.gzip,
         };
 
+        if watch {
+            let (sender, receiver) = mpsc::channel();
+            match self.watcher.as_mut().and_then(|w| w(sender)) {
+                Some(watcher) => {
+                    source.watcher = Some(watcher);
+                    source.event_receiver = Some(Mutex::new(receiver));
+                }
+                None => {
+                    if let Some(warning) = self.watch_warning {
+                        warn!("{id} does not have an AssetWatcher configured. {warning}");
+                    }
+                }
+            }
+        }
+
         Some(source)
     }
 
@@ -143,6 +215,50 @@ impl ResourceSourceBuilder {
         self
     }
 
+    /// Will use the given `reader` function to construct processed [`AssetReader`] instances.
+    pub fn with_processed_reader(
+        mut self,
+        reader: impl FnMut() -> Box<dyn ErasedAssetReader> + Send + Sync + 'static,
+    ) -> Self {
+        self.processed_reader = Some(Box::new(reader));
+        self
+    }
+
+    /// Will use the given `writer` function to construct processed [`AssetWriter`] instances.
+    pub fn with_processed_writer(
+        mut self,
+        writer: impl FnMut(bool) -> Option<Box<dyn ErasedAssetWriter>> + Send + Sync + 'static,
+    ) -> Self {
+        self.processed_writer = Some(Box::new(writer));
+        self
+    }
+
+    /// Will use the given `watcher` function to construct unprocessed [`AssetWatcher`] instances.
+    pub fn with_watcher(
+        mut self,
+        watcher: impl FnMut(Sender<AssetSourceEvent>) -> Option<Box<dyn AssetWatcher>>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.watcher = Some(Box::new(watcher));
+        self
+    }
+
+    /// Wraps every reader of this source in a [`BufferedReader`](super::BufferedReader) with a buffer
+    /// of `buffer_size` bytes, see [`DEFAULT_BUFFER_SIZE`](super::DEFAULT_BUFFER_SIZE).
+    pub fn with_buffered_reads(mut self, buffer_size: usize) -> Self {
+        self.read_buffer_size = Some(buffer_size);
+        self
+    }
+
+    /// Transparently decompresses assets ending in `.gz`. The loader is chosen by the extension
+    /// before `.gz`, so `notes.txt.gz` is loaded by the `txt` loader.
+    pub fn with_gzip(mut self) -> Self {
+        self.gzip = true;
+        self
+    }
+
     /// Enables a warning for the unprocessed source watcher, which will print when watching is enabled and the unprocessed source doesn't have a watcher.
     pub fn with_watch_warning(mut self, warning: &'static str) -> Self {
         self.watch_warning = Some(warning);
@@ -153,12 +269,14 @@ impl ResourceSourceBuilder {
     /// For most platforms, this will use [`FileAssetReader`](crate::io::file::FileAssetReader) / [`FileAssetWriter`](crate::io::file::FileAssetWriter),
     /// but some platforms (such as Android) have their own default readers / writers / watchers.
     pub fn platform_default(path: &str) -> Self {
-        let default = Self::default()
+        Self::default()
             .with_reader(ResourceSource::get_default_reader(path.to_string()))
             .with_writer(ResourceSource::get_default_writer(path.to_string()))
-            .with_watch_warning(ResourceSource::get_default_watch_warning());
-
-        default
+            .with_watcher(ResourceSource::get_default_watcher(
+                path.to_string(),
+                super::DEFAULT_DEBOUNCE_WINDOW,
+            ))
+            .with_watch_warning(ResourceSource::get_default_watch_warning())
     }
 }
 
@@ -166,7 +284,7 @@ impl ResourceSourceBuilder {
 /// for a given asset source.
 #[derive(Default)]
 pub struct ResourceSourceBuilders {
-    sources: HashMap<CowArc<'static, str>, ResourceSourceBuilder>,
+    sources: BTreeMap<CowArc<'static, str>, ResourceSourceBuilder>,
     default: Option<ResourceSourceBuilder>,
 }
 
@@ -200,21 +318,22 @@ impl ResourceSourceBuilders {
 
     /// Builds a new [`ResourceSources`] collection. If `watch` is true, the unprocessed sources will watch for changes.
     /// If `watch_processed` is true, the processed sources will watch for changes.
-    pub fn build_sources(&mut self) -> ResourceSources {
-        let mut sources = HashMap::new();
+    pub fn build_sources(&mut self, watch: bool) -> ResourceSources {
+        let mut sources = BTreeMap::new();
         for (id, source) in &mut self.sources {
-            if let Some(data) = source.build(ResourceSourceId::Name(id.clone_owned())) {
-                sources.insert(id.clone_owned(), data);
+            if let Some(data) = source.build(ResourceSourceId::Name(id.clone_owned()), watch) {
+                sources.insert(id.clone_owned(), Arc::new(data));
             }
         }
 
         ResourceSources {
             sources,
-            default: self
-                .default
-                .as_mut()
-                .and_then(|p| p.build(ResourceSourceId::Default))
-                .expect(MISSING_DEFAULT_SOURCE),
+            default: Arc::new(
+                self.default
+                    .as_mut()
+                    .and_then(|p| p.build(ResourceSourceId::Default, watch))
+                    .expect(MISSING_DEFAULT_SOURCE),
+            ),
         }
     }
 
@@ -231,6 +350,11 @@ pub struct ResourceSource {
     id: ResourceSourceId<'static>,
     reader: Box<dyn ErasedAssetReader>,
     writer: Option<Box<dyn ErasedAssetWriter>>,
+    processed_reader: Option<Box<dyn ErasedAssetReader>>,
+    processed_writer: Option<Box<dyn ErasedAssetWriter>>,
+    watcher: Option<Box<dyn AssetWatcher>>,
+    event_receiver: Option<Mutex<Receiver<AssetSourceEvent>>>,
+    gzip: bool,
 }
 
 impl ResourceSource {
@@ -259,14 +383,53 @@ impl ResourceSource {
             .ok_or_else(|| MissingAssetWriterError(self.id.clone_owned()))
     }
 
+    /// Return's this source's processed [`AssetReader`], if it exists.
+    #[inline]
+    pub fn processed_reader(&self) -> Option<&dyn ErasedAssetReader> {
+        self.processed_reader.as_deref()
+    }
+
+    /// Return's this source's processed [`AssetWriter`], if it exists.
+    #[inline]
+    pub fn processed_writer(&self) -> Option<&dyn ErasedAssetWriter> {
+        self.processed_writer.as_deref()
+    }
+
+    /// Returns true if this source was built with watching enabled and has an [`AssetWatcher`].
+    #[inline]
+    pub fn is_watching(&self) -> bool {
+        self.watcher.is_some()
+    }
+
+    /// Returns the next change reported by this source's [`AssetWatcher`], if any.
+    pub fn try_recv_event(&self) -> Option<AssetSourceEvent> {
+        self.event_receiver.as_ref()?.lock().try_recv().ok()
+    }
+
+    /// Returns the path used to pick a loader for `path`. Sources built with
+    /// [`with_gzip`](ResourceSourceBuilder::with_gzip) drop the `.gz` extension, so the loader
+    /// is chosen by the extension before it. Other sources return `path` unchanged.
+    pub fn loader_path<'p>(&self, path: &'p Path) -> Cow<'p, Path> {
+        if self.gzip && is_gzip_path(path) {
+            Cow::Owned(path.with_extension(""))
+        } else {
+            Cow::Borrowed(path)
+        }
+    }
+
+    /// Returns true if this source has both a processed reader and writer, so assets with a
+    /// registered [`ResourceProcessor`](crate::processor::ResourceProcessor) are processed before loading.
+    #[inline]
+    pub fn should_process(&self) -> bool {
+        self.processed_reader.is_some() && self.processed_writer.is_some()
+    }
+
     /// Returns a builder function for this platform's default [`AssetReader`]. `path` is the relative path to
     /// the asset root.
     pub fn get_default_reader(
         _path: String,
     ) -> impl FnMut() -> Box<dyn ErasedAssetReader> + Send + Sync {
-        move || {
-            return Box::new(super::file::FileAssetReader::new(&_path));
-        }
+        move || Box::new(super::file::FileAssetReader::new(&_path))
     }
 
     /// Returns a builder function for this platform's default [`AssetWriter`]. `path` is the relative path to
@@ -275,10 +438,40 @@ impl ResourceSource {
         _path: String,
     ) -> impl FnMut(bool) -> Option<Box<dyn ErasedAssetWriter>> + Send + Sync {
         move |_create_root: bool| {
-            return Some(Box::new(super::file::FileAssetWriter::new(
+            Some(Box::new(super::file::FileAssetWriter::new(
                 &_path,
                 _create_root,
-            )));
+            )))
+        }
+    }
+
+    /// Returns a builder function for this platform's default [`AssetWatcher`]. `path` is the relative path to
+    /// the asset root. Modifications of the same file within `debounce_window` are reported once.
+    pub fn get_default_watcher(
+        _path: String,
+        _debounce_window: std::time::Duration,
+    ) -> impl FnMut(Sender<AssetSourceEvent>) -> Option<Box<dyn AssetWatcher>> + Send + Sync {
+        move |_sender: Sender<AssetSourceEvent>| {
+            #[cfg(all(
+                not(target_arch = "wasm32"),
+                not(target_os = "android"),
+                feature = "file_watcher"
+            ))]
+            {
+                let path = super::file::get_base_path().join(&_path);
+                match super::file::FileWatcher::new(
+                    path.clone(),
+                    _sender,
+                    super::file::DEFAULT_POLL_INTERVAL,
+                    _debounce_window,
+                ) {
+                    Ok(watcher) => return Some(Box::new(watcher) as Box<dyn AssetWatcher>),
+                    Err(e) => {
+                        warn!("Failed to watch {}: {e}", path.display());
+                    }
+                }
+            }
+            None
         }
     }
 
@@ -304,9 +497,12 @@ impl ResourceSource {
 }
 
 /// A collection of [`ResourceSource`]s.
+///
+/// Named sources are iterated in the order of their names, the default source always comes last.
+/// Sources are shared, so a source that is replaced stays alive until the loads using it finish.
 pub struct ResourceSources {
-    sources: HashMap<CowArc<'static, str>, ResourceSource>,
-    default: ResourceSource,
+    sources: BTreeMap<CowArc<'static, str>, Arc<ResourceSource>>,
+    default: Arc<ResourceSource>,
 }
 
 impl ResourceSources {
@@ -314,24 +510,32 @@ impl ResourceSources {
     pub fn get<'a, 'b>(
         &'a self,
         id: impl Into<ResourceSourceId<'b>>,
-    ) -> Result<&'a ResourceSource, MissingAssetSourceError> {
+    ) -> Result<&'a Arc<ResourceSource>, MissingAssetSourceError> {
         match id.into().into_owned() {
             ResourceSourceId::Default => Ok(&self.default),
             ResourceSourceId::Name(name) => self
                 .sources
                 .get(&name)
-                .ok_or_else(|| MissingAssetSourceError(ResourceSourceId::Name(name))),
+                .ok_or(MissingAssetSourceError(ResourceSourceId::Name(name))),
         }
     }
 
-    /// Iterates all asset sources in the collection (including the default source).
-    pub fn iter(&self) -> impl Iterator<Item = &ResourceSource> {
-        self.sources.values().chain(Some(&self.default))
+    /// Inserts `source` under its id, replacing the existing source with the same id.
+    pub fn insert(&mut self, source: ResourceSource) {
+        match source.id() {
+            ResourceSourceId::Default => self.default = Arc::new(source),
+            ResourceSourceId::Name(name) => {
+                self.sources.insert(name, Arc::new(source));
+            }
+        }
     }
 
-    /// Mutably iterates all asset sources in the collection (including the default source).
-    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut ResourceSource> {
-        self.sources.values_mut().chain(Some(&mut self.default))
+    /// Iterates all asset sources in the collection (including the default source).
+    pub fn iter(&self) -> impl Iterator<Item = &ResourceSource> {
+        self.sources
+            .values()
+            .chain(Some(&self.default))
+            .map(|source| &**source)
     }
 
     /// Iterates over the [`ResourceSourceId`] of every [`ResourceSource`] in the collection (including the default source).
@@ -365,3 +569,80 @@ pub struct MissingProcessedAssetWriterError(ResourceSourceId<'static>);
 
 const MISSING_DEFAULT_SOURCE: &str =
     "A default ResourceSource is required. Add one to `ResourceSourceBuilders`";
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::io::MemoryAssetReader;
+
+    #[test]
+    fn test_sources_iteration_order() {
+        let mut builders = ResourceSourceBuilders::default();
+        for name in ["remote", "embedded", "cache"] {
+            builders.insert(
+                ResourceSourceId::Name(name.into()),
+                ResourceSourceBuilder::default()
+                    .with_reader(|| Box::new(MemoryAssetReader::default())),
+            );
+        }
+        builders.insert(
+            ResourceSourceId::Default,
+            ResourceSourceBuilder::default().with_reader(|| Box::new(MemoryAssetReader::default())),
+        );
+        let sources = builders.build_sources(false);
+
+        let expected = [
+            ResourceSourceId::Name("cache".into()),
+            ResourceSourceId::Name("embedded".into()),
+            ResourceSourceId::Name("remote".into()),
+            ResourceSourceId::Default,
+        ];
+        for _ in 0..3 {
+            assert_eq!(sources.ids().collect::<Vec<_>>(), expected);
+            assert_eq!(
+                sources.iter().map(|source| source.id()).collect::<Vec<_>>(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_build_with_watch() {
+        struct TestWatcher;
+        impl AssetWatcher for TestWatcher {}
+
+        let build = |watch: bool| {
+            ResourceSourceBuilder::default()
+                .with_reader(|| Box::new(MemoryAssetReader::default()))
+                .with_watcher(|sender| {
+                    sender
+                        .send(AssetSourceEvent::ModifiedAsset("a.txt".into()))
+                        .unwrap();
+                    Some(Box::new(TestWatcher))
+                })
+                .build(ResourceSourceId::Default, watch)
+                .unwrap()
+        };
+
+        // 没有要求监听时不会创建监听器
+        let source = build(false);
+        assert!(!source.is_watching());
+        assert_eq!(source.try_recv_event(), None);
+
+        let source = build(true);
+        assert!(source.is_watching());
+        assert_eq!(
+            source.try_recv_event(),
+            Some(AssetSourceEvent::ModifiedAsset("a.txt".into()))
+        );
+        assert_eq!(source.try_recv_event(), None);
+
+        // 没有监听器时只会输出警告
+        let source = ResourceSourceBuilder::default()
+            .with_reader(|| Box::new(MemoryAssetReader::default()))
+            .with_watch_warning("no watcher")
+            .build(ResourceSourceId::Default, true)
+            .unwrap();
+        assert!(!source.is_watching());
+    }
+}
@@ -1,14 +1,22 @@
 pub mod error;
+pub mod folder;
 pub mod io;
 pub mod loader;
 pub mod manager;
 pub mod meta;
+pub mod preload;
+pub mod processor;
 pub mod resource;
+pub mod ron_loader;
 
 pub mod prelude {
     pub use crate::error::*;
+    pub use crate::folder::*;
     pub use crate::io::*;
     pub use crate::loader::*;
     pub use crate::manager::*;
+    pub use crate::preload::*;
+    pub use crate::processor::*;
     pub use crate::resource::*;
+    pub use crate::ron_loader::*;
 }
@@ -1,9 +1,11 @@
-use std::{error::Error, path::Path, sync::Arc};
+use std::{any::type_name, error::Error, path::Path, sync::Arc};
 
 use mini_core::{
+    cow_arc::CowArc,
     downcast::Downcast,
     future::{BoxedFuture, ConditionalSendFuture},
     prelude::TypeUuidProvider,
+    type_uuid::register_type_uuid,
     uuid::Uuid,
 };
 
@@ -12,7 +14,7 @@ use crate::{
     manager::ResourceManager,
     meta::{ResourceMeta, ResourceMetaDyn, ResourceSettings},
     prelude::Resource,
-    resource::{ErasedResourceData, ResourceData},
+    resource::{ErasedResourceData, ResourceData, UntypedResource},
 };
 
 #[derive(Default, Clone)]
@@ -21,7 +23,21 @@ pub struct ResourceLoaders {
 }
 
 impl ResourceLoaders {
+    /// 添加 loader，不同的数据类型使用同一个 uuid 时 panic，错误信息中包含两个类型的名称
     pub fn push<T: ResourceLoader>(&mut self, loader: T) {
+        let data_type_uuid = <T as ResourceLoader>::data_type_uuid();
+        if let Some(existing) = self.find_by_data_type(data_type_uuid) {
+            let data_type_name = type_name::<T::ResourceData>();
+            if existing.data_type_name() != data_type_name {
+                panic!(
+                    "{} and {} have the same type uuid {}",
+                    existing.data_type_name(),
+                    data_type_name,
+                    data_type_uuid
+                );
+            }
+        }
+        register_type_uuid::<T::ResourceData>();
         self.loaders.push(Arc::new(loader));
     }
 
@@ -29,27 +45,106 @@ impl ResourceLoaders {
         self.loaders
             .iter()
             .find(|loader| loader.supports_extension(extension))
-            .and_then(|loader| Some(loader.clone()))
+            .cloned()
     }
 
     pub fn find_loader(&self, path: &Path) -> Option<Arc<dyn ErasedResourceLoader>> {
         path.extension()
             .and_then(|extension| self.find(&extension.to_string_lossy()))
     }
+
+    /// 和 [`find_loader`](Self::find_loader) 相同，但是优先使用输出 `data_type_uuid` 的 loader，
+    /// 不同类型的 loader 可以使用同一个扩展名。
+    ///
+    /// 没有匹配的类型时使用第一个支持扩展名的 loader，带标签的子资源由文件的 loader 加载。
+    pub fn find_loader_for_type(
+        &self,
+        path: &Path,
+        data_type_uuid: Uuid,
+    ) -> Option<Arc<dyn ErasedResourceLoader>> {
+        let extension = path.extension()?.to_string_lossy();
+        let mut loaders = self
+            .loaders
+            .iter()
+            .filter(|loader| loader.supports_extension(&extension));
+        let first = loaders.next()?;
+        if first.data_type_uuid() == data_type_uuid {
+            return Some(first.clone());
+        }
+        Some(
+            loaders
+                .find(|loader| loader.data_type_uuid() == data_type_uuid)
+                .unwrap_or(first)
+                .clone(),
+        )
+    }
+
+    /// 所有 loader 支持的扩展名，去掉开头的 `.` 并转为小写，排序且不重复
+    pub fn supported_extensions(&self) -> Vec<String> {
+        let mut extensions = self
+            .loaders
+            .iter()
+            .flat_map(|loader| loader.extensions())
+            .map(|extension| extension.trim_start_matches('.').to_ascii_lowercase())
+            .collect::<Vec<_>>();
+        extensions.sort();
+        extensions.dedup();
+        extensions
+    }
+
+    //通过输出数据的类型查找 loader
+    pub fn find_by_data_type(&self, data_type_uuid: Uuid) -> Option<Arc<dyn ErasedResourceLoader>> {
+        self.loaders
+            .iter()
+            .find(|loader| loader.data_type_uuid() == data_type_uuid)
+            .cloned()
+    }
+}
+
+/// loader 能够输出的数据，供工具在加载之前检查兼容性。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LoaderCapabilities {
+    //支持输出的格式，例如纹理格式的名字
+    pub output_formats: Vec<String>,
+    //是否可以输出 mipmap
+    pub mipmaps: bool,
+}
+
+impl LoaderCapabilities {
+    pub fn with_output_formats<S: Into<String>>(
+        mut self,
+        formats: impl IntoIterator<Item = S>,
+    ) -> Self {
+        self.output_formats
+            .extend(formats.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn with_mipmaps(mut self, mipmaps: bool) -> Self {
+        self.mipmaps = mipmaps;
+        self
+    }
+
+    pub fn supports_output_format(&self, format: &str) -> bool {
+        self.output_formats.iter().any(|f| f == format)
+    }
 }
 
 pub struct LoadedResource<T: ResourceData> {
     pub(crate) value: T,
+    pub(crate) dependencies: Vec<UntypedResource>,
 }
 
 pub struct ErasedLoadedResource {
     pub(crate) value: Box<dyn ErasedResourceData>,
+    pub(crate) dependencies: Vec<UntypedResource>,
 }
 
 impl<R: ResourceData> From<LoadedResource<R>> for ErasedLoadedResource {
     fn from(resource: LoadedResource<R>) -> Self {
         ErasedLoadedResource {
             value: Box::new(resource.value),
+            dependencies: resource.dependencies,
         }
     }
 }
@@ -57,6 +152,8 @@ impl<R: ResourceData> From<LoadedResource<R>> for ErasedLoadedResource {
 pub struct LoadContext<'a> {
     pub(crate) resource_mananger: &'a ResourceManager,
     resource_path: ResourcePath<'static>,
+    //加载过程中加载的其他资源
+    dependencies: Vec<UntypedResource>,
 }
 
 impl<'a> LoadContext<'a> {
@@ -76,18 +173,46 @@ impl<'a> LoadContext<'a> {
         Self {
             resource_mananger,
             resource_path,
+            dependencies: vec![],
         }
     }
 
+    /// 加载并等待依赖的资源
     pub async fn load_sub_resource<'b, R: ResourceData>(
-        &self,
+        &mut self,
         path: impl Into<ResourcePath<'b>>,
     ) -> Resource<R> {
-        self.resource_mananger.load_async::<R>(path).await
+        let resource = self.resource_mananger.load_async::<R>(path).await;
+        self.dependencies.push(resource.untyped.clone());
+        resource
+    }
+
+    /// 开始加载依赖的资源，不等待加载完成
+    pub fn load<'b, R: ResourceData>(&mut self, path: impl Into<ResourcePath<'b>>) -> Resource<R> {
+        let resource = self.resource_mananger.load::<R>(path);
+        self.dependencies.push(resource.untyped.clone());
+        resource
+    }
+
+    pub fn dependencies(&self) -> &[UntypedResource] {
+        &self.dependencies
+    }
+
+    /// 添加文件中带标签的资源，加载 `path#label` 时会返回这个资源而不会再次读取文件
+    pub fn add_labeled_resource<R: ResourceData>(
+        &mut self,
+        label: impl Into<CowArc<'static, str>>,
+        data: R,
+    ) -> Resource<R> {
+        let path = self.resource_path.clone().with_label(label);
+        Resource::new(self.resource_mananger.commit_labeled(path, data))
     }
 
     pub fn finish<R: ResourceData>(self, value: R) -> LoadedResource<R> {
-        LoadedResource { value }
+        LoadedResource {
+            value,
+            dependencies: self.dependencies,
+        }
     }
 }
 
@@ -110,6 +235,11 @@ pub trait ResourceLoader: 'static + Send + Sync {
     fn data_type_uuid() -> Uuid {
         <Self::ResourceData as TypeUuidProvider>::type_uuid()
     }
+
+    //默认不提供任何信息
+    fn capabilities(&self) -> LoaderCapabilities {
+        LoaderCapabilities::default()
+    }
 }
 
 pub trait ErasedResourceLoader: 'static + Sync + Downcast + Send {
@@ -135,6 +265,11 @@ pub trait ErasedResourceLoader: 'static + Sync + Downcast + Send {
 
     fn data_type_uuid(&self) -> Uuid;
 
+    //输出数据的类型名称，用于报告 uuid 冲突
+    fn data_type_name(&self) -> &'static str;
+
+    fn capabilities(&self) -> LoaderCapabilities;
+
     fn default_meta_from_dyn(&self, meta: &dyn ResourceMetaDyn)
         -> Option<Box<dyn ResourceMetaDyn>>;
 }
@@ -160,7 +295,7 @@ where
                 .expect("AssetLoader settings should match the loader type");
             let asset = <T as ResourceLoader>::load(self, reader, settings, &mut load_context)
                 .await
-                .map_err(|e| Box::new(e))?;
+                .map_err(Box::new)?;
             Ok(load_context.finish(asset).into())
         })
     }
@@ -177,13 +312,21 @@ where
         <T as ResourceLoader>::data_type_uuid()
     }
 
+    fn data_type_name(&self) -> &'static str {
+        type_name::<T::ResourceData>()
+    }
+
+    fn capabilities(&self) -> LoaderCapabilities {
+        <T as ResourceLoader>::capabilities(self)
+    }
+
     fn default_meta_from_dyn(
         &self,
         meta: &dyn ResourceMetaDyn,
     ) -> Option<Box<dyn ResourceMetaDyn>> {
         meta.loader_settings().and_then(|settings| {
             ResourceMeta::<T>::new_settings(settings)
-                .and_then(|meta| Some(Box::new(meta) as Box<dyn ResourceMetaDyn>))
+                .map(|meta| Box::new(meta) as Box<dyn ResourceMetaDyn>)
         })
     }
 }
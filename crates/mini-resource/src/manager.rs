@@ -1,15 +1,72 @@
-use mini_core::{parking_lot::Mutex, prelude::FxHashMap};
+use mini_core::{
+    futures::future::join_all,
+    futures_io::{self, AsyncRead, AsyncSeek},
+    futures_lite::{future::block_on, StreamExt},
+    parking_lot::{Mutex, RwLock},
+    prelude::{
+        fnv1a, FxHashMap, FxHashSet, FxHasher, TimeToLive, TypeUuidProvider, FNV1A_OFFSET_BASIS,
+    },
+    tracing::{debug, error, info_span, warn, Instrument},
+    uuid::Uuid,
+};
 use mini_task::TaskPool;
-use std::sync::Arc;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    future::Future,
+    hash::{Hash, Hasher},
+    io::SeekFrom,
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+// wasm32 上没有系统时钟，`Instant::now` 会 panic
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
 
 use crate::{
     error::{LoadError, ResourceError},
-    io::{Reader, ResourcePath, ResourceSourceBuilders, ResourceSources},
-    loader::{ErasedResourceLoader, LoadContext, ResourceLoader, ResourceLoaders},
-    meta::{ResourceMetaDyn, ResourceMetas},
-    resource::{Resource, ResourceData, ResourceKind, ResourceState, UntypedResource},
+    folder::LoadedFolder,
+    io::{
+        AssetReaderError, AssetSourceEvent, AssetWriterError, MissingAssetSourceError, Reader,
+        ResourcePath, ResourceSource, ResourceSourceBuilder, ResourceSourceBuilders,
+        ResourceSourceId, ResourceSources, VecReader,
+    },
+    loader::{
+        ErasedResourceLoader, LoadContext, LoaderCapabilities, ResourceLoader, ResourceLoaders,
+    },
+    meta::{ResourceMetaDyn, ResourceMetas, ResourceSettings},
+    preload::PreloadHandle,
+    processor::{ErasedResourceProcessor, ResourceProcessor, ResourceProcessors},
+    resource::{
+        LoadState, RecursiveDependencyLoadState, Resource, ResourceData, ResourceKind,
+        ResourceState, UntypedResource,
+    },
+    ron_loader::RonLoader,
 };
 
+fn is_meta_path(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("meta"))
+}
+
+fn to_ron_bytes<T: Serialize>(value: &T) -> ron::Result<Vec<u8>> {
+    ron::ser::to_string_pretty(value, ron::ser::PrettyConfig::default()).map(String::into_bytes)
+}
+
+/// 某种资源类型的统计信息，见 [`ResourceManager::stats_by_type`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceTypeStats {
+    pub uuid: Uuid,
+    //没有加载完成并且没有对应的 loader 时为 `unknown`
+    pub name: &'static str,
+    pub count: usize,
+    //所有已加载资源的 [`ResourceData::approx_memory_size`] 之和
+    pub approx_bytes: usize,
+}
+
 #[derive(Clone)]
 pub struct ResourceManager {
     state: Arc<ResourceManagerState>,
@@ -17,8 +74,32 @@ pub struct ResourceManager {
 
 impl ResourceManager {
     pub fn new(task_pool: Arc<TaskPool>) -> Self {
+        let mut asset_source_builders = ResourceSourceBuilders::default();
+        asset_source_builders.init_default_source("assets");
+
+        Self::with_sources(task_pool, asset_source_builders)
+    }
+
+    /// 使用自定义的资源来源创建，必须包含默认来源。
+    pub fn with_sources(
+        task_pool: Arc<TaskPool>,
+        asset_source_builders: ResourceSourceBuilders,
+    ) -> Self {
+        Self::with_sources_and_watch(task_pool, asset_source_builders, false)
+    }
+
+    /// 和 [`with_sources`](Self::with_sources) 相同，`watch` 为 true 时资源来源会监听文件的变化
+    pub fn with_sources_and_watch(
+        task_pool: Arc<TaskPool>,
+        mut asset_source_builders: ResourceSourceBuilders,
+        watch: bool,
+    ) -> Self {
         Self {
-            state: Arc::new(ResourceManagerState::new(task_pool)),
+            state: Arc::new(ResourceManagerState::new(
+                task_pool,
+                &mut asset_source_builders,
+                watch,
+            )),
         }
     }
 
@@ -26,7 +107,7 @@ impl ResourceManager {
     where
         T: ResourceData,
     {
-        let untyped = self.load_untyped(path);
+        let untyped = self.load_untyped_with_type(path, Some(T::type_uuid()));
         Resource::new(untyped)
     }
 
@@ -34,25 +115,43 @@ impl ResourceManager {
         &self,
         path: &ResourcePath<'_>,
         kind: ResourceKind,
+    ) -> Result<UntypedResource, Arc<dyn ErasedResourceLoader>> {
+        self.load_built_in_with_type(path, kind, None)
+    }
+
+    //`data_type_uuid` 不为空时优先使用输出这个类型的 loader
+    fn load_built_in_with_type(
+        &self,
+        path: &ResourcePath<'_>,
+        kind: ResourceKind,
+        data_type_uuid: Option<Uuid>,
     ) -> Result<UntypedResource, Arc<dyn ErasedResourceLoader>> {
         {
             let built_in_resources = self.state.built_in_resources.lock();
-            if let Some(built_in_resource) = built_in_resources.get(&path) {
+            if let Some(built_in_resource) = built_in_resources.get(path) {
                 return Ok(built_in_resource.clone());
             }
         }
 
+        let loader_path = match self.state.get_source(path.source()) {
+            Ok(source) => source.loader_path(path.path()).into_owned(),
+            Err(_) => path.path().to_path_buf(),
+        };
         let loaders = self.state.loaders.lock();
+        let loader = match data_type_uuid {
+            Some(data_type_uuid) => loaders.find_loader_for_type(&loader_path, data_type_uuid),
+            None => loaders.find_loader(&loader_path),
+        };
 
-        if let Some(loader) = loaders.find_loader(&path.path()) {
-            return Err(loader);
+        if let Some(loader) = loader {
+            Err(loader)
         } else {
             let err = LoadError::new(format!("There's no resource loader for {kind} resource!",));
-            return Ok(UntypedResource::new_load_error(
+            Ok(UntypedResource::new_load_error(
                 kind,
                 err,
                 Default::default(),
-            ));
+            ))
         }
     }
 
@@ -64,7 +163,7 @@ impl ResourceManager {
         let path: ResourcePath<'static> = path.into_owned();
 
         let kind = ResourceKind::External(path.clone());
-        let loader = match self.load_built_in(&path, kind.clone()) {
+        let loader = match self.load_built_in_with_type(&path, kind.clone(), Some(T::type_uuid())) {
             Ok(resource) => {
                 return Resource::new(resource);
             }
@@ -72,20 +171,52 @@ impl ResourceManager {
             Err(loader) => loader,
         };
 
-        let resource = UntypedResource::new_pending(kind, loader.data_type_uuid());
+        let (resource, is_new) = self.get_or_insert_pending(&path, &loader);
 
-        self.load_internal(path, resource.clone(), loader).await;
+        if is_new {
+            let _ = self.load_internal(path, resource.clone(), loader).await;
+        } else {
+            // 已经在加载中，等待加载完成
+            let _ = resource.clone().await;
+        }
 
         Resource::new(resource)
     }
 
+    /// 在当前线程上加载资源，加载完成或者失败后返回，用于工具和测试。
+    ///
+    /// 不能在 [`TaskPool`] 的任务中调用，阻塞线程池的线程可能导致死锁。
+    #[allow(clippy::result_large_err)]
+    pub fn load_sync<'a, T: ResourceData>(
+        &self,
+        path: impl Into<ResourcePath<'a>>,
+    ) -> Result<Resource<T>, ResourceError> {
+        let path: ResourcePath<'static> = path.into().into_owned();
+        let resource = block_on(self.load_async::<T>(path.clone()));
+        if let ResourceState::LoadError { ref error } = resource.untyped.0.lock().state {
+            return Err(ResourceError::Load {
+                path,
+                error: error.clone(),
+            });
+        }
+        Ok(resource)
+    }
+
     pub fn load_untyped<'a>(&self, path: impl Into<ResourcePath<'a>>) -> UntypedResource {
+        self.load_untyped_with_type(path, None)
+    }
+
+    fn load_untyped_with_type<'a>(
+        &self,
+        path: impl Into<ResourcePath<'a>>,
+        data_type_uuid: Option<Uuid>,
+    ) -> UntypedResource {
         let path: ResourcePath<'a> = path.into();
         let path: ResourcePath<'static> = path.into_owned();
 
         let kind = ResourceKind::External(path.clone());
 
-        let loader = match self.load_built_in(&path, kind.clone()) {
+        let loader = match self.load_built_in_with_type(&path, kind.clone(), data_type_uuid) {
             Ok(resource) => {
                 return resource;
             }
@@ -93,51 +224,758 @@ impl ResourceManager {
             Err(loader) => loader,
         };
 
-        let resource = UntypedResource::new_pending(kind, loader.data_type_uuid());
+        let (resource, is_new) = self.get_or_insert_pending(&path, &loader);
 
-        self.spawn_loading_task(path, resource.clone(), loader, false);
+        if is_new {
+            self.spawn_loading_task(path, resource.clone(), loader, false);
+        }
 
         resource
     }
 
+    /// 加载目录和子目录中所有有 loader 的文件，所有文件加载完成后目录资源才会完成加载。
+    pub fn load_folder<'a>(&self, path: impl Into<ResourcePath<'a>>) -> Resource<LoadedFolder> {
+        self.load_folder_filtered(path, |_| true)
+    }
+
+    /// 和 [`load_folder`](Self::load_folder) 相同，但是只加载满足 `predicate` 的文件，
+    /// 其余文件不会被读取。
+    ///
+    /// 可以使用 [`glob`](crate::io::glob) 按照通配符过滤。
+    pub fn load_folder_filtered<'a>(
+        &self,
+        path: impl Into<ResourcePath<'a>>,
+        predicate: impl Fn(&ResourcePath) -> bool + Send + Sync + 'static,
+    ) -> Resource<LoadedFolder> {
+        let path: ResourcePath<'static> = path.into().into_owned();
+        let folder = UntypedResource::new_pending(
+            ResourceKind::External(path.clone()),
+            LoadedFolder::type_uuid(),
+        );
+
+        let resource_manager = self.clone();
+        let folder_clone = folder.clone();
+        self.task_pool().spawn_task(async move {
+            let files = match resource_manager.read_folder(&path, &predicate).await {
+                Ok(files) => files,
+                Err(e) => {
+                    folder_clone.commit_error(e);
+                    return;
+                }
+            };
+
+            let resources = files
+                .into_iter()
+                .map(|file| resource_manager.load_untyped(file))
+                .collect::<Vec<_>>();
+            // 加载失败的子资源也算作完成
+            for resource in resources.iter() {
+                let _ = resource.clone().await;
+            }
+            folder_clone.commit_ok(LoadedFolder { resources });
+        });
+
+        Resource::new(folder)
+    }
+
+    /// 递归读取目录，返回有 loader 并且满足 `predicate` 的文件，按照路径排序
+    async fn read_folder(
+        &self,
+        path: &ResourcePath<'_>,
+        predicate: &(dyn Fn(&ResourcePath) -> bool + Send + Sync),
+    ) -> Result<Vec<ResourcePath<'static>>, ResourceError> {
+        let source = self.state.get_source(path.source())?;
+        let reader = source.reader();
+
+        let mut folders = vec![path.path().to_path_buf()];
+        let mut files = vec![];
+        while let Some(folder) = folders.pop() {
+            let mut entries = reader.read_directory(&folder).await?;
+            while let Some(entry) = entries.next().await {
+                if is_meta_path(&entry) {
+                    continue;
+                }
+
+                if reader.is_directory(&entry).await? {
+                    folders.push(entry);
+                    continue;
+                }
+
+                let file = ResourcePath::from_path(&entry)
+                    .into_owned()
+                    .with_source(path.source().clone_owned());
+                if predicate(&file)
+                    && self
+                        .state
+                        .loaders
+                        .lock()
+                        .find_loader(&source.loader_path(&entry))
+                        .is_some()
+                {
+                    files.push(file);
+                }
+            }
+        }
+
+        files.sort_by(|a, b| a.path().cmp(b.path()));
+        Ok(files)
+    }
+
+    /// 读取目录中满足 `predicate` 的直接子项，不会进入子目录，也不包含 meta 文件，按照路径排序。
+    ///
+    /// 可以使用 [`with_extensions`](crate::io::with_extensions) 按扩展名过滤。
+    pub async fn read_directory_filtered<'a>(
+        &self,
+        path: impl Into<ResourcePath<'a>>,
+        predicate: impl Fn(&Path) -> bool,
+    ) -> Result<Vec<PathBuf>, ResourceError> {
+        let path = path.into();
+        let source = self.state.get_source(path.source())?;
+        let reader = source.reader();
+
+        let mut entries = reader.read_directory(path.path()).await?;
+        let mut paths = vec![];
+        while let Some(entry) = entries.next().await {
+            if !is_meta_path(&entry) && predicate(&entry) {
+                paths.push(entry);
+            }
+        }
+
+        paths.sort();
+        Ok(paths)
+    }
+
+    /// 注册内置资源，之后加载 `path` 会直接返回这个资源。
+    ///
+    /// 每个路径只会注册一次，路径已经注册过时丢弃 `data` 并返回已有的资源。
+    pub fn add_built_in<'a, T: ResourceData>(
+        &self,
+        path: impl Into<ResourcePath<'a>>,
+        data: T,
+    ) -> Resource<T> {
+        let path: ResourcePath<'static> = path.into().into_owned();
+        let resource = self
+            .state
+            .built_in_resources
+            .lock()
+            .entry(path)
+            .or_insert_with_key(|path| {
+                UntypedResource::new_ok(ResourceKind::External(path.clone()), data)
+            })
+            .clone();
+        Resource::new(resource)
+    }
+
+    /// 按内容注册内置资源，类型和内容都相同的数据共享同一个嵌入资源。
+    ///
+    /// 适合很多对象使用同一份默认数据的情况，例如默认的白色纹理。`path` 已经注册过时返回已有的资源。
+    pub fn add_built_in_dedup<'a, T: ResourceData + Hash + PartialEq>(
+        &self,
+        path: impl Into<ResourcePath<'a>>,
+        data: T,
+    ) -> Resource<T> {
+        let path: ResourcePath<'static> = path.into().into_owned();
+        let mut built_in_resources = self.state.built_in_resources.lock();
+        if let Some(resource) = built_in_resources.get(&path) {
+            return Resource::new(resource.clone());
+        }
+
+        let mut hasher = FxHasher::default();
+        data.hash(&mut hasher);
+        let key = (T::type_uuid(), hasher.finish());
+
+        let mut embedded_resources = self.state.embedded_resources.lock();
+        let candidates = embedded_resources.entry(key).or_default();
+        // 哈希相同时再比较内容，避免哈希冲突或者数据被修改后误用
+        let shared = candidates
+            .iter()
+            .find(|candidate| {
+                Resource::<T>::new((*candidate).clone())
+                    .try_data_ref()
+                    .is_ok_and(|existing| *existing == data)
+            })
+            .cloned();
+        let resource = match shared {
+            Some(resource) => resource,
+            None => {
+                let resource = UntypedResource::new_ok(ResourceKind::Embedded, data);
+                candidates.push(resource.clone());
+                resource
+            }
+        };
+
+        built_in_resources.insert(path, resource.clone());
+        Resource::new(resource)
+    }
+
+    /// 同一个路径只会创建一个资源，返回的 bool 表示资源是否是新创建的
+    fn get_or_insert_pending(
+        &self,
+        path: &ResourcePath<'static>,
+        loader: &Arc<dyn ErasedResourceLoader>,
+    ) -> (UntypedResource, bool) {
+        let mut resources = self.state.resources.lock();
+        if let Some(resource) = resources.get(path) {
+            return (resource.clone(), false);
+        }
+
+        let resource = UntypedResource::new_pending(
+            ResourceKind::External(path.clone()),
+            loader.data_type_uuid(),
+        );
+        resources.insert(path.clone(), resource.clone());
+        (resource, true)
+    }
+
+    /// 取消正在加载的资源，返回资源是否还在加载。
+    ///
+    /// 资源会立即从缓存中移除，之后加载同一个路径会重新开始加载。加载任务在读取文件和
+    /// loader 完成后检查取消标记，向资源提交 [`LoadError::cancelled`]，不会提交数据。
+    pub fn cancel_load(&self, resource: &UntypedResource) -> bool {
+        {
+            let mut header = resource.0.lock();
+            if !matches!(header.state, ResourceState::Pending { .. }) {
+                return false;
+            }
+            header.cancelled = true;
+        }
+
+        let mut ttls = self.state.resource_ttls.lock();
+        self.state.resources.lock().retain(|path, tracked| {
+            if Arc::ptr_eq(&tracked.0, &resource.0) {
+                ttls.remove(path);
+                return false;
+            }
+            true
+        });
+        true
+    }
+
+    /// 设置缓存中资源的存活时间，只对之后开始计时的资源生效
+    pub fn set_default_ttl(&self, ttl: Duration) {
+        *self.state.default_ttl.lock() = TimeToLive(ttl.as_secs_f32());
+    }
+
+    /// 更新缓存中资源的存活时间，只被缓存持有的资源在存活时间耗尽后从缓存中移除。
+    ///
+    /// 资源再次被使用时存活时间会重置。
+    pub fn update_cache(&self, dt: Duration) {
+        let default_ttl = *self.state.default_ttl.lock();
+        let mut ttls = self.state.resource_ttls.lock();
+        let mut content_hashes = self.state.content_hashes.lock();
+        self.state.resources.lock().retain(|path, resource| {
+            if Arc::strong_count(&resource.0) > 1 {
+                ttls.remove(path);
+                return true;
+            }
+
+            let ttl = ttls.entry(path.clone()).or_insert(default_ttl);
+            **ttl -= dt.as_secs_f32();
+            if **ttl > 0.0 {
+                return true;
+            }
+            ttls.remove(path);
+            content_hashes.remove(path);
+            false
+        });
+    }
+
+    /// 按照类型统计缓存中的资源和内置资源，按照类型名称排序
+    pub fn stats_by_type(&self) -> Vec<ResourceTypeStats> {
+        let mut resources = self
+            .state
+            .resources
+            .lock()
+            .values()
+            .cloned()
+            .collect::<Vec<_>>();
+        resources.extend(self.state.built_in_resources.lock().values().cloned());
+
+        let mut visited = FxHashSet::default();
+        let mut stats = FxHashMap::<Uuid, ResourceTypeStats>::default();
+        for resource in resources {
+            // 同一个资源可能同时是内置资源
+            if !visited.insert(Arc::as_ptr(&resource.0)) {
+                continue;
+            }
+
+            let header = resource.0.lock();
+            let entry = stats
+                .entry(header.type_uuid)
+                .or_insert_with(|| ResourceTypeStats {
+                    uuid: header.type_uuid,
+                    name: "unknown",
+                    count: 0,
+                    approx_bytes: 0,
+                });
+            entry.count += 1;
+            if let ResourceState::Ok(ref data) = header.state {
+                entry.name = data.type_name();
+                entry.approx_bytes += data.approx_memory_size();
+            }
+        }
+
+        let loaders = self.state.loaders.lock();
+        let mut stats = stats
+            .into_values()
+            .map(|mut stats| {
+                if stats.name == "unknown" {
+                    if let Some(loader) = loaders.find_by_data_type(stats.uuid) {
+                        stats.name = loader.data_type_name();
+                    }
+                }
+                stats
+            })
+            .collect::<Vec<_>>();
+        stats.sort_by(|a, b| a.name.cmp(b.name).then(a.uuid.cmp(&b.uuid)));
+        stats
+    }
+
+    /// 已经加载或者正在加载的资源
+    pub fn get_untyped<'a>(&self, path: impl Into<ResourcePath<'a>>) -> Option<UntypedResource> {
+        self.state.resources.lock().get(&path.into()).cloned()
+    }
+
+    /// 资源的加载状态。
+    ///
+    /// 没有完成的资源只有在资源管理器中才是 `Loading`，否则没有任务会完成它，返回 `NotLoaded`。
+    pub fn get_load_state(&self, resource: &UntypedResource) -> LoadState {
+        let path = {
+            let header = resource.0.lock();
+            match header.state {
+                ResourceState::Ok(_) => return LoadState::Loaded,
+                ResourceState::LoadError { ref error } => return LoadState::Failed(error.clone()),
+                ResourceState::Pending { .. } => {}
+            }
+            match header.kind {
+                ResourceKind::External(ref path) => path.clone(),
+                // 缓存按照路径保存资源，嵌入的资源不会在缓存中
+                ResourceKind::Embedded => return LoadState::NotLoaded,
+            }
+        };
+
+        let is_tracked = |resources: &FxHashMap<ResourcePath<'static>, UntypedResource>| {
+            resources
+                .get(&path)
+                .is_some_and(|tracked| Arc::ptr_eq(&tracked.0, &resource.0))
+        };
+        if is_tracked(&self.state.resources.lock())
+            || is_tracked(&self.state.built_in_resources.lock())
+        {
+            LoadState::Loading
+        } else {
+            LoadState::NotLoaded
+        }
+    }
+
+    /// 缓存中还在加载的资源数量，可以用于显示加载进度
+    pub fn loading_count(&self) -> usize {
+        self.state
+            .resources
+            .lock()
+            .values()
+            .filter(|resource| matches!(resource.0.lock().state, ResourceState::Pending { .. }))
+            .count()
+    }
+
+    /// 文件中通过 [`LoadContext::add_labeled_resource`] 添加的标签，按添加的顺序返回
+    pub fn labels_for(&self, base_path: &ResourcePath) -> Vec<String> {
+        self.state
+            .labels
+            .lock()
+            .get(&base_path.without_label())
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// 将 `data` 序列化为 ron 并写入 `path`，来源没有 writer 时返回 [`MissingAssetWriterError`](crate::io::MissingAssetWriterError)
+    pub async fn save<'a, T: ResourceData + Serialize>(
+        &self,
+        path: impl Into<ResourcePath<'a>>,
+        data: &T,
+    ) -> Result<(), ResourceError> {
+        let path = path.into();
+        let bytes = to_ron_bytes(data).map_err(|err| ResourceError::Serialize {
+            path: path.clone_owned(),
+            message: err.to_string(),
+        })?;
+        let source = self.state.get_source(path.source())?;
+        let writer = source.writer()?;
+        writer.write_bytes(path.path(), &bytes).await?;
+        Ok(())
+    }
+
+    /// 将 loader 的设置序列化为 ron 并写入 `path` 的 meta 文件
+    pub async fn save_meta<'a, S: ResourceSettings + Serialize>(
+        &self,
+        path: impl Into<ResourcePath<'a>>,
+        settings: &S,
+    ) -> Result<(), ResourceError> {
+        let path = path.into();
+        let bytes = to_ron_bytes(settings).map_err(|err| ResourceError::Serialize {
+            path: path.clone_owned(),
+            message: err.to_string(),
+        })?;
+        let source = self.state.get_source(path.source())?;
+        let writer = source.writer()?;
+        writer.write_meta_bytes(path.path(), &bytes).await?;
+        Ok(())
+    }
+
+    /// 移动资源和它的 meta 文件，已经加载的资源会更新为新的路径。
+    ///
+    /// 两个路径必须属于同一个资源来源。
+    pub async fn rename<'a, 'b>(
+        &self,
+        from: impl Into<ResourcePath<'a>>,
+        to: impl Into<ResourcePath<'b>>,
+    ) -> Result<(), ResourceError> {
+        let from: ResourcePath<'static> = from.into().into_owned();
+        let to: ResourcePath<'static> = to.into().into_owned();
+
+        if from.source() != to.source() {
+            return Err(ResourceError::RenameAcrossSources { from, to });
+        }
+
+        let source = self.state.get_source(from.source())?;
+        let writer = source.writer()?;
+        writer.rename(from.path(), to.path()).await?;
+        // 没有 meta 文件的资源不需要移动 meta
+        match writer.rename_meta(from.path(), to.path()).await {
+            Err(AssetWriterError::Io(err)) if err.kind() == std::io::ErrorKind::NotFound => {}
+            result => result?,
+        }
+
+        let mut resources = self.state.resources.lock();
+        for new_path in rename_entries(&mut resources, &from, &to) {
+            resources[&new_path].0.lock().kind = ResourceKind::External(new_path.clone());
+        }
+        drop(resources);
+        // 缓存的存活时间和内容哈希跟随资源一起移动
+        rename_entries(&mut self.state.resource_ttls.lock(), &from, &to);
+        rename_entries(&mut self.state.content_hashes.lock(), &from, &to);
+
+        let mut labels = self.state.labels.lock();
+        if let Some(file_labels) = labels.remove(&from.without_label().into_owned()) {
+            labels.insert(to.without_label().into_owned(), file_labels);
+        }
+
+        Ok(())
+    }
+
+    /// 读取 `path` 的 meta 和内容，`source` 为 `path` 所属的资源来源，
+    /// 可以通过 [`get_source`](Self::get_source) 获取
     pub async fn get_meta_and_reader<'a>(
         &'a self,
+        source: &'a ResourceSource,
         path: &'a ResourcePath<'_>,
         loader: &'a Arc<dyn ErasedResourceLoader>,
     ) -> Result<(Box<dyn ResourceMetaDyn>, Box<dyn Reader + 'a>), ResourceError> {
-        let value = self.state.get_meta_and_reader(&path, &loader).await?;
+        let value = self.state.get_meta_and_reader(source, path, loader).await?;
 
         Ok(value)
     }
 
+    /// 加载资源并提交结果，成功时返回读取的字节数。
+    ///
+    /// 带标签的路径会先加载所在的文件，标签对应的资源由 loader 通过
+    /// [`LoadContext::add_labeled_resource`] 提交，文件中没有这个标签时提交 [`LoadError`]。
+    ///
+    /// 加载过程在 `load` span 中执行，完成或者失败时输出耗时，wasm32 上不统计耗时。
     async fn load_internal(
         &self,
         path: ResourcePath<'static>,
         resource: UntypedResource,
         loader: Arc<dyn ErasedResourceLoader>,
-    ) {
-        let (meta, mut reader) = match self.get_meta_and_reader(&path, &loader).await {
+    ) -> Result<u64, LoadError> {
+        let span = info_span!("load", path = %path);
+        async move {
+            #[cfg(not(target_arch = "wasm32"))]
+            let start = Instant::now();
+            let result = self.load_path(path, resource, loader).await;
+            #[cfg(not(target_arch = "wasm32"))]
+            let elapsed = Some(start.elapsed());
+            #[cfg(target_arch = "wasm32")]
+            let elapsed: Option<Duration> = None;
+            match &result {
+                Ok(bytes) => debug!(bytes, ?elapsed, "resource loaded"),
+                Err(error) if error.is_cancelled() => debug!(?elapsed, "resource load cancelled"),
+                Err(error) => error!(?error, ?elapsed, "failed to load resource"),
+            }
+            result
+        }
+        .instrument(span)
+        .await
+    }
+
+    async fn load_path(
+        &self,
+        path: ResourcePath<'static>,
+        resource: UntypedResource,
+        loader: Arc<dyn ErasedResourceLoader>,
+    ) -> Result<u64, LoadError> {
+        if path.label().is_none() {
+            return self.load_file(path, resource, loader).await;
+        }
+
+        let file_path = path.without_label().into_owned();
+        let (file, is_new) = self.get_or_insert_pending(&file_path, &loader);
+        let result = if is_new {
+            self.load_file(file_path, file, loader).await
+        } else {
+            file.await.map(|_| 0)
+        };
+
+        let mut guard = resource.0.lock();
+        if let ResourceState::Pending { .. } = guard.state {
+            let error = match result {
+                _ if guard.cancelled => LoadError::cancelled(),
+                Err(error) => error,
+                Ok(_) => LoadError::new(format!("There's no labeled resource {path}!")),
+            };
+            guard
+                .state
+                .commit(ResourceState::new_load_error(error.clone()));
+            return Err(error);
+        }
+        result
+    }
+
+    /// 提交文件中带标签的资源，已经存在的句柄会被更新
+    pub(crate) fn commit_labeled<T: ResourceData>(
+        &self,
+        path: ResourcePath<'static>,
+        data: T,
+    ) -> UntypedResource {
+        if let Some(label) = path.label() {
+            let mut labels = self.state.labels.lock();
+            let file_labels = labels.entry(path.without_label().into_owned()).or_default();
+            if !file_labels.iter().any(|existing| existing == label) {
+                file_labels.push(label.to_string());
+            }
+        }
+
+        let mut resources = self.state.resources.lock();
+        if let Some(resource) = resources.get(&path) {
+            resource.commit_ok(data);
+            return resource.clone();
+        }
+
+        let resource = UntypedResource::new_ok(ResourceKind::External(path.clone()), data);
+        resources.insert(path, resource.clone());
+        resource
+    }
+
+    async fn load_file(
+        &self,
+        path: ResourcePath<'static>,
+        resource: UntypedResource,
+        loader: Arc<dyn ErasedResourceLoader>,
+    ) -> Result<u64, LoadError> {
+        let source = match self.get_source(path.source()) {
+            Ok(source) => source,
+            Err(e) => {
+                let error = LoadError::new(ResourceError::from(e));
+                resource.commit_load_error(error.clone());
+                return Err(error);
+            }
+        };
+        let meta_and_reader = self.get_meta_and_reader(&source, &path, &loader).await;
+        if resource.is_cancelled() {
+            let error = LoadError::cancelled();
+            resource.commit_load_error(error.clone());
+            return Err(error);
+        }
+        let (meta, reader) = match meta_and_reader {
             Ok((meta, reader)) => (meta, reader),
             Err(e) => {
-                return resource.commit_error(e);
+                let error = LoadError::new(e);
+                resource.commit_load_error(error.clone());
+                return Err(error);
             }
         };
 
+        let mut reader = ByteCountReader::new(reader);
         let load_context = LoadContext::new(self, path.clone());
-        match loader.load(&mut (*reader), meta, load_context).await {
+        match loader.load(&mut reader, meta, load_context).await {
             Err(e) => {
-                return resource.commit_error(e);
+                let error = LoadError::new(e);
+                resource.commit_load_error(error.clone());
+                Err(error)
             }
 
             Ok(loaded_resource) => {
                 let mut mutex_guard = resource.0.lock();
+                if mutex_guard.cancelled {
+                    let error = LoadError::cancelled();
+                    mutex_guard
+                        .state
+                        .commit(ResourceState::new_load_error(error.clone()));
+                    return Err(error);
+                }
                 assert_eq!(mutex_guard.type_uuid, loaded_resource.value.type_uuid());
                 assert!(mutex_guard.kind.is_external());
+                mutex_guard.dependencies = loaded_resource.dependencies;
                 mutex_guard
                     .state
                     .commit(ResourceState::Ok(loaded_resource.value));
+                drop(mutex_guard);
+
+                if let Some(hash) = reader.content_hash() {
+                    self.state.content_hashes.lock().insert(path.clone(), hash);
+                }
+                Ok(reader.bytes_read)
+            }
+        }
+    }
+
+    /// 重新加载缓存中的资源，返回是否提交了新的数据。
+    ///
+    /// 文件内容的哈希和上次加载时相同时不会重新加载，编辑器保存没有修改的文件时不会重复上传数据。
+    /// 重新加载失败时保留原来的数据。
+    pub async fn reload<'a>(&self, path: impl Into<ResourcePath<'a>>) -> Result<bool, LoadError> {
+        let path: ResourcePath<'static> = path.into().into_owned();
+        // 没有加载完成的资源会由加载任务提交
+        let Some(resource) = self
+            .get_untyped(path.clone())
+            .filter(|resource| !matches!(resource.0.lock().state, ResourceState::Pending { .. }))
+        else {
+            return Ok(false);
+        };
+        let source = self.get_source(path.source()).map_err(LoadError::new)?;
+        let type_uuid = resource.0.lock().type_uuid;
+        let Some(loader) = self
+            .state
+            .loaders
+            .lock()
+            .find_loader_for_type(&source.loader_path(path.path()), type_uuid)
+        else {
+            return Ok(false);
+        };
+
+        let (meta, mut reader) = self
+            .get_meta_and_reader(&source, &path, &loader)
+            .await
+            .map_err(LoadError::new)?;
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .await
+            .map_err(LoadError::new)?;
+        drop(reader);
+
+        let hash = content_hash(&bytes);
+        if self.state.content_hashes.lock().get(&path) == Some(&hash) {
+            return Ok(false);
+        }
+
+        let mut reader = VecReader::new(bytes);
+        let load_context = LoadContext::new(self, path.clone());
+        let loaded_resource = loader
+            .load(&mut reader, meta, load_context)
+            .await
+            .map_err(LoadError::new)?;
+
+        {
+            let mut header = resource.0.lock();
+            header.type_uuid = loaded_resource.value.type_uuid();
+            header.dependencies = loaded_resource.dependencies;
+            header
+                .state
+                .commit(ResourceState::Ok(loaded_resource.value));
+        }
+        self.state.content_hashes.lock().insert(path, hash);
+        Ok(true)
+    }
+
+    /// 取出所有资源来源的监听器报告的变化，在任务池中重新加载被修改的缓存中的资源，
+    /// 返回开始重新加载的资源数量，需要每帧调用。
+    ///
+    /// 监听器已经合并了去抖窗口内的修改，每个路径在一个窗口内只会重新加载一次，
+    /// 内容没有变化的文件会被 [`reload`](Self::reload) 跳过。
+    pub fn process_file_events(&self) -> usize {
+        let mut modified = Vec::new();
+        for source in self.state.asset_sources.read().iter() {
+            while let Some(event) = source.try_recv_event() {
+                if let AssetSourceEvent::ModifiedAsset(path) = event {
+                    modified.push(ResourcePath::from(path).with_source(source.id()));
+                }
             }
         }
+
+        // 没有被加载过的文件不需要重新加载
+        let resources = self.state.resources.lock();
+        modified.retain(|path| resources.contains_key(path));
+        drop(resources);
+
+        let count = modified.len();
+        for path in modified {
+            let resource_manager = self.clone();
+            self.task_pool().spawn_task(async move {
+                if let Err(error) = resource_manager.reload(path.clone()).await {
+                    error!(?error, %path, "failed to reload resource");
+                }
+            });
+        }
+        count
+    }
+
+    /// 等待所有资源加载完成或者失败，按照 `handles` 的顺序返回每个资源的结果。
+    ///
+    /// 某个资源加载失败不会影响等待其余的资源。
+    pub fn wait_all(
+        handles: &[UntypedResource],
+    ) -> impl Future<Output = Vec<Result<(), LoadError>>> + Send + 'static {
+        join_all(
+            handles
+                .iter()
+                .cloned()
+                .map(|handle| async move { handle.await.map(|_| ()) }),
+        )
+    }
+
+    /// 加载一批资源，返回的 [`PreloadHandle`] 会在每个资源加载完成后更新 [`LoadReport`](crate::preload::LoadReport)。
+    pub fn preload<'a, P: Into<ResourcePath<'a>>>(
+        &self,
+        paths: impl IntoIterator<Item = P>,
+    ) -> PreloadHandle {
+        let handle = PreloadHandle::default();
+
+        for path in paths {
+            let path: ResourcePath<'static> = path.into().into_owned();
+            let kind = ResourceKind::External(path.clone());
+
+            let loader = match self.load_built_in(&path, kind.clone()) {
+                Ok(resource) => {
+                    let result = match resource.0.lock().state {
+                        ResourceState::LoadError { ref error } => Err(error.clone()),
+                        _ => Ok(0),
+                    };
+                    handle.push(resource);
+                    handle.finish(path, result);
+                    continue;
+                }
+                Err(loader) => loader,
+            };
+
+            let (resource, is_new) = self.get_or_insert_pending(&path, &loader);
+            handle.push(resource.clone());
+
+            let resource_manager = self.clone();
+            let handle = handle.clone();
+            self.task_pool().spawn_task(async move {
+                let result = if is_new {
+                    resource_manager
+                        .load_internal(path.clone(), resource, loader)
+                        .await
+                } else {
+                    resource.await.map(|_| 0)
+                };
+                handle.finish(path, result);
+            });
+        }
+
+        handle
     }
 
     fn spawn_loading_task(
@@ -150,25 +988,101 @@ impl ResourceManager {
         let resource_manger = (*self).clone();
 
         self.task_pool().spawn_task(async move {
-            resource_manger.load_internal(path, resource, loader).await;
+            let _ = resource_manger.load_internal(path, resource, loader).await;
         });
     }
 
     pub fn add_loader<L: ResourceLoader>(&self, loader: L) {
         self.state.add_loader(loader);
     }
+
+    /// 注册处理器，只有设置了 processed reader 和 writer 的来源会执行处理
+    pub fn add_processor<P: ResourceProcessor>(&self, processor: P) {
+        self.add_processor_with_settings(processor, Default::default());
+    }
+
+    pub fn add_processor_with_settings<P: ResourceProcessor>(
+        &self,
+        processor: P,
+        settings: P::Settings,
+    ) {
+        self.state.processors.lock().push(processor, settings);
+    }
+
+    /// 注册从 `.ron` 文件加载 `T` 的 [`RonLoader`]
+    pub fn add_ron_loader<T: ResourceData + DeserializeOwned>(&self) {
+        self.add_loader(RonLoader::<T>::new());
+    }
+
+    /// 资源和它递归依赖的所有资源是否都已经加载完成
+    pub fn is_loaded_with_dependencies<T: ResourceData>(&self, resource: &Resource<T>) -> bool {
+        resource.recursive_dependency_load_state() == RecursiveDependencyLoadState::Loaded
+    }
+
+    /// 已注册的 loader 能够加载的扩展名，可以用于构建文件过滤器
+    pub fn supported_extensions(&self) -> Vec<String> {
+        self.state.loaders.lock().supported_extensions()
+    }
+
+    /// 输出类型为 `data_type_uuid` 的 loader 的能力，没有这样的 loader 时返回 `None`。
+    pub fn loader_capabilities(&self, data_type_uuid: Uuid) -> Option<LoaderCapabilities> {
+        self.state
+            .loaders
+            .lock()
+            .find_by_data_type(data_type_uuid)
+            .map(|loader| loader.capabilities())
+    }
     pub fn task_pool(&self) -> Arc<TaskPool> {
         self.state.task_pool()
     }
+
+    pub fn get_source<'a>(
+        &self,
+        id: impl Into<ResourceSourceId<'a>>,
+    ) -> Result<Arc<ResourceSource>, MissingAssetSourceError> {
+        self.state.get_source(id)
+    }
+
+    /// 注册资源来源，已经存在的同名来源会被替换，之后可以加载 `name://path` 形式的路径。
+    ///
+    /// `builder` 没有 reader 时不会注册，返回 false。
+    pub fn register_source(
+        &self,
+        id: impl Into<ResourceSourceId<'static>>,
+        mut builder: ResourceSourceBuilder,
+    ) -> bool {
+        let id = id.into();
+        let Some(source) = builder.build(id.clone(), self.state.watch) else {
+            warn!("{id} does not have an AssetReader, it is not registered.");
+            return false;
+        };
+        self.state.asset_sources.write().insert(source);
+        true
+    }
 }
 
 pub struct ResourceManagerState {
     pub loaders: Mutex<ResourceLoaders>,
     pub metas: Mutex<ResourceMetas>,
+    pub processors: Mutex<ResourceProcessors>,
     //内置资源
     pub built_in_resources: Mutex<FxHashMap<ResourcePath<'static>, UntypedResource>>,
+    //按类型和内容哈希共享的嵌入资源
+    pub embedded_resources: Mutex<FxHashMap<(Uuid, u64), Vec<UntypedResource>>>,
+    //已经加载或者正在加载的资源
+    pub resources: Mutex<FxHashMap<ResourcePath<'static>, UntypedResource>>,
+    //只被缓存持有的资源剩余的存活时间
+    pub resource_ttls: Mutex<FxHashMap<ResourcePath<'static>, TimeToLive>>,
+    pub default_ttl: Mutex<TimeToLive>,
+    //文件路径和 loader 在这个文件中添加的标签
+    pub labels: Mutex<FxHashMap<ResourcePath<'static>, Vec<String>>>,
+    //加载时读取的文件内容的哈希，内容没有变化时跳过重新加载
+    pub content_hashes: Mutex<FxHashMap<ResourcePath<'static>, u64>>,
 
-    pub asset_sources: ResourceSources,
+    //加载中的资源持有来源的引用，替换来源不会影响它们
+    pub asset_sources: RwLock<ResourceSources>,
+    //运行时注册的来源是否监听文件变化
+    watch: bool,
 
     task_pool: Arc<TaskPool>,
 }
@@ -179,40 +1093,1301 @@ impl ResourceManagerState {
         self.metas.lock().insert::<L>();
     }
 
-    pub(crate) fn new(task_pool: Arc<TaskPool>) -> Self {
-        let mut asset_source_builders = ResourceSourceBuilders::default();
-        asset_source_builders.init_default_source("assets");
-
+    pub(crate) fn new(
+        task_pool: Arc<TaskPool>,
+        asset_source_builders: &mut ResourceSourceBuilders,
+        watch: bool,
+    ) -> Self {
+        let asset_sources = asset_source_builders.build_sources(watch);
         Self {
             task_pool,
             loaders: Default::default(),
             metas: Default::default(),
+            processors: Default::default(),
             built_in_resources: Default::default(),
-            asset_sources: asset_source_builders.build_sources(),
+            embedded_resources: Default::default(),
+            resources: Default::default(),
+            resource_ttls: Default::default(),
+            default_ttl: Default::default(),
+            labels: Default::default(),
+            content_hashes: Default::default(),
+            asset_sources: RwLock::new(asset_sources),
+            watch,
         }
     }
 
+    pub fn get_source<'a>(
+        &self,
+        id: impl Into<ResourceSourceId<'a>>,
+    ) -> Result<Arc<ResourceSource>, MissingAssetSourceError> {
+        self.asset_sources.read().get(id).cloned()
+    }
+
     pub fn task_pool(&self) -> Arc<TaskPool> {
         self.task_pool.clone()
     }
 
     pub async fn get_meta_and_reader<'a>(
         &'a self,
+        source: &'a ResourceSource,
         path: &'a ResourcePath<'_>,
         loader: &'a Arc<dyn ErasedResourceLoader>,
     ) -> Result<(Box<dyn ResourceMetaDyn>, Box<dyn Reader + 'a>), ResourceError> {
-        let source = self.asset_sources.get(path.source())?;
-
-        let asset_reader = source.reader();
-        let reader = asset_reader.read(path.path()).await?;
+        let processor = if source.should_process() {
+            self.processors.lock().find_processor(path.path())
+        } else {
+            None
+        };
+        let reader = match processor {
+            Some(processor) => Self::read_processed(source, path.path(), processor).await?,
+            None => source.reader().read(path.path()).await?,
+        };
 
         let metas = self.metas.lock();
 
         let meta = metas
             .get(&loader.data_type_uuid())
-            .and_then(|meta| loader.default_meta_from_dyn(meta.as_ref()))
+            .and_then(|meta| loader.default_meta_from_dyn(meta))
             .unwrap_or_else(|| loader.default_meta());
 
         Ok((meta, reader))
     }
+
+    /// 优先读取缓存的处理结果，没有缓存时处理源文件并写入 processed writer
+    ///
+    /// 源文件内容的哈希写在处理结果的 meta 中，源文件修改后哈希不同，会重新处理。
+    async fn read_processed<'a>(
+        source: &'a ResourceSource,
+        path: &'a std::path::Path,
+        processor: Arc<dyn ErasedResourceProcessor>,
+    ) -> Result<Box<dyn Reader + 'a>, ResourceError> {
+        let mut bytes = Vec::new();
+        let mut reader = source.reader().read(path).await?;
+        reader
+            .read_to_end(&mut bytes)
+            .await
+            .map_err(AssetReaderError::Io)?;
+        drop(reader);
+        let hash = format!("{:016x}", content_hash(&bytes));
+
+        let processed_reader = source.processed_reader().unwrap();
+        match processed_reader.read_meta_bytes(path).await {
+            Ok(processed_hash) if processed_hash == hash.as_bytes() => {
+                match processed_reader.read(path).await {
+                    Ok(reader) => return Ok(reader),
+                    Err(AssetReaderError::NotFound(_)) => {}
+                    Err(e) => return Err(e.into()),
+                }
+            }
+            Ok(_) | Err(AssetReaderError::NotFound(_)) => {}
+            Err(e) => return Err(e.into()),
+        }
+
+        let processed = processor.process(&bytes);
+        let processed_writer = source.processed_writer().unwrap();
+        processed_writer.write_bytes(path, &processed).await?;
+        processed_writer
+            .write_meta_bytes(path, hash.as_bytes())
+            .await?;
+
+        Ok(Box::new(VecReader::new(processed)))
+    }
+}
+
+//把 `from` 文件及其标签的条目移动到 `to`，返回移动后的路径
+fn rename_entries<V>(
+    map: &mut FxHashMap<ResourcePath<'static>, V>,
+    from: &ResourcePath<'static>,
+    to: &ResourcePath<'static>,
+) -> Vec<ResourcePath<'static>> {
+    let moved = map
+        .keys()
+        .filter(|path| path.without_label() == from.without_label())
+        .cloned()
+        .collect::<Vec<_>>();
+    moved
+        .into_iter()
+        .map(|old_path| {
+            let value = map.remove(&old_path).unwrap();
+            let new_path = match old_path.label_cow() {
+                Some(label) => to.clone().with_label(label.into_owned()),
+                None => to.clone(),
+            };
+            map.insert(new_path.clone(), value);
+            new_path
+        })
+        .collect()
+}
+
+/// 文件内容的哈希，用于判断文件是否需要重新加载
+pub(crate) fn content_hash(bytes: &[u8]) -> u64 {
+    fold_hash(fnv1a(FNV1A_OFFSET_BASIS, bytes))
+}
+
+//把 128 位的 FNV-1a 折叠为 64 位
+fn fold_hash(hash: u128) -> u64 {
+    (hash ^ (hash >> 64)) as u64
+}
+
+/// 统计 loader 读取的字节数
+struct ByteCountReader<'a> {
+    inner: Box<dyn Reader + 'a>,
+    bytes_read: u64,
+    //按顺序读取的内容的哈希，seek 之后无效
+    hash: Option<u128>,
+    eof: bool,
+}
+
+impl<'a> ByteCountReader<'a> {
+    fn new(inner: Box<dyn Reader + 'a>) -> Self {
+        Self {
+            inner,
+            bytes_read: 0,
+            hash: Some(FNV1A_OFFSET_BASIS),
+            eof: false,
+        }
+    }
+
+    /// 从头到尾按顺序读取了整个文件时返回内容的哈希
+    fn content_hash(&self) -> Option<u64> {
+        self.hash.filter(|_| self.eof).map(fold_hash)
+    }
+}
+
+impl AsyncRead for ByteCountReader<'_> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<futures_io::Result<usize>> {
+        let poll = Pin::new(&mut self.inner).poll_read(cx, buf);
+        if let Poll::Ready(Ok(n)) = poll {
+            self.bytes_read += n as u64;
+            if n == 0 && !buf.is_empty() {
+                self.eof = true;
+            }
+            self.hash = self.hash.map(|hash| fnv1a(hash, &buf[..n]));
+        }
+        poll
+    }
+}
+
+impl AsyncSeek for ByteCountReader<'_> {
+    fn poll_seek(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        pos: SeekFrom,
+    ) -> Poll<std::io::Result<u64>> {
+        self.hash = None;
+        Pin::new(&mut self.inner).poll_seek(cx, pos)
+    }
+}
+
+impl Reader for ByteCountReader<'_> {}
+
+#[cfg(test)]
+pub(crate) mod test {
+
+    use std::{
+        path::Path,
+        time::{Duration, Instant},
+    };
+
+    use mini_core::{futures_lite::future::block_on, type_uuid::TypeUuidProvider, uuid::uuid};
+
+    use super::*;
+    use crate::{
+        error::ResourceAccessError,
+        io::{
+            glob, with_extensions, Dir, MemoryAssetReader, MemoryAssetWriter,
+            ResourceSourceBuilder, ResourceSourceId,
+        },
+        resource::ResourceStateKind,
+    };
+
+    #[derive(Debug, PartialEq, Hash, TypeUuidProvider, ResourceData)]
+    #[type_uuid(id = "5a0b1e46-8f7a-4a8e-9a3c-6f1f0e2d9b11")]
+    struct Text(String);
+
+    struct TextLoader;
+
+    impl ResourceLoader for TextLoader {
+        type ResourceData = Text;
+        type Settings = ();
+        type Error = std::io::Error;
+
+        fn extensions(&self) -> &[&str] {
+            &["txt"]
+        }
+
+        async fn load<'a>(
+            &'a self,
+            reader: &'a mut dyn Reader,
+            _settings: &'a Self::Settings,
+            _load_context: &'a mut LoadContext<'_>,
+        ) -> Result<Self::ResourceData, Self::Error> {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            Ok(Text(String::from_utf8_lossy(&bytes).into_owned()))
+        }
+    }
+
+    pub(crate) fn memory_resource_manager(dir: &Dir) -> ResourceManager {
+        let reader = MemoryAssetReader { root: dir.clone() };
+        let writer = MemoryAssetWriter { root: dir.clone() };
+        let mut builders = ResourceSourceBuilders::default();
+        builders.insert(
+            ResourceSourceId::Default,
+            ResourceSourceBuilder::default()
+                .with_reader(move || Box::new(reader.clone()))
+                .with_writer(move |_| Some(Box::new(writer.clone()))),
+        );
+        ResourceManager::with_sources(Arc::new(TaskPool::new()), builders)
+    }
+
+    #[test]
+    fn test_preload_report() {
+        let dir = Dir::new();
+        dir.insert_asset("a.txt", b"hello".as_slice());
+        dir.insert_asset("levels/b.txt", b"world!".as_slice());
+
+        let resource_manager = memory_resource_manager(&dir);
+        resource_manager.add_loader(TextLoader);
+
+        let handle =
+            resource_manager.preload(["a.txt", "missing.txt", "levels/b.txt", "no_loader.bin"]);
+
+        let start = Instant::now();
+        while !handle.is_finished() {
+            assert!(
+                start.elapsed() < Duration::from_secs(5),
+                "preload timed out"
+            );
+            std::thread::sleep(Duration::from_millis(1));
+        }
+
+        let report = handle.report();
+        let mut succeeded = report
+            .succeeded
+            .iter()
+            .map(|path| path.to_string())
+            .collect::<Vec<_>>();
+        succeeded.sort();
+        let mut failed = report
+            .failed
+            .iter()
+            .map(|(path, _)| path.to_string())
+            .collect::<Vec<_>>();
+        failed.sort();
+
+        assert_eq!(succeeded, ["a.txt", "levels/b.txt"]);
+        assert_eq!(failed, ["missing.txt", "no_loader.bin"]);
+        assert_eq!(report.total_bytes, 11);
+        let resources = handle.resources();
+        assert_eq!(resources.len(), 4);
+        let text = Resource::<Text>::new(resources[0].clone());
+        assert_eq!(text.data_ref().0, "hello");
+    }
+
+    #[test]
+    fn test_matches_path() {
+        let dir = Dir::new();
+        dir.insert_asset("a.txt", b"hello".as_slice());
+
+        let resource_manager = memory_resource_manager(&dir);
+        resource_manager.add_loader(TextLoader);
+
+        let text =
+            mini_core::futures_lite::future::block_on(resource_manager.load_async::<Text>("a.txt"));
+        assert!(text.matches_path(&ResourcePath::from("a.txt")));
+        assert!(!text.matches_path(&ResourcePath::from("b.txt")));
+
+        let embedded = UntypedResource::new_ok(ResourceKind::Embedded, Text("embedded".into()));
+        assert!(!embedded.matches_path(&ResourcePath::from("a.txt")));
+    }
+
+    #[test]
+    fn test_rename() {
+        let dir = Dir::new();
+        dir.insert_asset("a.txt", b"hello".as_slice());
+        dir.insert_meta(Path::new("a.txt"), b"meta".as_slice());
+
+        let resource_manager = memory_resource_manager(&dir);
+        resource_manager.add_loader(TextLoader);
+
+        let text = block_on(resource_manager.load_async::<Text>("a.txt"));
+        block_on(resource_manager.rename("a.txt", "moved/b.txt")).unwrap();
+
+        assert!(dir.get_asset(Path::new("a.txt")).is_none());
+        assert!(dir.get_meta(Path::new("a.txt")).is_none());
+        assert_eq!(&*dir.get_asset(Path::new("moved/b.txt")).unwrap(), b"hello");
+        assert_eq!(&*dir.get_meta(Path::new("moved/b.txt")).unwrap(), b"meta");
+
+        assert!(text.matches_path(&ResourcePath::from("moved/b.txt")));
+        assert_eq!(text.data_ref().0, "hello");
+        assert!(resource_manager.get_untyped("a.txt").is_none());
+        let moved = resource_manager.load::<Text>("moved/b.txt");
+        assert!(Arc::ptr_eq(&moved.untyped.0, &text.untyped.0));
+
+        // 存活时间和内容哈希也移动到新的路径
+        let old_path = ResourcePath::from("a.txt");
+        let new_path = ResourcePath::from("moved/b.txt");
+        let content_hashes = resource_manager.state.content_hashes.lock();
+        assert!(!content_hashes.contains_key(&old_path));
+        assert_eq!(content_hashes.get(&new_path), Some(&content_hash(b"hello")));
+        drop(content_hashes);
+
+        // 只被缓存持有的资源保留剩余的存活时间
+        drop(moved);
+        drop(text);
+        resource_manager.update_cache(Duration::ZERO);
+        block_on(resource_manager.rename("moved/b.txt", "c.txt")).unwrap();
+        let ttls = resource_manager.state.resource_ttls.lock();
+        assert!(!ttls.contains_key(&old_path));
+        assert!(!ttls.contains_key(&new_path));
+        assert!(ttls.contains_key(&ResourcePath::from("c.txt")));
+        drop(ttls);
+        assert!(resource_manager.get_untyped("c.txt").is_some());
+
+        assert!(block_on(resource_manager.rename("missing.txt", "other.txt")).is_err());
+    }
+
+    #[test]
+    fn test_data_mut() {
+        let text = Resource::<Text>::new(UntypedResource::new_ok(
+            ResourceKind::Embedded,
+            Text("hello".into()),
+        ));
+        let shared = text.clone();
+
+        text.data_mut().0.push_str(" world");
+        assert_eq!(shared.data_ref().0, "hello world");
+        assert_eq!(
+            shared.data_ref().as_loaded_ref().map(|text| text.0.len()),
+            Some(11)
+        );
+
+        let pending = Resource::<Text>::new(UntypedResource::default());
+        assert!(pending.data_mut().as_loaded_mut().is_none());
+    }
+
+    #[test]
+    fn test_resource_identity() {
+        use std::hash::BuildHasher;
+
+        let dir = Dir::new();
+        dir.insert_asset("a.txt", b"same".as_slice());
+        dir.insert_asset("b.txt", b"same".as_slice());
+        let resource_manager = memory_resource_manager(&dir);
+        resource_manager.add_loader(TextLoader);
+
+        let a = block_on(resource_manager.load_async::<Text>("a.txt"));
+        let cached = block_on(resource_manager.load_async::<Text>("a.txt"));
+        let b = block_on(resource_manager.load_async::<Text>("b.txt"));
+        assert_eq!(a, cached);
+        // 内容相同的不同资源不相等
+        assert_ne!(a, b);
+
+        let hasher = std::collections::hash_map::RandomState::new();
+        assert_eq!(hasher.hash_one(&a), hasher.hash_one(a.clone()));
+        assert_eq!(hasher.hash_one(&a), hasher.hash_one(&cached));
+
+        // 哈希只使用指针，内部可变的数据不影响哈希
+        #[allow(clippy::mutable_key_type)]
+        let set = [a.clone(), cached, b, a.clone()]
+            .into_iter()
+            .collect::<FxHashSet<_>>();
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(&a));
+    }
+
+    #[test]
+    fn test_try_data_ref() {
+        let dir = Dir::new();
+        dir.insert_asset("a.txt", b"hello".as_slice());
+        let resource_manager = memory_resource_manager(&dir);
+        resource_manager.add_loader(TextLoader);
+
+        let pending = Resource::<Text>::new(UntypedResource::new_pending(
+            ResourceKind::Embedded,
+            Text::type_uuid(),
+        ));
+        assert_eq!(pending.state(), ResourceStateKind::Pending);
+        assert!(matches!(
+            pending.try_data_ref(),
+            Err(ResourceAccessError::Pending(_))
+        ));
+
+        let missing = block_on(resource_manager.load_async::<Text>("missing.txt"));
+        assert_eq!(missing.state(), ResourceStateKind::LoadError);
+        assert!(matches!(
+            missing.try_data_ref(),
+            Err(ResourceAccessError::LoadError { .. })
+        ));
+
+        let text = block_on(resource_manager.load_async::<Text>("a.txt"));
+        assert_eq!(text.state(), ResourceStateKind::Ok);
+        assert_eq!(text.try_data_ref().unwrap().0, "hello");
+
+        let mismatched = Resource::<LoadedFolder> {
+            untyped: text.untyped.clone(),
+            type_marker: Default::default(),
+        };
+        assert!(matches!(
+            mismatched.try_data_ref(),
+            Err(ResourceAccessError::TypeMismatch(_))
+        ));
+    }
+
+    #[test]
+    fn test_register_built_in_once() {
+        let resource_manager = memory_resource_manager(&Dir::new());
+
+        let barrier = Arc::new(std::sync::Barrier::new(2));
+        let threads = ["first", "second"].map(|text| {
+            let resource_manager = resource_manager.clone();
+            let barrier = barrier.clone();
+            std::thread::spawn(move || {
+                barrier.wait();
+                resource_manager.add_built_in("built_in.txt", Text(text.into()))
+            })
+        });
+        let [first, second] = threads.map(|thread| thread.join().unwrap());
+
+        assert!(Arc::ptr_eq(&first.untyped.0, &second.untyped.0));
+        let text = block_on(resource_manager.load_async::<Text>("built_in.txt"));
+        assert!(Arc::ptr_eq(&text.untyped.0, &first.untyped.0));
+        // 先注册的数据被保留，另一个被丢弃
+        assert!(["first", "second"].contains(&text.data_ref().0.as_str()));
+    }
+
+    #[test]
+    fn test_built_in_dedup() {
+        let resource_manager = memory_resource_manager(&Dir::new());
+
+        let first = resource_manager.add_built_in_dedup("white_a.txt", Text("white".into()));
+        let second = resource_manager.add_built_in_dedup("white_b.txt", Text("white".into()));
+        let other = resource_manager.add_built_in_dedup("black.txt", Text("black".into()));
+
+        assert!(Arc::ptr_eq(&first.untyped.0, &second.untyped.0));
+        assert!(!Arc::ptr_eq(&first.untyped.0, &other.untyped.0));
+        assert!(first.untyped.0.lock().kind.is_embedded());
+
+        let loaded = block_on(resource_manager.load_async::<Text>("white_b.txt"));
+        assert!(Arc::ptr_eq(&loaded.untyped.0, &first.untyped.0));
+    }
+
+    #[derive(Debug, TypeUuidProvider, ResourceData)]
+    #[type_uuid(id = "5a0b1e46-8f7a-4a8e-9a3c-6f1f0e2d9b11")]
+    struct FakeText;
+
+    struct FakeTextLoader;
+
+    impl ResourceLoader for FakeTextLoader {
+        type ResourceData = FakeText;
+        type Settings = ();
+        type Error = std::io::Error;
+
+        fn extensions(&self) -> &[&str] {
+            &["fake"]
+        }
+
+        async fn load<'a>(
+            &'a self,
+            _reader: &'a mut dyn Reader,
+            _settings: &'a Self::Settings,
+            _load_context: &'a mut LoadContext<'_>,
+        ) -> Result<Self::ResourceData, Self::Error> {
+            Ok(FakeText)
+        }
+    }
+
+    #[test]
+    fn test_loader_type_uuid_collision() {
+        let mut loaders = ResourceLoaders::default();
+        loaders.push(TextLoader);
+        // 同一个数据类型可以有多个 loader
+        loaders.push(TextLoader);
+
+        let error = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            loaders.push(FakeTextLoader);
+        }))
+        .unwrap_err();
+        let message = error.downcast_ref::<String>().unwrap();
+        assert!(message.contains(std::any::type_name::<Text>()));
+        assert!(message.contains(std::any::type_name::<FakeText>()));
+    }
+
+    #[test]
+    fn test_resource_type_check() {
+        let untyped = UntypedResource::new_ok(ResourceKind::Embedded, Text("hello".into()));
+        assert!(Resource::<Text>::try_new(untyped.clone()).is_ok());
+        let Err(error) = Resource::<LoadedFolder>::try_new(untyped.clone()) else {
+            panic!("The type mismatch is not reported");
+        };
+        assert_eq!(error.expected, LoadedFolder::type_uuid());
+        assert_eq!(error.actual, Text::type_uuid());
+
+        // 还没有数据的资源不检查类型
+        let pending = UntypedResource::new_pending(ResourceKind::Embedded, Text::type_uuid());
+        assert!(Resource::<LoadedFolder>::try_new(pending).is_ok());
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            Resource::<LoadedFolder>::new(untyped.clone())
+        }));
+        assert_eq!(result.is_err(), cfg!(debug_assertions));
+
+        // 持有数据的锁时创建和复制句柄不会阻塞
+        let text = Resource::<Text>::new(untyped);
+        let data = text.data_ref();
+        let other = Resource::<Text>::new(text.untyped.clone());
+        assert_eq!(other.clone(), text);
+        drop(data);
+    }
+
+    #[test]
+    fn test_load_gzipped_text() {
+        use flate2::{write::GzEncoder, Compression};
+        use std::io::Write;
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"hello gzip").unwrap();
+        let dir = Dir::new();
+        dir.insert_asset("notes.txt.gz", encoder.finish().unwrap());
+
+        let reader = MemoryAssetReader { root: dir.clone() };
+        let mut builders = ResourceSourceBuilders::default();
+        builders.insert(
+            ResourceSourceId::Default,
+            ResourceSourceBuilder::default()
+                .with_reader(move || Box::new(reader.clone()))
+                .with_gzip(),
+        );
+        let resource_manager = ResourceManager::with_sources(Arc::new(TaskPool::new()), builders);
+        resource_manager.add_loader(TextLoader);
+
+        let text = block_on(resource_manager.load_async::<Text>("notes.txt.gz"));
+        assert_eq!(text.data_ref().0, "hello gzip");
+
+        // 不解压的来源按照 `.gz` 查找 loader
+        let resource_manager = memory_resource_manager(&dir);
+        resource_manager.add_loader(TextLoader);
+        let untyped = resource_manager.load_untyped("notes.txt.gz");
+        assert!(matches!(
+            untyped.0.lock().state,
+            ResourceState::LoadError { .. }
+        ));
+    }
+
+    #[test]
+    fn test_load_folder() {
+        let dir = Dir::new();
+        dir.insert_asset("textures/a.txt", b"a".as_slice());
+        dir.insert_meta(Path::new("textures/a.txt"), b"meta".as_slice());
+        dir.insert_asset("textures/no_loader.bin", b"bin".as_slice());
+        dir.insert_asset("textures/sub/b.txt", b"b".as_slice());
+        dir.insert_asset("other/c.txt", b"c".as_slice());
+
+        let resource_manager = memory_resource_manager(&dir);
+        resource_manager.add_loader(TextLoader);
+
+        let folder = resource_manager.load_folder("textures");
+        block_on(folder.untyped.clone()).unwrap();
+
+        let folder = folder.data_ref();
+        let texts = folder
+            .resources
+            .iter()
+            .map(|resource| Resource::<Text>::new(resource.clone()).data_ref().0.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(texts, ["a", "b"]);
+
+        let missing = resource_manager.load_folder("missing");
+        assert!(block_on(missing.untyped.clone()).is_err());
+    }
+
+    #[test]
+    fn test_load_folder_filtered() {
+        let dir = Dir::new();
+        dir.insert_asset("textures/a.txt", b"a".as_slice());
+        dir.insert_asset("textures/no_loader.bin", b"bin".as_slice());
+        dir.insert_asset("textures/sub/b.txt", b"b".as_slice());
+        dir.insert_asset("textures/other/c.txt", b"c".as_slice());
+
+        let resource_manager = memory_resource_manager(&dir);
+        resource_manager.add_loader(TextLoader);
+
+        let texts = |folder: Resource<LoadedFolder>| {
+            block_on(folder.untyped.clone()).unwrap();
+            let folder = folder.data_ref();
+            folder
+                .resources
+                .iter()
+                .map(|resource| Resource::<Text>::new(resource.clone()).data_ref().0.clone())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            texts(resource_manager.load_folder("textures")),
+            ["a", "c", "b"]
+        );
+        assert_eq!(
+            texts(resource_manager.load_folder_filtered("textures", glob("textures/*.txt"))),
+            ["a"]
+        );
+        assert_eq!(
+            texts(resource_manager.load_folder_filtered("textures", glob("textures/sub/**"))),
+            ["b"]
+        );
+        // 没有 loader 的文件即使匹配也不会加载
+        assert!(
+            texts(resource_manager.load_folder_filtered("textures", glob("**.bin"))).is_empty()
+        );
+    }
+
+    #[test]
+    fn test_update_cache() {
+        let resource_manager = memory_resource_manager(&Dir::new());
+        resource_manager.set_default_ttl(Duration::from_secs(10));
+
+        let cached = |text: &str| {
+            let path = ResourcePath::from(text.to_string());
+            let resource =
+                UntypedResource::new_ok(ResourceKind::External(path.clone()), Text(text.into()));
+            resource_manager
+                .state
+                .resources
+                .lock()
+                .insert(path, resource.clone());
+            resource
+        };
+        let held = cached("held.txt");
+        drop(cached("unused.txt"));
+        drop(cached("reused.txt"));
+
+        resource_manager.update_cache(Duration::from_secs(6));
+        // 再次被使用时重置存活时间
+        let reused = resource_manager.get_untyped("reused.txt").unwrap();
+        resource_manager.update_cache(Duration::from_secs(6));
+        drop(reused);
+
+        assert!(resource_manager.get_untyped("held.txt").is_some());
+        assert!(resource_manager.get_untyped("unused.txt").is_none());
+        assert!(resource_manager.get_untyped("reused.txt").is_some());
+
+        resource_manager.update_cache(Duration::from_secs(6));
+        assert!(resource_manager.get_untyped("reused.txt").is_some());
+        resource_manager.update_cache(Duration::from_secs(6));
+        assert!(resource_manager.get_untyped("reused.txt").is_none());
+        assert!(Arc::ptr_eq(
+            &resource_manager.get_untyped("held.txt").unwrap().0,
+            &held.0
+        ));
+    }
+
+    #[test]
+    fn test_wait_all() {
+        let dir = Dir::new();
+        dir.insert_asset("a.txt", b"a".as_slice());
+        dir.insert_asset("b.txt", b"b".as_slice());
+        let resource_manager = memory_resource_manager(&dir);
+        resource_manager.add_loader(TextLoader);
+
+        let handles =
+            ["a.txt", "missing.txt", "b.txt"].map(|path| resource_manager.load_untyped(path));
+        let results = block_on(ResourceManager::wait_all(&handles));
+
+        assert_eq!(results.len(), 3);
+        assert!(results[0].is_ok());
+        assert!(results[1].is_err());
+        assert!(results[2].is_ok());
+        assert_eq!(Resource::<Text>::new(handles[2].clone()).data_ref().0, "b");
+    }
+
+    #[test]
+    fn test_weak_resource() {
+        let dir = Dir::new();
+        dir.insert_asset("a.txt", b"hello".as_slice());
+        let resource_manager = memory_resource_manager(&dir);
+        resource_manager.add_loader(TextLoader);
+        resource_manager.set_default_ttl(Duration::from_secs(10));
+
+        let text = block_on(resource_manager.load_async::<Text>("a.txt"));
+        let weak = text.downgrade();
+        assert_eq!(weak.upgrade().unwrap(), text);
+
+        // 只剩下缓存和弱句柄，资源被当作没有使用
+        drop(text);
+        resource_manager.update_cache(Duration::from_secs(11));
+        assert!(resource_manager.get_untyped("a.txt").is_none());
+        assert!(weak.upgrade().is_none());
+    }
+
+    #[test]
+    fn test_read_directory_filtered() {
+        let dir = Dir::new();
+        dir.insert_asset("textures/b.JPG", b"b".as_slice());
+        dir.insert_asset("textures/a.png", b"a".as_slice());
+        dir.insert_meta(Path::new("textures/a.png"), b"meta".as_slice());
+        dir.insert_asset("textures/notes.txt", b"notes".as_slice());
+        dir.insert_asset("textures/sub/c.png", b"c".as_slice());
+
+        let resource_manager = memory_resource_manager(&dir);
+
+        let is_texture = with_extensions(&["png", ".jpg"]);
+        let textures =
+            block_on(resource_manager.read_directory_filtered("textures", is_texture)).unwrap();
+        assert_eq!(
+            textures,
+            [Path::new("textures/a.png"), Path::new("textures/b.JPG")]
+        );
+
+        let all = block_on(resource_manager.read_directory_filtered("textures", |_| true)).unwrap();
+        assert_eq!(
+            all,
+            [
+                Path::new("textures/a.png"),
+                Path::new("textures/b.JPG"),
+                Path::new("textures/notes.txt"),
+                Path::new("textures/sub"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dependency_load_state() {
+        // 每一行是一个依赖的路径
+        struct ListLoader;
+
+        impl ResourceLoader for ListLoader {
+            type ResourceData = Text;
+            type Settings = ();
+            type Error = std::io::Error;
+
+            fn extensions(&self) -> &[&str] {
+                &["list"]
+            }
+
+            async fn load<'a>(
+                &'a self,
+                reader: &'a mut dyn Reader,
+                _settings: &'a Self::Settings,
+                load_context: &'a mut LoadContext<'_>,
+            ) -> Result<Self::ResourceData, Self::Error> {
+                let mut bytes = Vec::new();
+                reader.read_to_end(&mut bytes).await?;
+                let text = String::from_utf8_lossy(&bytes).into_owned();
+                for line in text.lines() {
+                    load_context.load::<Text>(line.to_string());
+                }
+                Ok(Text(text))
+            }
+        }
+
+        let dir = Dir::new();
+        dir.insert_asset("a.txt", b"a".as_slice());
+        dir.insert_asset("b.txt", b"b".as_slice());
+        dir.insert_asset("ok.list", b"a.txt\nb.txt".as_slice());
+        dir.insert_asset("nested.list", b"ok.list".as_slice());
+        dir.insert_asset("broken.list", b"a.txt\nmissing.txt".as_slice());
+
+        let resource_manager = memory_resource_manager(&dir);
+        resource_manager.add_loader(TextLoader);
+        resource_manager.add_loader(ListLoader);
+
+        let nested = block_on(resource_manager.load_async::<Text>("nested.list"));
+        assert_eq!(nested.dependencies().len(), 1);
+        let ok = Resource::<Text>::new(nested.dependencies()[0].clone());
+        block_on(ok.untyped.clone()).unwrap();
+        for dependency in ok.dependencies() {
+            block_on(dependency).unwrap();
+        }
+        assert_eq!(
+            nested.recursive_dependency_load_state(),
+            RecursiveDependencyLoadState::Loaded
+        );
+        assert!(resource_manager.is_loaded_with_dependencies(&nested));
+
+        let broken = block_on(resource_manager.load_async::<Text>("broken.list"));
+        for dependency in broken.dependencies() {
+            let _ = block_on(dependency);
+        }
+        assert_eq!(
+            broken.recursive_dependency_load_state(),
+            RecursiveDependencyLoadState::Failed
+        );
+
+        let pending = UntypedResource::new_pending(ResourceKind::Embedded, Text::type_uuid());
+        let parent = UntypedResource::new_ok(ResourceKind::Embedded, Text("parent".into()));
+        parent.0.lock().dependencies.push(pending);
+        assert_eq!(
+            parent.recursive_dependency_load_state(),
+            RecursiveDependencyLoadState::Loading
+        );
+    }
+
+    #[test]
+    fn test_get_load_state() {
+        let dir = Dir::new();
+        dir.insert_asset("a.txt", b"hello".as_slice());
+        let resource_manager = memory_resource_manager(&dir);
+        resource_manager.add_loader(TextLoader);
+
+        let text = block_on(resource_manager.load_async::<Text>("a.txt"));
+        assert!(resource_manager.get_load_state(&text.untyped).is_loaded());
+
+        let missing = block_on(resource_manager.load_async::<Text>("missing.txt"));
+        assert!(resource_manager
+            .get_load_state(&missing.untyped)
+            .is_failed());
+
+        let pending = UntypedResource::new_pending(
+            ResourceKind::External("pending.txt".into()),
+            Text::type_uuid(),
+        );
+        assert!(matches!(
+            resource_manager.get_load_state(&pending),
+            LoadState::NotLoaded
+        ));
+        assert_eq!(resource_manager.loading_count(), 0);
+
+        resource_manager
+            .state
+            .resources
+            .lock()
+            .insert("pending.txt".into(), pending.clone());
+        assert!(matches!(
+            resource_manager.get_load_state(&pending),
+            LoadState::Loading
+        ));
+        assert_eq!(resource_manager.loading_count(), 1);
+
+        pending.0.lock().state.commit_ok(Text("done".into()));
+        assert!(resource_manager.get_load_state(&pending).is_loaded());
+        assert_eq!(resource_manager.loading_count(), 0);
+    }
+
+    #[test]
+    fn test_cancel_load() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        // 在 `release` 被设置之前不会完成加载
+        struct SlowLoader {
+            release: Arc<AtomicBool>,
+        }
+
+        impl ResourceLoader for SlowLoader {
+            type ResourceData = Text;
+            type Settings = ();
+            type Error = std::io::Error;
+
+            fn extensions(&self) -> &[&str] {
+                &["slow"]
+            }
+
+            async fn load<'a>(
+                &'a self,
+                reader: &'a mut dyn Reader,
+                _settings: &'a Self::Settings,
+                _load_context: &'a mut LoadContext<'_>,
+            ) -> Result<Self::ResourceData, Self::Error> {
+                while !self.release.load(Ordering::SeqCst) {
+                    mini_core::futures_lite::future::yield_now().await;
+                }
+                let mut bytes = Vec::new();
+                reader.read_to_end(&mut bytes).await?;
+                Ok(Text(String::from_utf8_lossy(&bytes).into_owned()))
+            }
+        }
+
+        let dir = Dir::new();
+        dir.insert_asset("a.slow", b"slow".as_slice());
+        let resource_manager = memory_resource_manager(&dir);
+        let release = Arc::new(AtomicBool::new(false));
+        resource_manager.add_loader(SlowLoader {
+            release: release.clone(),
+        });
+
+        let cancelled = resource_manager.load_untyped("a.slow");
+        assert!(resource_manager.cancel_load(&cancelled));
+        assert!(resource_manager.get_untyped("a.slow").is_none());
+        assert!(!resource_manager.cancel_load(&UntypedResource::default()));
+
+        release.store(true, Ordering::SeqCst);
+        assert!(block_on(cancelled.clone()).unwrap_err().is_cancelled());
+        assert!(resource_manager.get_load_state(&cancelled).is_failed());
+
+        // 之后的加载重新开始
+        let reloaded = resource_manager.load_untyped("a.slow");
+        assert!(!Arc::ptr_eq(&reloaded.0, &cancelled.0));
+        block_on(reloaded.clone()).unwrap();
+        assert!(!resource_manager.cancel_load(&reloaded));
+        assert!(resource_manager.get_untyped("a.slow").is_some());
+    }
+
+    #[test]
+    fn test_load_sync() {
+        let dir = Dir::new();
+        dir.insert_asset("a.txt", b"hello".as_slice());
+        let resource_manager = memory_resource_manager(&dir);
+        resource_manager.add_loader(TextLoader);
+
+        let text = resource_manager.load_sync::<Text>("a.txt").unwrap();
+        assert_eq!(text.data_ref().0, "hello");
+
+        assert!(matches!(
+            resource_manager.load_sync::<Text>("missing.txt"),
+            Err(ResourceError::Load { .. })
+        ));
+    }
+
+    #[test]
+    fn test_reload_skips_unchanged_content() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        struct CountingLoader(Arc<AtomicUsize>);
+
+        impl ResourceLoader for CountingLoader {
+            type ResourceData = Text;
+            type Settings = ();
+            type Error = std::io::Error;
+
+            fn extensions(&self) -> &[&str] {
+                &["count"]
+            }
+
+            async fn load<'a>(
+                &'a self,
+                reader: &'a mut dyn Reader,
+                _settings: &'a Self::Settings,
+                _load_context: &'a mut LoadContext<'_>,
+            ) -> Result<Self::ResourceData, Self::Error> {
+                self.0.fetch_add(1, Ordering::SeqCst);
+                let mut bytes = Vec::new();
+                reader.read_to_end(&mut bytes).await?;
+                Ok(Text(String::from_utf8_lossy(&bytes).into_owned()))
+            }
+        }
+
+        let dir = Dir::new();
+        dir.insert_asset("a.count", b"hello".as_slice());
+        let resource_manager = memory_resource_manager(&dir);
+        let loads = Arc::new(AtomicUsize::new(0));
+        resource_manager.add_loader(CountingLoader(loads.clone()));
+
+        let text = block_on(resource_manager.load_async::<Text>("a.count"));
+        assert_eq!(loads.load(Ordering::SeqCst), 1);
+
+        // 文件被重新写入但是内容相同
+        dir.insert_asset("a.count", b"hello".as_slice());
+        assert!(!block_on(resource_manager.reload("a.count")).unwrap());
+        assert_eq!(loads.load(Ordering::SeqCst), 1);
+
+        dir.insert_asset("a.count", b"world".as_slice());
+        assert!(block_on(resource_manager.reload("a.count")).unwrap());
+        assert_eq!(loads.load(Ordering::SeqCst), 2);
+        assert_eq!(text.data_ref().0, "world");
+
+        assert!(!block_on(resource_manager.reload("a.count")).unwrap());
+        assert_eq!(loads.load(Ordering::SeqCst), 2);
+
+        assert!(!block_on(resource_manager.reload("missing.count")).unwrap());
+    }
+
+    #[cfg(feature = "file_watcher")]
+    #[test]
+    fn test_file_events_reload_once_per_window() {
+        use std::{
+            sync::atomic::{AtomicUsize, Ordering},
+            thread,
+        };
+
+        use crate::io::{FileAssetReader, FileWatcher};
+
+        struct CountingLoader(Arc<AtomicUsize>);
+
+        impl ResourceLoader for CountingLoader {
+            type ResourceData = Text;
+            type Settings = ();
+            type Error = std::io::Error;
+
+            fn extensions(&self) -> &[&str] {
+                &["count"]
+            }
+
+            async fn load<'a>(
+                &'a self,
+                reader: &'a mut dyn Reader,
+                _settings: &'a Self::Settings,
+                _load_context: &'a mut LoadContext<'_>,
+            ) -> Result<Self::ResourceData, Self::Error> {
+                self.0.fetch_add(1, Ordering::SeqCst);
+                let mut bytes = Vec::new();
+                reader.read_to_end(&mut bytes).await?;
+                Ok(Text(String::from_utf8_lossy(&bytes).into_owned()))
+            }
+        }
+
+        let root = std::env::temp_dir().join(format!("mini-hot-reload-{}", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(root.join("a.count"), b"v0").unwrap();
+
+        let debounce_window = Duration::from_millis(400);
+        let reader_root = root.clone();
+        let watcher_root = root.clone();
+        let mut builders = ResourceSourceBuilders::default();
+        builders.insert(
+            ResourceSourceId::Default,
+            ResourceSourceBuilder::default()
+                .with_reader(move || Box::new(FileAssetReader::new(&reader_root)))
+                .with_watcher(move |sender| {
+                    let watcher = FileWatcher::new(
+                        watcher_root.clone(),
+                        sender,
+                        Duration::from_millis(10),
+                        debounce_window,
+                    )
+                    .unwrap();
+                    Some(Box::new(watcher))
+                }),
+        );
+        let resource_manager =
+            ResourceManager::with_sources_and_watch(Arc::new(TaskPool::new()), builders, true);
+        let loads = Arc::new(AtomicUsize::new(0));
+        resource_manager.add_loader(CountingLoader(loads.clone()));
+
+        let text = block_on(resource_manager.load_async::<Text>("a.count"));
+        assert_eq!(loads.load(Ordering::SeqCst), 1);
+
+        // 每帧处理监听事件，直到超时或者满足条件
+        let pump = |duration: Duration, done: &dyn Fn() -> bool| {
+            let start = Instant::now();
+            while !done() && start.elapsed() < duration {
+                resource_manager.process_file_events();
+                thread::sleep(Duration::from_millis(10));
+            }
+        };
+
+        // 编辑器保存时连续写入三次，都在同一个去抖窗口内，写入之间引擎照常处理事件
+        for content in ["v1", "v2", "v3"] {
+            std::fs::write(root.join("a.count"), content).unwrap();
+            pump(Duration::from_millis(60), &|| false);
+        }
+
+        pump(Duration::from_secs(5), &|| {
+            loads.load(Ordering::SeqCst) >= 2
+        });
+        // 再等待两个窗口，确认没有多余的重新加载
+        pump(debounce_window * 2, &|| false);
+
+        assert_eq!(loads.load(Ordering::SeqCst), 2);
+        assert_eq!(text.data_ref().0, "v3");
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[cfg(feature = "file_watcher")]
+    #[test]
+    fn test_platform_default_source_watch() {
+        use std::thread;
+
+        let root = std::env::temp_dir().join(format!("mini-watch-source-{}", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(root.join("a.txt"), b"before").unwrap();
+
+        let build = |watch: bool| {
+            let mut builders = ResourceSourceBuilders::default();
+            builders.init_default_source(root.to_str().unwrap());
+            let resource_manager =
+                ResourceManager::with_sources_and_watch(Arc::new(TaskPool::new()), builders, watch);
+            resource_manager.add_loader(TextLoader);
+            resource_manager
+        };
+
+        // 没有要求监听时不会创建监听器
+        let resource_manager = build(false);
+        assert!(!resource_manager
+            .get_source(ResourceSourceId::Default)
+            .unwrap()
+            .is_watching());
+
+        // 默认来源使用 FileWatcher，修改的文件通过 process_file_events 重新加载
+        let resource_manager = build(true);
+        assert!(resource_manager
+            .get_source(ResourceSourceId::Default)
+            .unwrap()
+            .is_watching());
+        let text = block_on(resource_manager.load_async::<Text>("a.txt"));
+        assert_eq!(text.data_ref().0, "before");
+
+        std::fs::write(root.join("a.txt"), b"after").unwrap();
+        let start = Instant::now();
+        while text.data_ref().0 != "after" && start.elapsed() < Duration::from_secs(5) {
+            resource_manager.process_file_events();
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(text.data_ref().0, "after");
+
+        drop(resource_manager);
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_labeled_resources() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        // 每一行是一个带标签的资源，格式为 `label=text`
+        struct SceneLoader(Arc<AtomicUsize>);
+
+        impl ResourceLoader for SceneLoader {
+            type ResourceData = Text;
+            type Settings = ();
+            type Error = std::io::Error;
+
+            fn extensions(&self) -> &[&str] {
+                &["scene"]
+            }
+
+            async fn load<'a>(
+                &'a self,
+                reader: &'a mut dyn Reader,
+                _settings: &'a Self::Settings,
+                load_context: &'a mut LoadContext<'_>,
+            ) -> Result<Self::ResourceData, Self::Error> {
+                self.0.fetch_add(1, Ordering::Relaxed);
+                let mut bytes = Vec::new();
+                reader.read_to_end(&mut bytes).await?;
+                let text = String::from_utf8_lossy(&bytes).into_owned();
+                for line in text.lines() {
+                    let (label, value) = line.split_once('=').unwrap();
+                    load_context.add_labeled_resource(label.to_string(), Text(value.into()));
+                }
+                Ok(Text(text))
+            }
+        }
+
+        let dir = Dir::new();
+        dir.insert_asset("a.scene", b"Mesh0=cube\nMesh1=sphere".as_slice());
+        let resource_manager = memory_resource_manager(&dir);
+        let reads = Arc::new(AtomicUsize::new(0));
+        resource_manager.add_loader(SceneLoader(reads.clone()));
+
+        let mesh = block_on(resource_manager.load_async::<Text>("a.scene#Mesh1"));
+        assert_eq!(mesh.data_ref().0, "sphere");
+        let mesh = block_on(resource_manager.load_async::<Text>("a.scene#Mesh0"));
+        assert_eq!(mesh.data_ref().0, "cube");
+        assert!(resource_manager.get_untyped("a.scene").is_some());
+
+        let missing = block_on(resource_manager.load_async::<Text>("a.scene#Mesh2"));
+        assert!(missing.is_load_error());
+        assert_eq!(reads.load(Ordering::Relaxed), 1);
+
+        assert_eq!(
+            resource_manager.labels_for(&ResourcePath::from("a.scene")),
+            ["Mesh0", "Mesh1"]
+        );
+        assert_eq!(
+            resource_manager.labels_for(&ResourcePath::from("a.scene#Mesh1")),
+            ["Mesh0", "Mesh1"]
+        );
+        assert!(resource_manager
+            .labels_for(&ResourcePath::from("b.scene"))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_processed_bytes_are_cached() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        struct ReverseProcessor(Arc<AtomicUsize>);
+
+        impl ResourceProcessor for ReverseProcessor {
+            type Settings = ();
+
+            fn extensions(&self) -> &[&str] {
+                &["txt"]
+            }
+
+            fn process(&self, bytes: &[u8], _settings: &Self::Settings) -> Vec<u8> {
+                self.0.fetch_add(1, Ordering::Relaxed);
+                bytes.iter().rev().copied().collect()
+            }
+        }
+
+        let dir = Dir::new();
+        dir.insert_asset("a.txt", b"hello".as_slice());
+        let processed_dir = Dir::new();
+        let processed_count = Arc::new(AtomicUsize::new(0));
+
+        let new_resource_manager = || {
+            let reader = MemoryAssetReader { root: dir.clone() };
+            let processed_reader = MemoryAssetReader {
+                root: processed_dir.clone(),
+            };
+            let processed_writer = MemoryAssetWriter {
+                root: processed_dir.clone(),
+            };
+            let mut builders = ResourceSourceBuilders::default();
+            builders.insert(
+                ResourceSourceId::Default,
+                ResourceSourceBuilder::default()
+                    .with_reader(move || Box::new(reader.clone()))
+                    .with_processed_reader(move || Box::new(processed_reader.clone()))
+                    .with_processed_writer(move |_| Some(Box::new(processed_writer.clone()))),
+            );
+            let resource_manager =
+                ResourceManager::with_sources(Arc::new(TaskPool::new()), builders);
+            resource_manager.add_loader(TextLoader);
+            resource_manager.add_processor(ReverseProcessor(processed_count.clone()));
+            resource_manager
+        };
+
+        let text = block_on(new_resource_manager().load_async::<Text>("a.txt"));
+        assert_eq!(text.data_ref().0, "olleh");
+        assert_eq!(
+            &*processed_dir.get_asset(Path::new("a.txt")).unwrap(),
+            b"olleh"
+        );
+
+        // 新的 manager 直接读取缓存的处理结果
+        let resource_manager = new_resource_manager();
+        let text = block_on(resource_manager.load_async::<Text>("a.txt"));
+        assert_eq!(text.data_ref().0, "olleh");
+        assert_eq!(processed_count.load(Ordering::Relaxed), 1);
+
+        // 源文件修改后重新加载时重新处理
+        dir.insert_asset("a.txt", b"world".as_slice());
+        assert!(block_on(resource_manager.reload("a.txt")).unwrap());
+        assert_eq!(text.data_ref().0, "dlrow");
+        assert_eq!(processed_count.load(Ordering::Relaxed), 2);
+
+        let text = block_on(new_resource_manager().load_async::<Text>("a.txt"));
+        assert_eq!(text.data_ref().0, "dlrow");
+        assert_eq!(processed_count.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn test_register_source() {
+        let resource_manager = memory_resource_manager(&Dir::new());
+        resource_manager.add_loader(TextLoader);
+
+        // 注册之前来源不存在
+        let text = block_on(resource_manager.load_async::<Text>("memory://notes.txt"));
+        assert!(text.is_load_error());
+        assert!(!resource_manager.register_source("empty", ResourceSourceBuilder::default()));
+
+        let dir = Dir::new();
+        dir.insert_asset("notes.txt", b"remote".as_slice());
+        let reader = MemoryAssetReader { root: dir };
+        assert!(resource_manager.register_source(
+            "memory",
+            ResourceSourceBuilder::default().with_reader(move || Box::new(reader.clone())),
+        ));
+
+        // 失败的加载结果被缓存，重新加载后读取新的来源
+        assert!(block_on(resource_manager.reload("memory://notes.txt")).unwrap());
+        let text = block_on(resource_manager.load_async::<Text>("memory://notes.txt"));
+        assert_eq!(text.data_ref().0, "remote");
+        assert!(resource_manager.get_source("memory").is_ok());
+        assert!(resource_manager.get_source("empty").is_err());
+    }
 }
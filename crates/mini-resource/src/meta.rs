@@ -33,8 +33,8 @@ impl ResourceMetas {
             .insert(R::data_type_uuid(), Box::new(ResourceMeta::<R>::new()));
     }
 
-    pub fn get(&self, key: &Uuid) -> Option<&Box<dyn ResourceMetaDyn>> {
-        self.metas.get(key)
+    pub fn get(&self, key: &Uuid) -> Option<&dyn ResourceMetaDyn> {
+        self.metas.get(key).map(|meta| meta.as_ref())
     }
 }
 
@@ -46,7 +46,13 @@ pub struct ResourceMeta<R: ResourceLoader> {
 
 impl<R: ResourceLoader> ResourceMetaDyn for ResourceMeta<R> {
     fn loader_settings(&self) -> Option<&dyn ResourceSettings> {
-        return Some(&self.settings);
+        Some(&self.settings)
+    }
+}
+
+impl<R: ResourceLoader> Default for ResourceMeta<R> {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -58,6 +64,10 @@ impl<R: ResourceLoader> ResourceMeta<R> {
         }
     }
 
+    pub fn meta_format_version(&self) -> &str {
+        &self.meta_format_version
+    }
+
     pub fn new_settings(settings: &dyn ResourceSettings) -> Option<Self> {
         if settings.is::<R::Settings>() {
             let settings =
@@ -0,0 +1,66 @@
+use std::sync::Arc;
+
+use mini_core::parking_lot::Mutex;
+
+use crate::{error::LoadError, io::ResourcePath, resource::UntypedResource};
+
+/// 一批资源加载完成后的汇总
+#[derive(Debug, Clone, Default)]
+pub struct LoadReport {
+    pub succeeded: Vec<ResourcePath<'static>>,
+    pub failed: Vec<(ResourcePath<'static>, LoadError)>,
+    //成功加载的资源读取的字节数
+    pub total_bytes: u64,
+}
+
+#[derive(Default)]
+struct PreloadState {
+    report: LoadReport,
+    pending: usize,
+    resources: Vec<UntypedResource>,
+}
+
+/// [`ResourceManager::preload`](crate::manager::ResourceManager::preload) 返回的句柄，
+/// 每个资源加载完成后都会更新报告。
+#[derive(Clone, Default)]
+pub struct PreloadHandle {
+    state: Arc<Mutex<PreloadState>>,
+}
+
+impl PreloadHandle {
+    pub(crate) fn push(&self, resource: UntypedResource) {
+        let mut state = self.state.lock();
+        state.pending += 1;
+        state.resources.push(resource);
+    }
+
+    pub(crate) fn finish(&self, path: ResourcePath<'static>, result: Result<u64, LoadError>) {
+        let mut state = self.state.lock();
+        state.pending -= 1;
+        match result {
+            Ok(bytes) => {
+                state.report.succeeded.push(path);
+                state.report.total_bytes += bytes;
+            }
+            Err(error) => state.report.failed.push((path, error)),
+        }
+    }
+
+    /// 当前的报告，还没有加载完成的资源不会出现在报告中。
+    pub fn report(&self) -> LoadReport {
+        self.state.lock().report.clone()
+    }
+
+    pub fn pending_count(&self) -> usize {
+        self.state.lock().pending
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.pending_count() == 0
+    }
+
+    /// 这一批的所有资源，句柄存在时资源不会被释放。
+    pub fn resources(&self) -> Vec<UntypedResource> {
+        self.state.lock().resources.clone()
+    }
+}
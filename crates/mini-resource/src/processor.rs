@@ -0,0 +1,61 @@
+use std::{path::Path, sync::Arc};
+
+use crate::meta::ResourceSettings;
+
+/// 在加载之前将源文件转换为处理后的格式，结果会写入来源的 processed writer 并在之后的加载中复用
+pub trait ResourceProcessor: Send + Sync + 'static {
+    type Settings: ResourceSettings + Default;
+
+    //需要处理的文件扩展名
+    fn extensions(&self) -> &[&str];
+
+    fn process(&self, bytes: &[u8], settings: &Self::Settings) -> Vec<u8>;
+}
+
+pub trait ErasedResourceProcessor: Send + Sync + 'static {
+    fn extensions(&self) -> &[&str];
+
+    fn process(&self, bytes: &[u8]) -> Vec<u8>;
+}
+
+//处理器和它使用的设置
+struct ProcessorWithSettings<P: ResourceProcessor> {
+    processor: P,
+    settings: P::Settings,
+}
+
+impl<P: ResourceProcessor> ErasedResourceProcessor for ProcessorWithSettings<P> {
+    fn extensions(&self) -> &[&str] {
+        self.processor.extensions()
+    }
+
+    fn process(&self, bytes: &[u8]) -> Vec<u8> {
+        self.processor.process(bytes, &self.settings)
+    }
+}
+
+#[derive(Default, Clone)]
+pub struct ResourceProcessors {
+    processors: Vec<Arc<dyn ErasedResourceProcessor>>,
+}
+
+impl ResourceProcessors {
+    pub fn push<P: ResourceProcessor>(&mut self, processor: P, settings: P::Settings) {
+        self.processors.push(Arc::new(ProcessorWithSettings {
+            processor,
+            settings,
+        }));
+    }
+
+    pub fn find(&self, extension: &str) -> Option<Arc<dyn ErasedResourceProcessor>> {
+        self.processors
+            .iter()
+            .find(|processor| processor.extensions().contains(&extension))
+            .cloned()
+    }
+
+    pub fn find_processor(&self, path: &Path) -> Option<Arc<dyn ErasedResourceProcessor>> {
+        path.extension()
+            .and_then(|extension| self.find(&extension.to_string_lossy()))
+    }
+}
@@ -1,22 +1,24 @@
 use std::{
     fmt::{Debug, Display, Formatter},
     future::Future,
+    hash::{Hash, Hasher},
     marker::PhantomData,
     ops::{Deref, DerefMut},
     pin::Pin,
-    sync::Arc,
+    sync::{Arc, Weak},
     task::{Context, Poll, Waker},
 };
 
 use mini_core::{
     downcast::Downcast,
     parking_lot::{Mutex, MutexGuard},
-    type_uuid::TypeUuidProvider,
+    type_uuid::{register_type_uuid, TypeUuidProvider},
+    utils::FxHashSet,
     uuid::{uuid, Uuid},
 };
 
 use crate::{
-    error::{LoadError, ResourceLoadError},
+    error::{LoadError, ResourceAccessError, ResourceLoadError, TypeMismatchError},
     io::ResourcePath,
 };
 
@@ -24,20 +26,36 @@ pub use mini_resource_macros::ResourceData;
 
 impl<T> ResourceLoadError for T where T: 'static + Debug + Send + Sync {}
 
-pub trait ResourceData: TypeUuidProvider + 'static + Send + Sync + Debug {}
+pub trait ResourceData: TypeUuidProvider + 'static + Send + Sync + Debug {
+    /// 资源数据占用的大致字节数，只用于 [`ResourceManager::stats_by_type`](crate::manager::ResourceManager::stats_by_type) 统计
+    fn approx_memory_size(&self) -> usize {
+        0
+    }
+}
 
 impl<T: ResourceData> ErasedResourceData for T {
     fn type_uuid(&self) -> Uuid {
         <T as TypeUuidProvider>::type_uuid()
     }
+
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<T>()
+    }
+
+    fn approx_memory_size(&self) -> usize {
+        <T as ResourceData>::approx_memory_size(self)
+    }
 }
 
 pub trait ErasedResourceData: 'static + Debug + Send + Downcast {
     //用于向上转换
     fn type_uuid(&self) -> Uuid;
+
+    fn type_name(&self) -> &'static str;
+
+    fn approx_memory_size(&self) -> usize;
 }
 
-#[derive(Clone)]
 pub struct Resource<T>
 where
     T: ResourceData,
@@ -46,9 +64,85 @@ where
     pub type_marker: PhantomData<T>,
 }
 
+impl<T: ResourceData> Debug for Resource<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(&self.untyped, f)
+    }
+}
+
+//派生的 Clone 会要求 T: Clone
+//类型已经在创建句柄时检查过，复制时不再检查
+impl<T: ResourceData> Clone for Resource<T> {
+    fn clone(&self) -> Self {
+        Self {
+            untyped: self.untyped.clone(),
+            type_marker: PhantomData,
+        }
+    }
+}
+
+/// 不持有资源的句柄，只剩下弱句柄时资源可以被缓存移除，用于打破资源之间的循环引用
+pub struct WeakResource<T>
+where
+    T: ResourceData,
+{
+    weak: Weak<Mutex<ResourceHeader>>,
+    type_marker: PhantomData<T>,
+}
+
+impl<T: ResourceData> Debug for WeakResource<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "WeakResource({:?})", self.weak.as_ptr())
+    }
+}
+
+impl<T: ResourceData> Clone for WeakResource<T> {
+    fn clone(&self) -> Self {
+        Self {
+            weak: self.weak.clone(),
+            type_marker: PhantomData,
+        }
+    }
+}
+
+impl<T: ResourceData> WeakResource<T> {
+    /// 资源已经被释放时返回 `None`
+    pub fn upgrade(&self) -> Option<Resource<T>> {
+        self.weak.upgrade().map(|header| Resource {
+            untyped: UntypedResource(header),
+            type_marker: PhantomData,
+        })
+    }
+}
+
+/// 比较的是资源的句柄而不是数据，指向同一个资源的句柄才相等
+impl<T: ResourceData> PartialEq for Resource<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.untyped == other.untyped
+    }
+}
+
+impl<T: ResourceData> Eq for Resource<T> {}
+
+impl<T: ResourceData> Hash for Resource<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.untyped.hash(state);
+    }
+}
+
 impl<T: ResourceData> Resource<T> {
+    /// 资源已经加载完成时，在 debug 构建中检查数据的类型
+    ///
+    /// 检查不会等待资源的锁，资源正在被其他地方访问时跳过检查。
     pub fn new(untyped: UntypedResource) -> Self {
-        // assert_eq!(untyped.type_uuid(), T::type_uuid());
+        #[cfg(debug_assertions)]
+        if let Some(error) = untyped
+            .0
+            .try_lock()
+            .and_then(|header| Self::type_mismatch(&header))
+        {
+            panic!("{error}");
+        }
 
         Self {
             untyped,
@@ -56,6 +150,61 @@ impl<T: ResourceData> Resource<T> {
         }
     }
 
+    /// 资源已经加载完成并且数据的类型不是 `T` 时返回错误，不会 panic
+    pub fn try_new(untyped: UntypedResource) -> Result<Self, TypeMismatchError> {
+        let error = Self::type_mismatch(&untyped.0.lock());
+        match error {
+            Some(error) => Err(error),
+            None => Ok(Self {
+                untyped,
+                type_marker: PhantomData,
+            }),
+        }
+    }
+
+    /// 创建不持有资源的 [`WeakResource`]
+    pub fn downgrade(&self) -> WeakResource<T> {
+        WeakResource {
+            weak: Arc::downgrade(&self.untyped.0),
+            type_marker: PhantomData,
+        }
+    }
+
+    // 正在加载或者加载失败的资源还没有数据，不检查
+    fn type_mismatch(header: &ResourceHeader) -> Option<TypeMismatchError> {
+        match header.state {
+            ResourceState::Ok(ref data) if data.type_uuid() != T::type_uuid() => {
+                Some(TypeMismatchError {
+                    kind: header.kind.clone(),
+                    expected: T::type_uuid(),
+                    actual: data.type_uuid(),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// 资源是否从 `path` 加载，内嵌的资源总是返回 false
+    pub fn matches_path(&self, path: &ResourcePath) -> bool {
+        self.untyped.matches_path(path)
+    }
+
+    pub fn is_load_error(&self) -> bool {
+        self.untyped.is_load_error()
+    }
+
+    pub fn dependencies(&self) -> Vec<UntypedResource> {
+        self.untyped.dependencies()
+    }
+
+    pub fn state(&self) -> ResourceStateKind {
+        self.untyped.state()
+    }
+
+    pub fn recursive_dependency_load_state(&self) -> RecursiveDependencyLoadState {
+        self.untyped.recursive_dependency_load_state()
+    }
+
     #[inline]
     pub fn data_ref(&self) -> ResourceDataRef<'_, T> {
         ResourceDataRef {
@@ -63,8 +212,37 @@ impl<T: ResourceData> Resource<T> {
             phantom: Default::default(),
         }
     }
+
+    /// 资源没有加载完成或者加载失败时返回错误，不会 panic
+    pub fn try_data_ref(&self) -> Result<ResourceDataRef<'_, T>, ResourceAccessError> {
+        let guard = self.untyped.0.lock();
+        match guard.state {
+            ResourceState::Pending { .. } => Err(ResourceAccessError::Pending(guard.kind.clone())),
+            ResourceState::LoadError { ref error } => Err(ResourceAccessError::LoadError {
+                kind: guard.kind.clone(),
+                error: error.clone(),
+            }),
+            ResourceState::Ok(_) if loaded_ref::<T>(&guard).is_none() => {
+                Err(ResourceAccessError::TypeMismatch(guard.kind.clone()))
+            }
+            ResourceState::Ok(_) => Ok(ResourceDataRef {
+                guard,
+                phantom: Default::default(),
+            }),
+        }
+    }
+
+    /// 可以修改资源数据的引用，和 [`Resource::data_ref`] 一样会锁住资源
+    #[inline]
+    pub fn data_mut(&self) -> ResourceDataMut<'_, T> {
+        ResourceDataMut {
+            guard: self.untyped.0.lock(),
+            phantom: Default::default(),
+        }
+    }
 }
 
+/// 资源数据的只读引用，持有资源的锁
 pub struct ResourceDataRef<'a, T>
 where
     T: ResourceData,
@@ -79,10 +257,46 @@ where
 {
     #[inline]
     pub fn as_loaded_ref(&self) -> Option<&T> {
-        match self.guard.state {
-            ResourceState::Ok(ref data) => <dyn ErasedResourceData>::as_any(&**data).downcast_ref(),
-            _ => None,
-        }
+        loaded_ref(&self.guard)
+    }
+}
+
+impl<'a, T> Debug for ResourceDataRef<'a, T>
+where
+    T: ResourceData,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        fmt_header_data(&self.guard, f)
+    }
+}
+
+impl<'a, T> Deref for ResourceDataRef<'a, T>
+where
+    T: ResourceData,
+{
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        loaded_ref(&self.guard).unwrap_or_else(|| panic_not_loaded(&self.guard))
+    }
+}
+
+/// 资源数据的可变引用，持有资源的锁
+pub struct ResourceDataMut<'a, T>
+where
+    T: ResourceData,
+{
+    guard: MutexGuard<'a, ResourceHeader>,
+    phantom: PhantomData<T>,
+}
+
+impl<'a, T> ResourceDataMut<'a, T>
+where
+    T: ResourceData,
+{
+    #[inline]
+    pub fn as_loaded_ref(&self) -> Option<&T> {
+        loaded_ref(&self.guard)
     }
 
     #[inline]
@@ -96,81 +310,67 @@ where
     }
 }
 
-impl<'a, T> Debug for ResourceDataRef<'a, T>
+impl<'a, T> Debug for ResourceDataMut<'a, T>
 where
     T: ResourceData,
 {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        match self.guard.state {
-            ResourceState::Pending { .. } => {
-                write!(
-                    f,
-                    "Attempt to get reference to resource data while it is not loaded! Path is {}",
-                    self.guard.kind
-                )
-            }
-            ResourceState::LoadError { .. } => {
-                write!(
-                    f,
-                    "Attempt to get reference to resource data which failed to load! Path is {}",
-                    self.guard.kind
-                )
-            }
-            ResourceState::Ok(ref data) => data.fmt(f),
-        }
+        fmt_header_data(&self.guard, f)
     }
 }
 
-impl<'a, T> Deref for ResourceDataRef<'a, T>
+impl<'a, T> Deref for ResourceDataMut<'a, T>
 where
     T: ResourceData,
 {
     type Target = T;
 
     fn deref(&self) -> &Self::Target {
-        match self.guard.state {
-            ResourceState::Pending { .. } => {
-                panic!(
-                    "Attempt to get reference to resource data while it is not loaded! Path is {}",
-                    self.guard.kind
-                )
-            }
-            ResourceState::LoadError { .. } => {
-                panic!(
-                    "Attempt to get reference to resource data which failed to load! Path is {}",
-                    self.guard.kind
-                )
-            }
-            ResourceState::Ok(ref data) => <dyn ErasedResourceData>::as_any(&**data)
-                .downcast_ref()
-                .expect("Type mismatch!"),
-        }
+        loaded_ref(&self.guard).unwrap_or_else(|| panic_not_loaded(&self.guard))
     }
 }
 
-impl<'a, T> DerefMut for ResourceDataRef<'a, T>
+impl<'a, T> DerefMut for ResourceDataMut<'a, T>
 where
     T: ResourceData,
 {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        let header = &mut *self.guard;
-        match header.state {
-            ResourceState::Pending { .. } => {
-                panic!(
-                    "Attempt to get reference to resource data while it is not loaded! Path is {}",
-                    header.kind
-                )
-            }
-            ResourceState::LoadError { .. } => {
-                panic!(
-                    "Attempt to get reference to resource data which failed to load! Path is {}",
-                    header.kind
-                )
-            }
-            ResourceState::Ok(ref mut data) => <dyn ErasedResourceData>::as_any_mut(&mut **data)
-                .downcast_mut()
-                .expect("Type mismatch!"),
+        if !matches!(self.guard.state, ResourceState::Ok(_)) {
+            panic_not_loaded(&self.guard)
         }
+        self.as_loaded_mut().expect("Type mismatch!")
+    }
+}
+
+fn loaded_ref<T: ResourceData>(header: &ResourceHeader) -> Option<&T> {
+    match header.state {
+        ResourceState::Ok(ref data) => <dyn ErasedResourceData>::as_any(&**data).downcast_ref(),
+        _ => None,
+    }
+}
+
+fn not_loaded_message(header: &ResourceHeader) -> String {
+    match header.state {
+        ResourceState::LoadError { .. } => format!(
+            "Attempt to get reference to resource data which failed to load! Path is {}",
+            header.kind
+        ),
+        ResourceState::Ok(_) => "Type mismatch!".to_string(),
+        ResourceState::Pending { .. } => format!(
+            "Attempt to get reference to resource data while it is not loaded! Path is {}",
+            header.kind
+        ),
+    }
+}
+
+fn panic_not_loaded(header: &ResourceHeader) -> ! {
+    panic!("{}", not_loaded_message(header))
+}
+
+fn fmt_header_data(header: &ResourceHeader, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match header.state {
+        ResourceState::Ok(ref data) => data.fmt(f),
+        _ => write!(f, "{}", not_loaded_message(header)),
     }
 }
 
@@ -186,23 +386,89 @@ impl Default for UntypedResource {
             state: ResourceState::new_load_error(LoadError::new(
                 "Default resource state of unknown type.",
             )),
+            dependencies: vec![],
+            cancelled: false,
         })))
     }
 }
 
+/// 比较的是资源的句柄而不是数据，指向同一个资源的句柄才相等
+impl PartialEq for UntypedResource {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for UntypedResource {}
+
+impl Hash for UntypedResource {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // 和 eq 一致，只使用地址
+        (Arc::as_ptr(&self.0) as usize).hash(state);
+    }
+}
+
 impl UntypedResource {
     pub fn type_uuid(&self) -> Uuid {
         self.0.lock().type_uuid
     }
 
+    /// 资源是否从 `path` 加载，内嵌的资源总是返回 false
+    pub fn matches_path(&self, path: &ResourcePath) -> bool {
+        match &self.0.lock().kind {
+            ResourceKind::External(resource_path) => resource_path == path,
+            ResourceKind::Embedded => false,
+        }
+    }
+
+    pub fn state(&self) -> ResourceStateKind {
+        self.0.lock().state.kind()
+    }
+
+    /// 资源是否加载失败
+    pub fn is_load_error(&self) -> bool {
+        matches!(self.0.lock().state, ResourceState::LoadError { .. })
+    }
+
+    pub fn dependencies(&self) -> Vec<UntypedResource> {
+        self.0.lock().dependencies.clone()
+    }
+
+    /// 任意一个资源加载失败时返回 `Failed`，否则有资源正在加载时返回 `Loading`
+    pub fn recursive_dependency_load_state(&self) -> RecursiveDependencyLoadState {
+        let mut visited = FxHashSet::default();
+        let mut stack = vec![self.clone()];
+        let mut state = RecursiveDependencyLoadState::Loaded;
+
+        while let Some(resource) = stack.pop() {
+            // 依赖之间可能有环
+            if !visited.insert(Arc::as_ptr(&resource.0)) {
+                continue;
+            }
+
+            let header = resource.0.lock();
+            match header.state {
+                ResourceState::LoadError { .. } => return RecursiveDependencyLoadState::Failed,
+                ResourceState::Pending { .. } => state = RecursiveDependencyLoadState::Loading,
+                ResourceState::Ok(_) => {}
+            }
+            stack.extend(header.dependencies.iter().cloned());
+        }
+
+        state
+    }
+
     pub fn new_ok<T>(kind: ResourceKind, data: T) -> Self
     where
         T: ResourceData,
     {
+        register_type_uuid::<T>();
         Self(Arc::new(Mutex::new(ResourceHeader {
             kind,
             type_uuid: data.type_uuid(),
             state: ResourceState::new_ok(data),
+            dependencies: vec![],
+            cancelled: false,
         })))
     }
 
@@ -211,6 +477,8 @@ impl UntypedResource {
             kind,
             type_uuid,
             state: ResourceState::new_load_error(error),
+            dependencies: vec![],
+            cancelled: false,
         })))
     }
 
@@ -219,6 +487,8 @@ impl UntypedResource {
             kind,
             type_uuid,
             state: ResourceState::new_pending(),
+            dependencies: vec![],
+            cancelled: false,
         })))
     }
 
@@ -231,6 +501,18 @@ impl UntypedResource {
     pub fn commit_error<E: ResourceLoadError>(&self, error: E) {
         self.0.lock().state.commit_error(error);
     }
+
+    /// 加载是否已经被取消
+    pub fn is_cancelled(&self) -> bool {
+        self.0.lock().cancelled
+    }
+
+    pub fn commit_load_error(&self, error: LoadError) {
+        self.0
+            .lock()
+            .state
+            .commit(ResourceState::new_load_error(error));
+    }
 }
 
 impl Future for UntypedResource {
@@ -262,6 +544,38 @@ pub struct ResourceHeader {
     pub state: ResourceState,
     pub type_uuid: Uuid,
     pub kind: ResourceKind,
+    //加载时通过 LoadContext 加载的资源
+    pub dependencies: Vec<UntypedResource>,
+    //加载被取消，加载任务不会再提交数据
+    pub cancelled: bool,
+}
+
+/// 资源和它所有依赖的加载状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecursiveDependencyLoadState {
+    Loading,
+    Loaded,
+    Failed,
+}
+
+/// 资源的加载状态，见 [`ResourceManager::get_load_state`](crate::manager::ResourceManager::get_load_state)
+#[derive(Debug, Clone)]
+pub enum LoadState {
+    //资源没有被资源管理器加载
+    NotLoaded,
+    Loading,
+    Loaded,
+    Failed(LoadError),
+}
+
+impl LoadState {
+    pub fn is_loaded(&self) -> bool {
+        matches!(self, LoadState::Loaded)
+    }
+
+    pub fn is_failed(&self) -> bool {
+        matches!(self, LoadState::Failed(_))
+    }
 }
 
 #[derive(Debug, Default, Clone)]
@@ -325,7 +639,23 @@ impl DerefMut for WakersList {
     }
 }
 
+/// 不包含数据的 [`ResourceState`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResourceStateKind {
+    Pending,
+    Ok,
+    LoadError,
+}
+
 impl ResourceState {
+    pub fn kind(&self) -> ResourceStateKind {
+        match self {
+            ResourceState::Ok(_) => ResourceStateKind::Ok,
+            ResourceState::LoadError { .. } => ResourceStateKind::LoadError,
+            ResourceState::Pending { .. } => ResourceStateKind::Pending,
+        }
+    }
+
     pub fn new_load_error(error: LoadError) -> Self {
         Self::LoadError { error }
     }
@@ -343,7 +673,13 @@ impl ResourceState {
     pub fn commit(&mut self, state: ResourceState) {
         assert!(!matches!(state, ResourceState::Pending { .. }));
 
-        *self = state;
+        let old = std::mem::replace(self, state);
+        // 唤醒等待加载完成的 future
+        if let ResourceState::Pending { wakers } = old {
+            for waker in wakers.0 {
+                waker.wake();
+            }
+        }
     }
 
     pub fn commit_ok<T: ResourceData>(&mut self, data: T) {
@@ -0,0 +1,193 @@
+use std::{marker::PhantomData, path::PathBuf};
+
+use mini_core::thiserror::Error;
+use serde::de::DeserializeOwned;
+
+use crate::{
+    io::Reader,
+    loader::{LoadContext, ResourceLoader},
+    resource::ResourceData,
+};
+
+/// 从 `.ron` 文件反序列化 `T` 的加载器，用于配置和数据资源。
+pub struct RonLoader<T> {
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> RonLoader<T> {
+    pub fn new() -> Self {
+        Self {
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for RonLoader<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum RonLoaderError {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("could not parse {}:{line}:{column}: {message}", path.display())]
+    Parse {
+        path: PathBuf,
+        line: usize,
+        column: usize,
+        message: String,
+    },
+}
+
+impl<T> ResourceLoader for RonLoader<T>
+where
+    T: ResourceData + DeserializeOwned,
+{
+    type ResourceData = T;
+    type Settings = ();
+    type Error = RonLoaderError;
+
+    fn extensions(&self) -> &[&str] {
+        &["ron"]
+    }
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut dyn Reader,
+        _settings: &'a Self::Settings,
+        load_context: &'a mut LoadContext<'_>,
+    ) -> Result<Self::ResourceData, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        ron::de::from_bytes::<T>(&bytes).map_err(|err| RonLoaderError::Parse {
+            path: load_context.path().to_path_buf(),
+            line: err.position.line,
+            column: err.position.col,
+            message: err.code.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use mini_core::{
+        futures_lite::future::block_on,
+        type_uuid::TypeUuidProvider,
+        uuid::{uuid, Uuid},
+    };
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+    use crate::{
+        error::ResourceError,
+        io::{
+            Dir, MemoryAssetReader, ResourceSourceBuilder, ResourceSourceBuilders, ResourceSourceId,
+        },
+        manager::{test::memory_resource_manager, ResourceManager},
+        resource::ResourceState,
+    };
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, TypeUuidProvider, ResourceData)]
+    #[type_uuid(id = "0f5a3b7e-2c41-4d8e-9b6a-1e7d4c2f8a90")]
+    struct Config {
+        name: String,
+        volume: f32,
+    }
+
+    #[test]
+    fn test_ron_loader() {
+        let dir = Dir::new();
+        dir.insert_asset("config.ron", b"(name: \"mini\", volume: 0.5)".as_slice());
+        dir.insert_asset("broken.ron", b"(\n  name: 1,\n)".as_slice());
+
+        let resource_manager = memory_resource_manager(&dir);
+        resource_manager.add_ron_loader::<Config>();
+
+        let config = block_on(resource_manager.load_async::<Config>("config.ron"));
+        assert_eq!(
+            *config.data_ref(),
+            Config {
+                name: "mini".to_string(),
+                volume: 0.5,
+            }
+        );
+
+        let broken = block_on(resource_manager.load_async::<Config>("broken.ron"));
+        let header = broken.untyped.0.lock();
+        let ResourceState::LoadError { ref error } = header.state else {
+            panic!("broken.ron should fail to load");
+        };
+        let message = format!("{error:?}");
+        assert!(message.contains("broken.ron"));
+        assert!(message.contains("line: 2"));
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, TypeUuidProvider, ResourceData)]
+    #[type_uuid(id = "6c1e9d42-8b3f-4a7e-a5d0-3f9b2e8c7d14")]
+    struct Level {
+        width: u32,
+        height: u32,
+    }
+
+    #[test]
+    fn test_ron_loaders_share_extension() {
+        let dir = Dir::new();
+        dir.insert_asset("config.ron", b"(name: \"mini\", volume: 0.5)".as_slice());
+        dir.insert_asset("level.ron", b"(width: 16, height: 9)".as_slice());
+
+        let resource_manager = memory_resource_manager(&dir);
+        resource_manager.add_ron_loader::<Config>();
+        resource_manager.add_ron_loader::<Level>();
+
+        // 两个 loader 都支持 `.ron`，按照请求的类型选择 loader
+        let level = block_on(resource_manager.load_async::<Level>("level.ron"));
+        assert_eq!(
+            *level.data_ref(),
+            Level {
+                width: 16,
+                height: 9,
+            }
+        );
+        let config = resource_manager.load_sync::<Config>("config.ron").unwrap();
+        assert_eq!(config.data_ref().name, "mini");
+
+        let level = resource_manager.load::<Level>("level.ron");
+        assert_eq!(level.data_ref().width, 16);
+    }
+
+    #[test]
+    fn test_save() {
+        let dir = Dir::new();
+        let resource_manager = memory_resource_manager(&dir);
+        resource_manager.add_ron_loader::<Config>();
+
+        let config = Config {
+            name: "saved".to_string(),
+            volume: 0.25,
+        };
+        block_on(resource_manager.save("configs/saved.ron", &config)).unwrap();
+        block_on(resource_manager.save_meta("configs/saved.ron", &())).unwrap();
+        assert!(dir
+            .get_meta(std::path::Path::new("configs/saved.ron"))
+            .is_some());
+
+        let loaded = block_on(resource_manager.load_async::<Config>("configs/saved.ron"));
+        assert_eq!(*loaded.data_ref(), config);
+
+        // 只读的来源不能保存
+        let reader = MemoryAssetReader { root: dir.clone() };
+        let mut builders = ResourceSourceBuilders::default();
+        builders.insert(
+            ResourceSourceId::Default,
+            ResourceSourceBuilder::default().with_reader(move || Box::new(reader.clone())),
+        );
+        let read_only = ResourceManager::with_sources(Default::default(), builders);
+        assert!(matches!(
+            block_on(read_only.save("configs/saved.ron", &config)),
+            Err(ResourceError::MissingAssetWriterError(_))
+        ));
+    }
+}
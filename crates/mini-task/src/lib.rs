@@ -1,3 +1,4 @@
+#[cfg(not(target_arch = "wasm32"))]
 use futures::executor::ThreadPool;
 use mini_core::uuid::Uuid;
 use parking_lot::Mutex;
@@ -7,16 +8,35 @@ use std::{
     sync::mpsc::{self, Receiver, Sender},
 };
 
+mod local;
+
+pub use local::*;
+
+#[cfg(target_arch = "wasm32")]
+thread_local! {
+    //wasm32 上只有一个线程，所有任务池共享这个执行器
+    static LOCAL_TASK_POOL: LocalTaskPool = LocalTaskPool::new();
+}
+
+/// 任务池，wasm32 上没有线程，任务由 [`LocalTaskPool`] 在当前线程执行
 pub struct TaskPool {
+    #[cfg(not(target_arch = "wasm32"))]
     thread_pool: ThreadPool,
     sender: Sender<TaskResult>,
     receiver: Mutex<Receiver<TaskResult>>,
 }
 
+impl Default for TaskPool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl TaskPool {
     pub fn new() -> Self {
         let (sender, receiver) = mpsc::channel();
         Self {
+            #[cfg(not(target_arch = "wasm32"))]
             thread_pool: ThreadPool::new().unwrap(),
             sender,
             receiver: Mutex::new(receiver),
@@ -26,9 +46,12 @@ impl TaskPool {
     //执行task
     pub fn spawn_task<F>(&self, future: F)
     where
-        F: Future<Output = ()> + Send + 'static,
+        F: Future<Output = ()> + MaybeSend + 'static,
     {
+        #[cfg(not(target_arch = "wasm32"))]
         self.thread_pool.spawn_ok(future);
+        #[cfg(target_arch = "wasm32")]
+        LOCAL_TASK_POOL.with(|pool| pool.spawn_task(future));
     }
 
     //提供给资源加载器的接口，异步加载资源
@@ -56,6 +79,16 @@ impl TaskPool {
     pub fn next_task_result(&self) -> Option<TaskResult> {
         self.receiver.lock().try_recv().ok()
     }
+
+    /// 执行本地队列中能够继续的任务，返回完成的任务数量，需要在每帧调用。
+    ///
+    /// 使用线程池时任务在后台执行，这里直接返回 0
+    pub fn run_until_stalled(&self) -> usize {
+        #[cfg(not(target_arch = "wasm32"))]
+        return 0;
+        #[cfg(target_arch = "wasm32")]
+        return LOCAL_TASK_POOL.with(LocalTaskPool::run_until_stalled);
+    }
 }
 
 pub struct TaskResult {
@@ -63,9 +96,23 @@ pub struct TaskResult {
     pub payload: Box<dyn AsyncTaskResult>,
 }
 
-pub trait AsyncTask<R: AsyncTaskResult>: Future<Output = R> + Send + 'static {}
+/// 在有线程的平台上等同于 `Send`，wasm32 上任务在当前线程执行，所有类型都满足
+#[cfg(not(target_arch = "wasm32"))]
+pub trait MaybeSend: Send {}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<T: Send> MaybeSend for T {}
+
+/// 在有线程的平台上等同于 `Send`，wasm32 上任务在当前线程执行，所有类型都满足
+#[cfg(target_arch = "wasm32")]
+pub trait MaybeSend {}
+
+#[cfg(target_arch = "wasm32")]
+impl<T> MaybeSend for T {}
+
+pub trait AsyncTask<R: AsyncTaskResult>: Future<Output = R> + MaybeSend + 'static {}
 
-impl<T, R: AsyncTaskResult> AsyncTask<R> for T where T: Future<Output = R> + Send + 'static {}
+impl<T, R: AsyncTaskResult> AsyncTask<R> for T where T: Future<Output = R> + MaybeSend + 'static {}
 
 pub trait AsyncTaskResult: Any + Send + 'static {
     fn into_any(self: Box<Self>) -> Box<dyn Any>;
@@ -0,0 +1,137 @@
+use futures::{
+    executor::{LocalPool, LocalSpawner},
+    task::LocalSpawnExt,
+};
+use mini_core::uuid::Uuid;
+use std::{
+    cell::RefCell,
+    future::Future,
+    sync::mpsc::{self, Receiver, Sender},
+};
+
+use crate::{AsyncTaskResult, TaskResult};
+
+/// 在当前线程上执行任务的任务池，用于没有线程的平台（例如 wasm32）。
+///
+/// 任务不需要实现 `Send`，也不会自动执行，需要每帧调用 [`LocalTaskPool::run_until_stalled`]。
+pub struct LocalTaskPool {
+    pool: RefCell<LocalPool>,
+    spawner: LocalSpawner,
+    sender: Sender<TaskResult>,
+    receiver: Receiver<TaskResult>,
+}
+
+impl Default for LocalTaskPool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LocalTaskPool {
+    pub fn new() -> Self {
+        let pool = LocalPool::new();
+        let spawner = pool.spawner();
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            pool: RefCell::new(pool),
+            spawner,
+            sender,
+            receiver,
+        }
+    }
+
+    //把任务放入队列，下一次 run_until_stalled 时执行
+    pub fn spawn_task<F>(&self, future: F)
+    where
+        F: Future<Output = ()> + 'static,
+    {
+        // 执行器和 spawner 一起释放，不会出现执行器已经关闭的情况
+        self.spawner
+            .spawn_local(future)
+            .expect("the local pool is owned by the task pool");
+    }
+
+    #[inline]
+    pub fn spawn_with_result<F, T>(&self, future: F) -> Uuid
+    where
+        F: Future<Output = T> + 'static,
+        T: AsyncTaskResult,
+    {
+        let id = Uuid::new_v4();
+        let sender = self.sender.clone();
+        self.spawn_task(async move {
+            let result = future.await;
+            sender
+                .send(TaskResult {
+                    id,
+                    payload: Box::new(result),
+                })
+                .unwrap();
+        });
+        id
+    }
+
+    #[inline]
+    pub fn next_task_result(&self) -> Option<TaskResult> {
+        self.receiver.try_recv().ok()
+    }
+
+    /// 执行所有能够继续的任务，直到剩下的任务都在等待，返回完成的任务数量。
+    ///
+    /// # Panics
+    /// 在任务中调用时 panic。
+    pub fn run_until_stalled(&self) -> usize {
+        let mut pool = self.pool.borrow_mut();
+        let mut completed = 0;
+        while pool.try_run_one() {
+            completed += 1;
+        }
+        completed
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::rc::Rc;
+
+    use futures::channel::oneshot;
+
+    use super::*;
+
+    #[test]
+    fn test_local_task_pool() {
+        let pool = LocalTaskPool::new();
+        let (sender, receiver) = oneshot::channel::<u32>();
+
+        let id = pool.spawn_with_result(async move { receiver.await.unwrap() * 2 });
+        pool.spawn_task(async {});
+        assert!(pool.next_task_result().is_none());
+
+        // 没有调用 run_until_stalled 之前任务不会执行
+        assert_eq!(pool.run_until_stalled(), 1);
+        assert!(pool.next_task_result().is_none());
+        assert_eq!(pool.run_until_stalled(), 0);
+
+        // 唤醒等待中的任务
+        sender.send(21).unwrap();
+        assert_eq!(pool.run_until_stalled(), 1);
+
+        let result = pool.next_task_result().unwrap();
+        assert_eq!(result.id, id);
+        assert_eq!(*result.payload.downcast::<u32>().unwrap(), 42);
+    }
+
+    #[test]
+    fn test_local_task_pool_non_send() {
+        let pool = LocalTaskPool::new();
+        // Rc 不是 Send，只能在当前线程上执行
+        let value = Rc::new(RefCell::new(0));
+        let shared = value.clone();
+        pool.spawn_task(async move {
+            *shared.borrow_mut() += 1;
+        });
+
+        assert_eq!(pool.run_until_stalled(), 1);
+        assert_eq!(*value.borrow(), 1);
+    }
+}
@@ -0,0 +1,78 @@
+/// 鼠标指针的图标，与 winit 的 `CursorIcon` 一一对应
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CursorIcon {
+    #[default]
+    Default,
+    ContextMenu,
+    Help,
+    Pointer,
+    Progress,
+    Wait,
+    Cell,
+    Crosshair,
+    Text,
+    VerticalText,
+    Alias,
+    Copy,
+    Move,
+    NoDrop,
+    NotAllowed,
+    Grab,
+    Grabbing,
+    EResize,
+    NResize,
+    NeResize,
+    NwResize,
+    SResize,
+    SeResize,
+    SwResize,
+    WResize,
+    EwResize,
+    NsResize,
+    NeswResize,
+    NwseResize,
+    ColResize,
+    RowResize,
+    AllScroll,
+    ZoomIn,
+    ZoomOut,
+}
+
+impl CursorIcon {
+    pub const ALL: &'static [CursorIcon] = &[
+        CursorIcon::Default,
+        CursorIcon::ContextMenu,
+        CursorIcon::Help,
+        CursorIcon::Pointer,
+        CursorIcon::Progress,
+        CursorIcon::Wait,
+        CursorIcon::Cell,
+        CursorIcon::Crosshair,
+        CursorIcon::Text,
+        CursorIcon::VerticalText,
+        CursorIcon::Alias,
+        CursorIcon::Copy,
+        CursorIcon::Move,
+        CursorIcon::NoDrop,
+        CursorIcon::NotAllowed,
+        CursorIcon::Grab,
+        CursorIcon::Grabbing,
+        CursorIcon::EResize,
+        CursorIcon::NResize,
+        CursorIcon::NeResize,
+        CursorIcon::NwResize,
+        CursorIcon::SResize,
+        CursorIcon::SeResize,
+        CursorIcon::SwResize,
+        CursorIcon::WResize,
+        CursorIcon::EwResize,
+        CursorIcon::NsResize,
+        CursorIcon::NeswResize,
+        CursorIcon::NwseResize,
+        CursorIcon::ColResize,
+        CursorIcon::RowResize,
+        CursorIcon::AllScroll,
+        CursorIcon::ZoomIn,
+        CursorIcon::ZoomOut,
+    ];
+}
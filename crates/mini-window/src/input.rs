@@ -0,0 +1,280 @@
+use std::{collections::HashSet, hash::Hash};
+
+use mini_math::Vec2;
+
+/// 键盘上按键的物理位置，与 winit 的 `KeyCode` 同名
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyCode {
+    Backquote,
+    Backslash,
+    BracketLeft,
+    BracketRight,
+    Comma,
+    Digit0,
+    Digit1,
+    Digit2,
+    Digit3,
+    Digit4,
+    Digit5,
+    Digit6,
+    Digit7,
+    Digit8,
+    Digit9,
+    Equal,
+    KeyA,
+    KeyB,
+    KeyC,
+    KeyD,
+    KeyE,
+    KeyF,
+    KeyG,
+    KeyH,
+    KeyI,
+    KeyJ,
+    KeyK,
+    KeyL,
+    KeyM,
+    KeyN,
+    KeyO,
+    KeyP,
+    KeyQ,
+    KeyR,
+    KeyS,
+    KeyT,
+    KeyU,
+    KeyV,
+    KeyW,
+    KeyX,
+    KeyY,
+    KeyZ,
+    Minus,
+    Period,
+    Quote,
+    Semicolon,
+    Slash,
+    AltLeft,
+    AltRight,
+    Backspace,
+    CapsLock,
+    ControlLeft,
+    ControlRight,
+    Enter,
+    SuperLeft,
+    SuperRight,
+    ShiftLeft,
+    ShiftRight,
+    Space,
+    Tab,
+    Delete,
+    End,
+    Home,
+    Insert,
+    PageDown,
+    PageUp,
+    ArrowDown,
+    ArrowLeft,
+    ArrowRight,
+    ArrowUp,
+    Escape,
+    F1,
+    F2,
+    F3,
+    F4,
+    F5,
+    F6,
+    F7,
+    F8,
+    F9,
+    F10,
+    F11,
+    F12,
+}
+
+/// 鼠标按键
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MouseButton {
+    Left,
+    Right,
+    Middle,
+    Back,
+    Forward,
+    Other(u16),
+}
+
+/// 记录按键的按下和松开状态
+#[derive(Debug, Clone)]
+pub struct ButtonInput<T> {
+    pressed: HashSet<T>,
+    just_pressed: HashSet<T>,
+    just_released: HashSet<T>,
+}
+
+impl<T> Default for ButtonInput<T> {
+    fn default() -> Self {
+        Self {
+            pressed: HashSet::new(),
+            just_pressed: HashSet::new(),
+            just_released: HashSet::new(),
+        }
+    }
+}
+
+impl<T: Copy + Eq + Hash> ButtonInput<T> {
+    //按住不放时的重复事件不会再次触发 just_pressed
+    pub fn press(&mut self, input: T) {
+        if self.pressed.insert(input) {
+            self.just_pressed.insert(input);
+        }
+    }
+
+    pub fn release(&mut self, input: T) {
+        if self.pressed.remove(&input) {
+            self.just_released.insert(input);
+        }
+    }
+
+    pub fn release_all(&mut self) {
+        self.just_released.extend(self.pressed.drain());
+    }
+
+    pub fn pressed(&self, input: T) -> bool {
+        self.pressed.contains(&input)
+    }
+
+    pub fn just_pressed(&self, input: T) -> bool {
+        self.just_pressed.contains(&input)
+    }
+
+    pub fn just_released(&self, input: T) -> bool {
+        self.just_released.contains(&input)
+    }
+
+    pub fn get_pressed(&self) -> impl Iterator<Item = &T> {
+        self.pressed.iter()
+    }
+
+    /// 清除这一帧的 just_pressed 和 just_released
+    pub fn clear(&mut self) {
+        self.just_pressed.clear();
+        self.just_released.clear();
+    }
+}
+
+/// 键盘和鼠标的输入状态，每帧结束时由引擎调用 [`Input::clear`]
+#[derive(Debug, Clone, Default)]
+pub struct Input {
+    pub keyboard: ButtonInput<KeyCode>,
+    pub mouse: ButtonInput<MouseButton>,
+    //窗口内的鼠标位置，单位为物理像素
+    cursor_position: Option<Vec2>,
+    //这一帧的滚动距离，单位为行
+    scroll_delta: Vec2,
+    //这一帧设备报告的原始鼠标移动，不受光标加速和窗口边界影响
+    mouse_motion: Vec2,
+}
+
+impl Input {
+    pub fn pressed(&self, key: KeyCode) -> bool {
+        self.keyboard.pressed(key)
+    }
+
+    pub fn just_pressed(&self, key: KeyCode) -> bool {
+        self.keyboard.just_pressed(key)
+    }
+
+    pub fn just_released(&self, key: KeyCode) -> bool {
+        self.keyboard.just_released(key)
+    }
+
+    pub fn mouse_pressed(&self, button: MouseButton) -> bool {
+        self.mouse.pressed(button)
+    }
+
+    pub fn mouse_just_pressed(&self, button: MouseButton) -> bool {
+        self.mouse.just_pressed(button)
+    }
+
+    pub fn mouse_just_released(&self, button: MouseButton) -> bool {
+        self.mouse.just_released(button)
+    }
+
+    pub fn cursor_position(&self) -> Option<Vec2> {
+        self.cursor_position
+    }
+
+    pub fn set_cursor_position(&mut self, position: Option<Vec2>) {
+        self.cursor_position = position;
+    }
+
+    pub fn scroll_delta(&self) -> Vec2 {
+        self.scroll_delta
+    }
+
+    pub fn add_scroll_delta(&mut self, delta: Vec2) {
+        self.scroll_delta += delta;
+    }
+
+    /// 这一帧累计的原始鼠标移动，适合用于第一人称相机的视角控制
+    pub fn mouse_motion(&self) -> Vec2 {
+        self.mouse_motion
+    }
+
+    pub fn add_mouse_motion(&mut self, delta: Vec2) {
+        self.mouse_motion += delta;
+    }
+
+    pub fn clear(&mut self) {
+        self.keyboard.clear();
+        self.mouse.clear();
+        self.scroll_delta = Vec2::ZERO;
+        self.mouse_motion = Vec2::ZERO;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_button_input() {
+        let mut input = Input::default();
+
+        input.keyboard.press(KeyCode::KeyW);
+        assert!(input.pressed(KeyCode::KeyW));
+        assert!(input.just_pressed(KeyCode::KeyW));
+
+        input.clear();
+        // 重复的按下事件不会再次触发 just_pressed
+        input.keyboard.press(KeyCode::KeyW);
+        assert!(input.pressed(KeyCode::KeyW));
+        assert!(!input.just_pressed(KeyCode::KeyW));
+
+        input.keyboard.release(KeyCode::KeyW);
+        assert!(!input.pressed(KeyCode::KeyW));
+        assert!(input.just_released(KeyCode::KeyW));
+
+        input.mouse.press(MouseButton::Left);
+        input.add_scroll_delta(Vec2::new(0.0, 1.0));
+        input.add_scroll_delta(Vec2::new(0.0, 2.0));
+        assert!(input.mouse_just_pressed(MouseButton::Left));
+        assert_eq!(input.scroll_delta(), Vec2::new(0.0, 3.0));
+
+        input.clear();
+        assert!(!input.just_released(KeyCode::KeyW));
+        assert!(!input.mouse_just_pressed(MouseButton::Left));
+        assert!(input.mouse_pressed(MouseButton::Left));
+        assert_eq!(input.scroll_delta(), Vec2::ZERO);
+    }
+
+    #[test]
+    fn test_mouse_motion() {
+        let mut input = Input::default();
+
+        input.add_mouse_motion(Vec2::new(1.0, -2.0));
+        input.add_mouse_motion(Vec2::new(3.5, 0.5));
+        assert_eq!(input.mouse_motion(), Vec2::new(4.5, -1.5));
+
+        input.clear();
+        assert_eq!(input.mouse_motion(), Vec2::ZERO);
+    }
+}
@@ -1,7 +1,11 @@
+pub mod cursor;
+pub mod input;
 pub mod window;
 pub mod window_wrapper;
 
 pub mod prelude {
+    pub use crate::cursor::*;
+    pub use crate::input::*;
     pub use crate::window::*;
     pub use crate::window_wrapper::*;
 }
@@ -1,6 +1,6 @@
-use mini_math::UVec2;
+use mini_math::{IVec2, UVec2, Vec2};
 
-use crate::prelude::{RawHandleWrapper, RawHandleWrapperHolder};
+use crate::prelude::{CursorIcon, RawHandleWrapper, RawHandleWrapperHolder};
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Copy)]
 pub struct WindowId(u64);
@@ -11,7 +11,7 @@ impl WindowId {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct WindowResolution {
     /// Width of the window in physical pixels.
     physical_width: u32,
@@ -27,9 +27,60 @@ pub struct WindowResolution {
     scale_factor: f32,
 }
 impl WindowResolution {
+    pub fn new(physical_width: u32, physical_height: u32) -> Self {
+        WindowResolution {
+            physical_width,
+            physical_height,
+            ..Default::default()
+        }
+    }
+
     pub fn physical_size(&self) -> UVec2 {
         UVec2::new(self.physical_width, self.physical_height)
     }
+
+    /// The window's client area size in logical pixels, i.e. the physical size divided by [`scale_factor`](Self::scale_factor).
+    pub fn logical_size(&self) -> Vec2 {
+        self.to_logical(self.physical_size().as_vec2())
+    }
+
+    /// Converts a position or size in physical pixels to logical pixels, useful for UI layout.
+    pub fn to_logical(&self, physical: Vec2) -> Vec2 {
+        physical / self.scale_factor()
+    }
+
+    /// The ratio of physical pixels to logical pixels.
+    ///
+    /// Returns the override when set, otherwise the value provided by the OS.
+    pub fn scale_factor(&self) -> f32 {
+        self.scale_factor_override.unwrap_or(self.scale_factor)
+    }
+
+    /// The ratio of physical pixels to logical pixels provided by the OS, ignoring the override.
+    pub fn base_scale_factor(&self) -> f32 {
+        self.scale_factor
+    }
+
+    pub fn scale_factor_override(&self) -> Option<f32> {
+        self.scale_factor_override
+    }
+
+    /// Sets the window's physical size, e.g. after the OS resized the window.
+    pub fn set_physical_resolution(&mut self, physical_width: u32, physical_height: u32) {
+        self.physical_width = physical_width;
+        self.physical_height = physical_height;
+    }
+
+    /// Sets the scale factor provided by the OS. It has no effect on [`scale_factor`](Self::scale_factor)
+    /// while an override is set.
+    pub fn set_scale_factor(&mut self, scale_factor: f32) {
+        self.scale_factor = scale_factor;
+    }
+
+    /// Overrides the scale factor provided by the OS, `None` restores it.
+    pub fn set_scale_factor_override(&mut self, scale_factor_override: Option<f32>) {
+        self.scale_factor_override = scale_factor_override;
+    }
 }
 impl Default for WindowResolution {
     fn default() -> Self {
@@ -42,10 +93,35 @@ impl Default for WindowResolution {
     }
 }
 
-#[derive(Debug, Clone)]
+/// 显示模式，决定画面如何与显示器的刷新同步。
+///
+/// surface 不支持选择的模式时使用 `Fifo`。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum PresentMode {
+    /// 开启垂直同步，优先使用 `FifoRelaxed`，其次是 `Fifo`
+    AutoVsync,
+    /// 关闭垂直同步，优先使用 `Immediate`，其次是 `Mailbox`，都不支持时使用 `Fifo`
+    AutoNoVsync,
+    /// 所有平台都支持
+    #[default]
+    Fifo,
+    FifoRelaxed,
+    Immediate,
+    Mailbox,
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct Window {
     pub resolution: WindowResolution,
     pub title: String,
+    pub cursor_icon: CursorIcon,
+    //是否可以调整大小
+    pub resizable: bool,
+    //是否有标题栏和边框
+    pub decorations: bool,
+    //窗口左上角在屏幕上的位置，单位为物理像素，为 None 时在主显示器上居中
+    pub position: Option<IVec2>,
+    pub present_mode: PresentMode,
 }
 
 #[derive(Debug, Clone)]
@@ -61,6 +137,11 @@ impl Default for Window {
         Window {
             resolution: Default::default(),
             title: "App".to_string(),
+            cursor_icon: CursorIcon::default(),
+            resizable: true,
+            decorations: true,
+            position: None,
+            present_mode: PresentMode::default(),
         }
     }
 }
@@ -69,6 +150,42 @@ impl Window {
     pub fn physical_size(&self) -> UVec2 {
         self.resolution.physical_size()
     }
+
+    /// 逻辑像素表示的窗口大小，UI 布局使用这个大小
+    pub fn logical_size(&self) -> Vec2 {
+        self.resolution.logical_size()
+    }
+
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// 宽高的单位为物理像素
+    pub fn with_resolution(mut self, physical_width: u32, physical_height: u32) -> Self {
+        self.resolution = WindowResolution::new(physical_width, physical_height);
+        self
+    }
+
+    pub fn with_resizable(mut self, resizable: bool) -> Self {
+        self.resizable = resizable;
+        self
+    }
+
+    pub fn with_decorations(mut self, decorations: bool) -> Self {
+        self.decorations = decorations;
+        self
+    }
+
+    pub fn with_position(mut self, position: IVec2) -> Self {
+        self.position = Some(position);
+        self
+    }
+
+    pub fn with_present_mode(mut self, present_mode: PresentMode) -> Self {
+        self.present_mode = present_mode;
+        self
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -86,3 +203,58 @@ pub enum AppLifecycle {
     /// Applications have one extra frame to react to this event before being fully resumed.
     WillResume,
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_window_builder() {
+        let window = Window::default();
+        assert!(window.resizable);
+        assert!(window.decorations);
+        assert_eq!(window.position, None);
+
+        let window = Window::default()
+            .with_title("Editor")
+            .with_resolution(800, 600)
+            .with_resizable(false)
+            .with_decorations(false)
+            .with_position(IVec2::new(10, 20))
+            .with_present_mode(PresentMode::Mailbox);
+        assert_eq!(window.title, "Editor");
+        assert_eq!(window.physical_size(), UVec2::new(800, 600));
+        assert_eq!(window.resolution.scale_factor(), 1.0);
+        assert!(!window.resizable);
+        assert!(!window.decorations);
+        assert_eq!(window.position, Some(IVec2::new(10, 20)));
+        assert_eq!(window.present_mode, PresentMode::Mailbox);
+    }
+
+    #[test]
+    fn test_scale_factor_override() {
+        let mut resolution = WindowResolution::new(1920, 1080);
+        resolution.set_scale_factor(2.0);
+        assert_eq!(resolution.scale_factor(), 2.0);
+        assert_eq!(resolution.logical_size(), Vec2::new(960.0, 540.0));
+
+        // 设置覆盖值后忽略系统提供的缩放
+        resolution.set_scale_factor_override(Some(1.5));
+        resolution.set_scale_factor(3.0);
+        assert_eq!(resolution.scale_factor(), 1.5);
+        assert_eq!(resolution.base_scale_factor(), 3.0);
+        assert_eq!(resolution.logical_size(), Vec2::new(1280.0, 720.0));
+
+        resolution.set_scale_factor_override(None);
+        assert_eq!(resolution.scale_factor(), 3.0);
+        assert_eq!(
+            resolution.to_logical(Vec2::new(300.0, 150.0)),
+            Vec2::new(100.0, 50.0)
+        );
+
+        // 物理大小不受缩放影响
+        resolution.set_physical_resolution(600, 300);
+        assert_eq!(resolution.physical_size(), UVec2::new(600, 300));
+        assert_eq!(resolution.logical_size(), Vec2::new(200.0, 100.0));
+    }
+}
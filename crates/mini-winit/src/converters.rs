@@ -0,0 +1,216 @@
+use mini_math::Vec2;
+use mini_window::{
+    cursor::CursorIcon,
+    input::{KeyCode, MouseButton},
+    window::WindowId,
+};
+use winit::{event::MouseScrollDelta, keyboard::PhysicalKey};
+
+//把像素表示的滚动距离换算成行
+const PIXELS_PER_LINE: f32 = 20.0;
+
+pub fn convert_window_id(window_id: winit::window::WindowId) -> WindowId {
+    WindowId::new(window_id.into())
+}
+
+pub fn convert_cursor_icon(cursor_icon: CursorIcon) -> winit::window::CursorIcon {
+    match cursor_icon {
+        CursorIcon::Default => winit::window::CursorIcon::Default,
+        CursorIcon::ContextMenu => winit::window::CursorIcon::ContextMenu,
+        CursorIcon::Help => winit::window::CursorIcon::Help,
+        CursorIcon::Pointer => winit::window::CursorIcon::Pointer,
+        CursorIcon::Progress => winit::window::CursorIcon::Progress,
+        CursorIcon::Wait => winit::window::CursorIcon::Wait,
+        CursorIcon::Cell => winit::window::CursorIcon::Cell,
+        CursorIcon::Crosshair => winit::window::CursorIcon::Crosshair,
+        CursorIcon::Text => winit::window::CursorIcon::Text,
+        CursorIcon::VerticalText => winit::window::CursorIcon::VerticalText,
+        CursorIcon::Alias => winit::window::CursorIcon::Alias,
+        CursorIcon::Copy => winit::window::CursorIcon::Copy,
+        CursorIcon::Move => winit::window::CursorIcon::Move,
+        CursorIcon::NoDrop => winit::window::CursorIcon::NoDrop,
+        CursorIcon::NotAllowed => winit::window::CursorIcon::NotAllowed,
+        CursorIcon::Grab => winit::window::CursorIcon::Grab,
+        CursorIcon::Grabbing => winit::window::CursorIcon::Grabbing,
+        CursorIcon::EResize => winit::window::CursorIcon::EResize,
+        CursorIcon::NResize => winit::window::CursorIcon::NResize,
+        CursorIcon::NeResize => winit::window::CursorIcon::NeResize,
+        CursorIcon::NwResize => winit::window::CursorIcon::NwResize,
+        CursorIcon::SResize => winit::window::CursorIcon::SResize,
+        CursorIcon::SeResize => winit::window::CursorIcon::SeResize,
+        CursorIcon::SwResize => winit::window::CursorIcon::SwResize,
+        CursorIcon::WResize => winit::window::CursorIcon::WResize,
+        CursorIcon::EwResize => winit::window::CursorIcon::EwResize,
+        CursorIcon::NsResize => winit::window::CursorIcon::NsResize,
+        CursorIcon::NeswResize => winit::window::CursorIcon::NeswResize,
+        CursorIcon::NwseResize => winit::window::CursorIcon::NwseResize,
+        CursorIcon::ColResize => winit::window::CursorIcon::ColResize,
+        CursorIcon::RowResize => winit::window::CursorIcon::RowResize,
+        CursorIcon::AllScroll => winit::window::CursorIcon::AllScroll,
+        CursorIcon::ZoomIn => winit::window::CursorIcon::ZoomIn,
+        CursorIcon::ZoomOut => winit::window::CursorIcon::ZoomOut,
+    }
+}
+
+/// 不支持的按键返回 `None`
+pub fn convert_key_code(physical_key: PhysicalKey) -> Option<KeyCode> {
+    let PhysicalKey::Code(key_code) = physical_key else {
+        return None;
+    };
+
+    let key_code = match key_code {
+        winit::keyboard::KeyCode::Backquote => KeyCode::Backquote,
+        winit::keyboard::KeyCode::Backslash => KeyCode::Backslash,
+        winit::keyboard::KeyCode::BracketLeft => KeyCode::BracketLeft,
+        winit::keyboard::KeyCode::BracketRight => KeyCode::BracketRight,
+        winit::keyboard::KeyCode::Comma => KeyCode::Comma,
+        winit::keyboard::KeyCode::Digit0 => KeyCode::Digit0,
+        winit::keyboard::KeyCode::Digit1 => KeyCode::Digit1,
+        winit::keyboard::KeyCode::Digit2 => KeyCode::Digit2,
+        winit::keyboard::KeyCode::Digit3 => KeyCode::Digit3,
+        winit::keyboard::KeyCode::Digit4 => KeyCode::Digit4,
+        winit::keyboard::KeyCode::Digit5 => KeyCode::Digit5,
+        winit::keyboard::KeyCode::Digit6 => KeyCode::Digit6,
+        winit::keyboard::KeyCode::Digit7 => KeyCode::Digit7,
+        winit::keyboard::KeyCode::Digit8 => KeyCode::Digit8,
+        winit::keyboard::KeyCode::Digit9 => KeyCode::Digit9,
+        winit::keyboard::KeyCode::Equal => KeyCode::Equal,
+        winit::keyboard::KeyCode::KeyA => KeyCode::KeyA,
+        winit::keyboard::KeyCode::KeyB => KeyCode::KeyB,
+        winit::keyboard::KeyCode::KeyC => KeyCode::KeyC,
+        winit::keyboard::KeyCode::KeyD => KeyCode::KeyD,
+        winit::keyboard::KeyCode::KeyE => KeyCode::KeyE,
+        winit::keyboard::KeyCode::KeyF => KeyCode::KeyF,
+        winit::keyboard::KeyCode::KeyG => KeyCode::KeyG,
+        winit::keyboard::KeyCode::KeyH => KeyCode::KeyH,
+        winit::keyboard::KeyCode::KeyI => KeyCode::KeyI,
+        winit::keyboard::KeyCode::KeyJ => KeyCode::KeyJ,
+        winit::keyboard::KeyCode::KeyK => KeyCode::KeyK,
+        winit::keyboard::KeyCode::KeyL => KeyCode::KeyL,
+        winit::keyboard::KeyCode::KeyM => KeyCode::KeyM,
+        winit::keyboard::KeyCode::KeyN => KeyCode::KeyN,
+        winit::keyboard::KeyCode::KeyO => KeyCode::KeyO,
+        winit::keyboard::KeyCode::KeyP => KeyCode::KeyP,
+        winit::keyboard::KeyCode::KeyQ => KeyCode::KeyQ,
+        winit::keyboard::KeyCode::KeyR => KeyCode::KeyR,
+        winit::keyboard::KeyCode::KeyS => KeyCode::KeyS,
+        winit::keyboard::KeyCode::KeyT => KeyCode::KeyT,
+        winit::keyboard::KeyCode::KeyU => KeyCode::KeyU,
+        winit::keyboard::KeyCode::KeyV => KeyCode::KeyV,
+        winit::keyboard::KeyCode::KeyW => KeyCode::KeyW,
+        winit::keyboard::KeyCode::KeyX => KeyCode::KeyX,
+        winit::keyboard::KeyCode::KeyY => KeyCode::KeyY,
+        winit::keyboard::KeyCode::KeyZ => KeyCode::KeyZ,
+        winit::keyboard::KeyCode::Minus => KeyCode::Minus,
+        winit::keyboard::KeyCode::Period => KeyCode::Period,
+        winit::keyboard::KeyCode::Quote => KeyCode::Quote,
+        winit::keyboard::KeyCode::Semicolon => KeyCode::Semicolon,
+        winit::keyboard::KeyCode::Slash => KeyCode::Slash,
+        winit::keyboard::KeyCode::AltLeft => KeyCode::AltLeft,
+        winit::keyboard::KeyCode::AltRight => KeyCode::AltRight,
+        winit::keyboard::KeyCode::Backspace => KeyCode::Backspace,
+        winit::keyboard::KeyCode::CapsLock => KeyCode::CapsLock,
+        winit::keyboard::KeyCode::ControlLeft => KeyCode::ControlLeft,
+        winit::keyboard::KeyCode::ControlRight => KeyCode::ControlRight,
+        winit::keyboard::KeyCode::Enter => KeyCode::Enter,
+        winit::keyboard::KeyCode::SuperLeft => KeyCode::SuperLeft,
+        winit::keyboard::KeyCode::SuperRight => KeyCode::SuperRight,
+        winit::keyboard::KeyCode::ShiftLeft => KeyCode::ShiftLeft,
+        winit::keyboard::KeyCode::ShiftRight => KeyCode::ShiftRight,
+        winit::keyboard::KeyCode::Space => KeyCode::Space,
+        winit::keyboard::KeyCode::Tab => KeyCode::Tab,
+        winit::keyboard::KeyCode::Delete => KeyCode::Delete,
+        winit::keyboard::KeyCode::End => KeyCode::End,
+        winit::keyboard::KeyCode::Home => KeyCode::Home,
+        winit::keyboard::KeyCode::Insert => KeyCode::Insert,
+        winit::keyboard::KeyCode::PageDown => KeyCode::PageDown,
+        winit::keyboard::KeyCode::PageUp => KeyCode::PageUp,
+        winit::keyboard::KeyCode::ArrowDown => KeyCode::ArrowDown,
+        winit::keyboard::KeyCode::ArrowLeft => KeyCode::ArrowLeft,
+        winit::keyboard::KeyCode::ArrowRight => KeyCode::ArrowRight,
+        winit::keyboard::KeyCode::ArrowUp => KeyCode::ArrowUp,
+        winit::keyboard::KeyCode::Escape => KeyCode::Escape,
+        winit::keyboard::KeyCode::F1 => KeyCode::F1,
+        winit::keyboard::KeyCode::F2 => KeyCode::F2,
+        winit::keyboard::KeyCode::F3 => KeyCode::F3,
+        winit::keyboard::KeyCode::F4 => KeyCode::F4,
+        winit::keyboard::KeyCode::F5 => KeyCode::F5,
+        winit::keyboard::KeyCode::F6 => KeyCode::F6,
+        winit::keyboard::KeyCode::F7 => KeyCode::F7,
+        winit::keyboard::KeyCode::F8 => KeyCode::F8,
+        winit::keyboard::KeyCode::F9 => KeyCode::F9,
+        winit::keyboard::KeyCode::F10 => KeyCode::F10,
+        winit::keyboard::KeyCode::F11 => KeyCode::F11,
+        winit::keyboard::KeyCode::F12 => KeyCode::F12,
+        _ => return None,
+    };
+    Some(key_code)
+}
+
+pub fn convert_mouse_button(mouse_button: winit::event::MouseButton) -> MouseButton {
+    match mouse_button {
+        winit::event::MouseButton::Left => MouseButton::Left,
+        winit::event::MouseButton::Right => MouseButton::Right,
+        winit::event::MouseButton::Middle => MouseButton::Middle,
+        winit::event::MouseButton::Back => MouseButton::Back,
+        winit::event::MouseButton::Forward => MouseButton::Forward,
+        winit::event::MouseButton::Other(button) => MouseButton::Other(button),
+    }
+}
+
+/// 滚动距离统一使用行作为单位
+pub fn convert_scroll_delta(delta: MouseScrollDelta) -> Vec2 {
+    match delta {
+        MouseScrollDelta::LineDelta(x, y) => Vec2::new(x, y),
+        MouseScrollDelta::PixelDelta(position) => {
+            Vec2::new(position.x as f32, position.y as f32) / PIXELS_PER_LINE
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_convert_cursor_icon() {
+        assert_eq!(
+            convert_cursor_icon(CursorIcon::Pointer),
+            winit::window::CursorIcon::Pointer
+        );
+        assert_eq!(
+            convert_cursor_icon(CursorIcon::Text),
+            winit::window::CursorIcon::Text
+        );
+        assert_eq!(
+            convert_cursor_icon(CursorIcon::NwseResize),
+            winit::window::CursorIcon::NwseResize
+        );
+        assert_eq!(
+            convert_cursor_icon(CursorIcon::default()),
+            winit::window::CursorIcon::default()
+        );
+
+        // 名称一致说明每个图标都映射到了同名的 winit 图标
+        for cursor_icon in CursorIcon::ALL {
+            let winit_icon = convert_cursor_icon(*cursor_icon);
+            assert_eq!(format!("{cursor_icon:?}"), format!("{winit_icon:?}"));
+        }
+    }
+
+    #[test]
+    fn test_convert_key_code() {
+        assert_eq!(
+            convert_key_code(PhysicalKey::Code(winit::keyboard::KeyCode::KeyW)),
+            Some(KeyCode::KeyW)
+        );
+        assert_eq!(
+            convert_key_code(PhysicalKey::Code(winit::keyboard::KeyCode::Escape)),
+            Some(KeyCode::Escape)
+        );
+        assert_eq!(
+            convert_key_code(PhysicalKey::Code(winit::keyboard::KeyCode::NumpadAdd)),
+            None
+        );
+    }
+}
@@ -1,7 +1,9 @@
+pub mod converters;
 pub mod windows;
 
 pub use winit;
 
 pub mod prelude {
+    pub use crate::converters::*;
     pub use crate::windows::*;
 }
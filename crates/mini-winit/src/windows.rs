@@ -1,11 +1,25 @@
 use std::{collections::HashMap, sync::Arc};
 
 use mini_core::parking_lot::Mutex;
+use mini_math::{IVec2, UVec2};
+use mini_window::cursor::CursorIcon;
 use mini_window::{
     window::{ErasedWindow, Window, WindowId},
     window_wrapper::{RawHandleWrapper, RawHandleWrapperHolder, WindowWrapper},
 };
-use winit::{event_loop::ActiveEventLoop, window::Window as RawWinitWindow};
+use winit::{
+    dpi::{PhysicalPosition, PhysicalSize},
+    event_loop::ActiveEventLoop,
+    window::Window as RawWinitWindow,
+};
+
+use crate::converters::{convert_cursor_icon, convert_window_id};
+
+//窗口左上角的位置，窗口比显示器大时和显示器左上角对齐
+fn center_in_monitor(monitor_position: IVec2, monitor_size: UVec2, window_size: UVec2) -> IVec2 {
+    let offset = (monitor_size.as_ivec2() - window_size.as_ivec2()).max(IVec2::ZERO) / 2;
+    monitor_position + offset
+}
 
 #[derive(Debug)]
 pub struct WinitWindow {
@@ -21,10 +35,36 @@ pub struct WinitWindows {
 }
 
 impl WinitWindows {
-    pub fn create_window(&mut self, event_loop: &ActiveEventLoop, window: Window) {
-        let winit_window_attributes = RawWinitWindow::default_attributes();
+    pub fn create_window(&mut self, event_loop: &ActiveEventLoop, mut window: Window) -> WindowId {
+        let size = window.resolution.physical_size();
+        let mut winit_window_attributes = RawWinitWindow::default_attributes()
+            .with_title(window.title.clone())
+            .with_inner_size(PhysicalSize::new(size.x, size.y))
+            .with_resizable(window.resizable)
+            .with_decorations(window.decorations)
+            .with_cursor(convert_cursor_icon(window.cursor_icon));
+
+        // 没有指定位置时在主显示器上居中，获取不到显示器时由系统决定
+        let position = window.position.or_else(|| {
+            let monitor = event_loop.primary_monitor()?;
+            let monitor_position = monitor.position();
+            let monitor_size = monitor.size();
+            Some(center_in_monitor(
+                IVec2::new(monitor_position.x, monitor_position.y),
+                UVec2::new(monitor_size.width, monitor_size.height),
+                size,
+            ))
+        });
+        if let Some(position) = position {
+            winit_window_attributes = winit_window_attributes
+                .with_position(PhysicalPosition::new(position.x, position.y));
+        }
+
         let winit_window = event_loop.create_window(winit_window_attributes).unwrap();
-        let window_id = WindowId::new(winit_window.id().into());
+        let window_id = convert_window_id(winit_window.id());
+        window
+            .resolution
+            .set_scale_factor(winit_window.scale_factor() as f32);
 
         let window_wrapper = WindowWrapper::new(winit_window);
 
@@ -44,9 +84,46 @@ impl WinitWindows {
         };
 
         if self.primary.is_none() {
-            self.primary = Some(window_id.clone());
+            self.primary = Some(window_id);
         }
 
         self.windows.insert(window_id, window);
+
+        window_id
+    }
+
+    /// 移除窗口，移除的是主窗口时由剩余的窗口中的一个接替。
+    pub fn remove_window(&mut self, window_id: WindowId) -> Option<WinitWindow> {
+        let window = self.windows.remove(&window_id)?;
+
+        if self.primary == Some(window_id) {
+            self.primary = self.windows.keys().next().copied();
+        }
+
+        Some(window)
+    }
+
+    pub fn get_window(&self, window_id: WindowId) -> Option<&WinitWindow> {
+        self.windows.get(&window_id)
+    }
+
+    pub fn get_window_mut(&mut self, window_id: WindowId) -> Option<&mut WinitWindow> {
+        self.windows.get_mut(&window_id)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.windows.is_empty()
+    }
+
+    /// 修改窗口的鼠标图标，窗口不存在时返回 false。
+    pub fn set_cursor_icon(&mut self, window_id: WindowId, cursor_icon: CursorIcon) -> bool {
+        let Some(window) = self.windows.get_mut(&window_id) else {
+            return false;
+        };
+        window.erased_window.window.cursor_icon = cursor_icon;
+        window
+            .window_wrapper
+            .set_cursor(convert_cursor_icon(cursor_icon));
+        true
     }
 }